@@ -3,7 +3,7 @@
 use crate::algorithm::{AlgorithmError, AlgorithmErrorKind::TagInvalid};
 
 /// Asymmetric algorithms (RSA or ECC)
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[allow(non_camel_case_types)]
 #[repr(u8)]
 pub enum Algorithm {
@@ -0,0 +1,283 @@
+//! Elliptic curve domain parameters, along with the arithmetic needed to
+//! validate and decompress (public) SEC1 points.
+
+use crate::asymmetric;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+/// Domain parameters for a short Weierstrass curve (as hex strings)
+pub(crate) struct Params {
+    /// Field modulus
+    pub p: &'static str,
+
+    /// Curve coefficient `a`
+    pub a: &'static str,
+
+    /// Curve coefficient `b`
+    pub b: &'static str,
+
+    /// Base point x-coordinate (only needed by the `MockHsm`)
+    #[cfg_attr(not(feature = "mockhsm"), allow(dead_code))]
+    pub gx: &'static str,
+
+    /// Base point y-coordinate (only needed by the `MockHsm`)
+    #[cfg_attr(not(feature = "mockhsm"), allow(dead_code))]
+    pub gy: &'static str,
+
+    /// Order of the base point
+    pub n: &'static str,
+}
+
+impl Params {
+    /// Get the domain parameters for the given algorithm, if it's a short
+    /// Weierstrass curve
+    pub fn for_algorithm(algorithm: asymmetric::Algorithm) -> Option<&'static Self> {
+        Some(match algorithm {
+            asymmetric::Algorithm::EC_P224 => &NIST_P224,
+            asymmetric::Algorithm::EC_P256 => &NIST_P256,
+            asymmetric::Algorithm::EC_P384 => &NIST_P384,
+            asymmetric::Algorithm::EC_P521 => &NIST_P521,
+            asymmetric::Algorithm::EC_K256 => &SECP256K1,
+            asymmetric::Algorithm::EC_BP256 => &BRAINPOOL_P256,
+            asymmetric::Algorithm::EC_BP384 => &BRAINPOOL_P384,
+            asymmetric::Algorithm::EC_BP512 => &BRAINPOOL_P512,
+            _ => return None,
+        })
+    }
+}
+
+/// NIST P-224 domain parameters
+const NIST_P224: Params = Params {
+    p: "ffffffffffffffffffffffffffffffff000000000000000000000001",
+    a: "fffffffffffffffffffffffffffffffefffffffffffffffffffffffe",
+    b: "b4050a850c04b3abf54132565044b0b7d7bfd8ba270b39432355ffb4",
+    gx: "b70e0cbd6bb4bf7f321390b94a03c1d356c21122343280d6115c1d21",
+    gy: "bd376388b5f723fb4c22dfe6cd4375a05a07476444d5819985007e34",
+    n: "ffffffffffffffffffffffffffff16a2e0b8f03e13dd29455c5c2a3d",
+};
+
+/// NIST P-256 domain parameters
+const NIST_P256: Params = Params {
+    p: "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+    a: "ffffffff00000001000000000000000000000000fffffffffffffffffffffffc",
+    b: "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+    gx: "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+    gy: "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+    n: "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+};
+
+/// NIST P-384 domain parameters
+const NIST_P384: Params = Params {
+    p: "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe\
+        ffffffff0000000000000000ffffffff",
+    a: "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe\
+        ffffffff0000000000000000fffffffc",
+    b: "b3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875a\
+        c656398d8a2ed19d2a85c8edd3ec2aef",
+    gx: "aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a38\
+         5502f25dbf55296c3a545e3872760ab7",
+    gy: "3617de4a96262c6f5d9e98bf9292dc29f8f41dbd289a147ce9da3113b5f0b8c0\
+         0a60b1ce1d7e819d7a431d7c90ea0e5f",
+    n: "ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf\
+        581a0db248b0a77aecec196accc52973",
+};
+
+/// NIST P-521 domain parameters
+const NIST_P521: Params = Params {
+    p: "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+        ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+        ffff",
+    a: "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+        ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+        fffc",
+    b: "0051953eb9618e1c9a1f929a21a0b68540eea2da725b99b315f3b8b489918ef1\
+        09e156193951ec7e937b1652c0bd3bb1bf073573df883d2c34f1ef451fd46b50\
+        3f00",
+    gx: "00c6858e06b70404e9cd9e3ecb662395b4429c648139053fb521f828af606b4d\
+         3dbaa14b5e77efe75928fe1dc127a2ffa8de3348b3c1856a429bf97e7e31c2e5\
+         bd66",
+    gy: "011839296a789a3bc0045c8a5fb42c7d1bd998f54449579b446817afbd17273e\
+         662c97ee72995ef42640c550b9013fad0761353c7086a272c24088be94769fd1\
+         6650",
+    n: "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+        fa51868783bf2f966b7fcc0148f709a5d03bb5c9b8899c47aebb6fb71e913864\
+        09",
+};
+
+/// secp256k1 domain parameters
+const SECP256K1: Params = Params {
+    p: "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+    a: "00",
+    b: "07",
+    gx: "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+    gy: "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+    n: "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+};
+
+/// brainpoolP256r1 domain parameters
+const BRAINPOOL_P256: Params = Params {
+    p: "a9fb57dba1eea9bc3e660a909d838d726e3bf623d52620282013481d1f6e5377",
+    a: "7d5a0975fc2c3057eef67530417affe7fb8055c126dc5c6ce94a4b44f330b5d9",
+    b: "26dc5c6ce94a4b44f330b5d9bbd77cbf958416295cf7e1ce6bccdc18ff8c07b6",
+    gx: "8bd2aeb9cb7e57cb2c4b482ffc81b7afb9de27e1e3bd23c23a4453bd9ace3262",
+    gy: "547ef835c3dac4fd97f8461a14611dc9c27745132ded8e545c1d54c72f046997",
+    n: "a9fb57dba1eea9bc3e660a909d838d718c397aa3b561a6f7901e0e82974856a7",
+};
+
+/// brainpoolP384r1 domain parameters
+const BRAINPOOL_P384: Params = Params {
+    p: "8cb91e82a3386d280f5d6f7e50e641df152f7109ed5456b412b1da197fb71123\
+        acd3a729901d1a71874700133107ec53",
+    a: "7bc382c63d8c150c3c72080ace05afa0c2bea28e4fb22787139165efba91f90f\
+        8aa5814a503ad4eb04a8c7dd22ce2826",
+    b: "04a8c7dd22ce28268b39b55416f0447c2fb77de107dcd2a62e880ea53eeb62d5\
+        7cb4390295dbc9943ab78696fa504c11",
+    gx: "1d1c64f068cf45ffa2a63a81b7c13f6b8847a3e77ef14fe3db7fcafe0cbd10e8\
+         e826e03436d646aaef87b2e247d4af1e",
+    gy: "8abe1d7520f9c2a45cb1eb8e95cfd55262b70b29feec5864e19c054ff9912928\
+         0e4646217791811142820341263c5315",
+    n: "8cb91e82a3386d280f5d6f7e50e641df152f7109ed5456b31f166e6cac0425a7\
+        cf3ab6af6b7fc3103b883202e9046565",
+};
+
+/// brainpoolP512r1 domain parameters
+const BRAINPOOL_P512: Params = Params {
+    p: "aadd9db8dbe9c48b3fd4e6ae33c9fc07cb308db3b3c9d20ed6639cca70330871\
+        7d4d9b009bc66842aecda12ae6a380e62881ff2f2d82c68528aa6056583a48f3",
+    a: "7830a3318b603b89e2327145ac234cc594cbdd8d3df91610a83441caea9863bc\
+        2ded5d5aa8253aa10a2ef1c98b9ac8b57f1117a72bf2c7b9e7c1ac4d77fc94ca",
+    b: "3df91610a83441caea9863bc2ded5d5aa8253aa10a2ef1c98b9ac8b57f1117a7\
+        2bf2c7b9e7c1ac4d77fc94cadc083e67984050b75ebae5dd2809bd638016f723",
+    gx: "81aee4bdd82ed9645a21322e9c4c6a9385ed9f70b5d916c1b43b62eef4d0098e\
+         ff3b1f78e2d0d48d50d1687b93b97d5f7c6d5047406a5e688b352209bcb9f822",
+    gy: "7dde385d566332ecc0eabfa9cf7822fdf209f70024a57b1aa000c55b881f8111\
+         b2dcde494a5f485e5bca4bd88a2763aed1ca2b2fa8f0540678cd1e0f3ad80892",
+    n: "aadd9db8dbe9c48b3fd4e6ae33c9fc07cb308db3b3c9d20ed6639cca70330870\
+        553e5c414ca92619418661197fac10471db1d381085ddaddb58796829ca90069",
+};
+
+/// Short Weierstrass curve: `y^2 = x^3 + ax + b (mod p)`.
+///
+/// This is a textbook implementation which makes no attempt to run in
+/// constant time, and therefore must only ever be used with public data
+/// (i.e. validating and decompressing points). It deliberately provides no
+/// scalar multiplication.
+#[derive(Clone, Debug)]
+pub(crate) struct Curve {
+    /// Field modulus
+    p: BigUint,
+
+    /// Curve coefficient `a`
+    a: BigUint,
+
+    /// Curve coefficient `b`
+    b: BigUint,
+
+    /// Order of the base point
+    n: BigUint,
+
+    /// Size of a field element or scalar in bytes
+    size: usize,
+}
+
+impl Curve {
+    /// Get the curve for the given algorithm, if it's a short Weierstrass curve
+    pub fn new(algorithm: asymmetric::Algorithm) -> Option<Self> {
+        let params = Params::for_algorithm(algorithm)?;
+
+        Some(Self {
+            p: parse_hex(params.p),
+            a: parse_hex(params.a),
+            b: parse_hex(params.b),
+            n: parse_hex(params.n),
+            size: algorithm.key_len(),
+        })
+    }
+
+    /// Is the given affine point on the curve?
+    pub fn is_on_curve(&self, x: &BigUint, y: &BigUint) -> bool {
+        x < &self.p && y < &self.p && (y * y) % &self.p == self.rhs(x)
+    }
+
+    /// Can compressed points on this curve be decompressed? (i.e. is the
+    /// field modulus `3 (mod 4)`)
+    pub fn supports_decompression(&self) -> bool {
+        &self.p % 4u32 == BigUint::from(3u32)
+    }
+
+    /// Compute the y-coordinate of the point with the given x-coordinate and
+    /// parity, returning `None` if there is no such point.
+    ///
+    /// Always `None` for curves which don't support decompression.
+    pub fn y_coordinate(&self, x: &BigUint, odd: bool) -> Option<BigUint> {
+        if x >= &self.p {
+            return None;
+        }
+
+        let y = self.sqrt(&self.rhs(x))?;
+
+        // Zero is its own negation, so there's no odd point with this x
+        if y.is_zero() {
+            return if odd { None } else { Some(y) };
+        }
+
+        if (&y % 2u32 == BigUint::one()) == odd {
+            Some(y)
+        } else {
+            Some(&self.p - y)
+        }
+    }
+
+    /// Is the given (fixed-size, big endian) scalar in the range `[1, n)`?
+    pub fn is_valid_scalar(&self, scalar: &[u8]) -> bool {
+        let k = BigUint::from_bytes_be(scalar);
+        scalar.len() == self.size && !k.is_zero() && k < self.n
+    }
+
+    /// Compute `x^3 + ax + b (mod p)`
+    fn rhs(&self, x: &BigUint) -> BigUint {
+        (x * x * x + &self.a * x + &self.b) % &self.p
+    }
+
+    /// Compute a square root modulo `p` (which must be `3 (mod 4)`), if one
+    /// exists
+    fn sqrt(&self, n: &BigUint) -> Option<BigUint> {
+        if !self.supports_decompression() {
+            return None;
+        }
+
+        let root = n.modpow(&((&self.p + BigUint::one()) >> 2), &self.p);
+
+        if (&root * &root) % &self.p == *n {
+            Some(root)
+        } else {
+            None
+        }
+    }
+}
+
+/// Parse a hex-encoded curve parameter
+pub(crate) fn parse_hex(hex: &str) -> BigUint {
+    BigUint::parse_bytes(hex.as_bytes(), 16).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn y_coordinate_zero_test() {
+        // Toy curve `y^2 = x^3 + 6 (mod 7)`, which has the point (1, 0)
+        let curve = Curve {
+            p: BigUint::from(7u32),
+            a: BigUint::zero(),
+            b: BigUint::from(6u32),
+            n: BigUint::from(2u32),
+            size: 1,
+        };
+
+        let x = BigUint::one();
+        assert_eq!(curve.y_coordinate(&x, false), Some(BigUint::zero()));
+        assert_eq!(curve.y_coordinate(&x, true), None);
+    }
+}
@@ -0,0 +1,53 @@
+//! Private key import error types
+
+use crate::{
+    asymmetric::{PublicKeyError, PublicKeyErrorKind},
+    client::{ClientError, ClientErrorKind},
+    error::Error,
+};
+use std::error::Error as StdError;
+
+/// Private key import errors
+pub type ImportError = Error<ImportErrorKind>;
+
+/// Private key import error kinds
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Fail)]
+pub enum ImportErrorKind {
+    /// Key algorithm isn't supported by this library or by the device
+    #[fail(display = "unsupported algorithm")]
+    AlgorithmUnsupported,
+
+    /// Error communicating with the HSM
+    #[fail(display = "client error: {}", kind)]
+    ClientError {
+        /// Client error kind
+        kind: ClientErrorKind,
+    },
+
+    /// Malformed encoding (DER, PEM, OpenSSH, etc)
+    #[fail(display = "invalid format")]
+    FormatInvalid,
+
+    /// Key is the wrong size for its algorithm, or is otherwise invalid
+    #[fail(display = "invalid key")]
+    KeyInvalid,
+}
+
+impl From<ClientError> for ImportError {
+    fn from(err: ClientError) -> Self {
+        let kind = ImportErrorKind::ClientError { kind: err.kind() };
+        err!(kind, err.description())
+    }
+}
+
+impl From<PublicKeyError> for ImportError {
+    fn from(err: PublicKeyError) -> Self {
+        let kind = match err.kind() {
+            PublicKeyErrorKind::AlgorithmUnsupported => ImportErrorKind::AlgorithmUnsupported,
+            PublicKeyErrorKind::FormatInvalid => ImportErrorKind::FormatInvalid,
+            PublicKeyErrorKind::KeyInvalid => ImportErrorKind::KeyInvalid,
+        };
+
+        err!(kind, err.description())
+    }
+}
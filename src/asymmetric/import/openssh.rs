@@ -0,0 +1,155 @@
+//! OpenSSH private keys (i.e. the `openssh-key-v1` format described in
+//! OpenSSH's `PROTOCOL.key`)
+
+use super::{
+    ImportError,
+    ImportErrorKind::{AlgorithmUnsupported, FormatInvalid},
+    PrivateKey,
+};
+use crate::{
+    asymmetric::{self, PublicKey},
+    ssh::encoding::Decoder,
+};
+
+/// Magic string at the beginning of every OpenSSH private key
+const AUTH_MAGIC: &[u8] = b"openssh-key-v1\0";
+
+/// Cipher and KDF name for unencrypted keys
+const NONE: &[u8] = b"none";
+
+/// Decode the (base64-decoded) contents of an OpenSSH private key
+pub(super) fn decode(bytes: &[u8]) -> Result<PrivateKey, ImportError> {
+    ensure!(
+        bytes.starts_with(AUTH_MAGIC),
+        FormatInvalid,
+        "not an openssh-key-v1 private key"
+    );
+
+    let mut decoder = Decoder::new(&bytes[AUTH_MAGIC.len()..]);
+    let cipher_name = decoder.string().map_err(malformed)?;
+    let kdf_name = decoder.string().map_err(malformed)?;
+    let _kdf_options = decoder.string().map_err(malformed)?;
+
+    ensure!(
+        cipher_name == NONE && kdf_name == NONE,
+        AlgorithmUnsupported,
+        "encrypted OpenSSH private keys are unsupported"
+    );
+
+    ensure!(
+        decoder.u32().map_err(malformed)? == 1,
+        FormatInvalid,
+        "expected exactly one key in OpenSSH private key"
+    );
+
+    let public_key = PublicKey::from_ssh_wire_format(decoder.string().map_err(malformed)?)?;
+    let private_section = decoder.string().map_err(malformed)?;
+
+    ensure!(
+        decoder.remaining().is_empty(),
+        FormatInvalid,
+        "trailing data after OpenSSH private key"
+    );
+
+    let mut decoder = Decoder::new(private_section);
+    let check_int = decoder.u32().map_err(malformed)?;
+
+    ensure!(
+        decoder.u32().map_err(malformed)? == check_int,
+        FormatInvalid,
+        "OpenSSH private key check failed"
+    );
+
+    let key_type = decoder.string().map_err(malformed)?;
+
+    let mut private_key = match public_key.algorithm {
+        asymmetric::Algorithm::RSA_2048
+        | asymmetric::Algorithm::RSA_3072
+        | asymmetric::Algorithm::RSA_4096 => {
+            ensure!(key_type == b"ssh-rsa", FormatInvalid, "key type mismatch");
+
+            let modulus = decoder.mpint().map_err(malformed)?;
+            let exponent = decoder.mpint().map_err(malformed)?;
+            let _private_exponent = decoder.mpint().map_err(malformed)?;
+            let _coefficient = decoder.mpint().map_err(malformed)?;
+            let p = decoder.mpint().map_err(malformed)?;
+            let q = decoder.mpint().map_err(malformed)?;
+
+            ensure!(
+                modulus == public_key.as_slice(),
+                FormatInvalid,
+                "public and private RSA keys don't match"
+            );
+
+            PrivateKey::rsa(modulus, exponent, p, q)?
+        }
+        asymmetric::Algorithm::Ed25519 => {
+            ensure!(
+                key_type == b"ssh-ed25519",
+                FormatInvalid,
+                "key type mismatch"
+            );
+
+            let point = decoder.string().map_err(malformed)?;
+
+            // The "private key" is the seed concatenated with the public key
+            let keypair = decoder.string().map_err(malformed)?;
+            let seed_len = asymmetric::Algorithm::Ed25519.key_len();
+
+            ensure!(
+                point == public_key.as_slice()
+                    && keypair.len() == seed_len * 2
+                    && &keypair[seed_len..] == point,
+                FormatInvalid,
+                "public and private Ed25519 keys don't match"
+            );
+
+            PrivateKey::ed25519(&keypair[..seed_len])?
+        }
+        algorithm => {
+            ensure!(
+                key_type.starts_with(b"ecdsa-sha2-"),
+                FormatInvalid,
+                "key type mismatch"
+            );
+
+            let _curve = decoder.string().map_err(malformed)?;
+            let point = decoder.string().map_err(malformed)?;
+            let scalar = decoder.mpint().map_err(malformed)?;
+
+            ensure!(
+                PublicKey::from_sec1(algorithm, point)? == public_key,
+                FormatInvalid,
+                "public and private ECDSA keys don't match"
+            );
+
+            PrivateKey::ecc(algorithm, scalar)?
+        }
+    };
+
+    private_key.public_key = Some(public_key);
+
+    let _comment = decoder.string().map_err(malformed)?;
+
+    // Padding is the sequence 1, 2, 3, ... up to the cipher block size
+    ensure!(
+        decoder
+            .remaining()
+            .iter()
+            .enumerate()
+            .all(|(i, &byte)| usize::from(byte) == i + 1),
+        FormatInvalid,
+        "invalid padding in OpenSSH private key"
+    );
+
+    Ok(private_key)
+}
+
+/// Error for malformed SSH wire format data
+fn malformed<E: ToString>(e: E) -> ImportError {
+    err!(
+        FormatInvalid,
+        "malformed OpenSSH private key: {}",
+        e.to_string()
+    )
+}
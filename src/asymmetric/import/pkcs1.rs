@@ -0,0 +1,58 @@
+//! PKCS#1 RSA private keys (RFC 8017 Appendix A.1.2)
+
+use super::{
+    ImportError,
+    ImportErrorKind::{AlgorithmUnsupported, FormatInvalid},
+    PrivateKey,
+};
+use crate::der;
+
+/// Number of `INTEGER` fields in a two-prime `RSAPrivateKey`, after the version
+const NUM_FIELDS: usize = 8;
+
+/// Decode a DER `RSAPrivateKey`
+pub(super) fn decode(bytes: &[u8]) -> Result<PrivateKey, ImportError> {
+    let mut outer = der::Decoder::new(bytes);
+    let rsa_private_key = outer.read(der::SEQUENCE);
+    ensure!(
+        outer.is_empty(),
+        FormatInvalid,
+        "trailing data after PKCS#1 key"
+    );
+
+    let mut rsa_private_key = der::Decoder::new(rsa_private_key.ok_or_else(malformed)?);
+    let version = rsa_private_key.read(der::INTEGER).ok_or_else(malformed)?;
+
+    // Version 1 is for multi-prime keys, which the YubiHSM 2 doesn't support
+    ensure!(
+        version == [0],
+        AlgorithmUnsupported,
+        "unsupported PKCS#1 private key version (multi-prime?)"
+    );
+
+    // modulus, publicExponent, privateExponent, prime1, prime2,
+    // exponent1, exponent2, coefficient
+    let mut fields = Vec::with_capacity(NUM_FIELDS);
+
+    for _ in 0..NUM_FIELDS {
+        fields.push(
+            rsa_private_key
+                .read(der::INTEGER)
+                .and_then(der::decode_integer)
+                .ok_or_else(malformed)?,
+        );
+    }
+
+    ensure!(
+        rsa_private_key.is_empty(),
+        FormatInvalid,
+        "trailing data in PKCS#1 private key"
+    );
+
+    PrivateKey::rsa(fields[0], fields[1], fields[3], fields[4])
+}
+
+/// Error for malformed DER
+fn malformed() -> ImportError {
+    err!(FormatInvalid, "malformed PKCS#1 private key")
+}
@@ -0,0 +1,98 @@
+//! PKCS#8 private keys (RFC 5208 and RFC 5958)
+
+use super::{
+    pkcs1, sec1, ImportError,
+    ImportErrorKind::{AlgorithmUnsupported, FormatInvalid},
+    PrivateKey,
+};
+use crate::{asymmetric::oid, der};
+
+/// Decode a DER `PrivateKeyInfo` or `OneAsymmetricKey`
+pub(super) fn decode(bytes: &[u8]) -> Result<PrivateKey, ImportError> {
+    let mut outer = der::Decoder::new(bytes);
+    let private_key_info = outer.read(der::SEQUENCE);
+    ensure!(
+        outer.is_empty(),
+        FormatInvalid,
+        "trailing data after PKCS#8 key"
+    );
+
+    let mut private_key_info = der::Decoder::new(private_key_info.ok_or_else(malformed)?);
+    let version = private_key_info.read(der::INTEGER).ok_or_else(malformed)?;
+
+    // v1 (`PrivateKeyInfo`) or v2 (`OneAsymmetricKey`)
+    ensure!(
+        version.len() == 1 && version[0] <= 1,
+        FormatInvalid,
+        "unsupported PKCS#8 version"
+    );
+
+    let mut algorithm_identifier =
+        der::Decoder::new(private_key_info.read(der::SEQUENCE).ok_or_else(malformed)?);
+    let private_key = private_key_info
+        .read(der::OCTET_STRING)
+        .ok_or_else(malformed)?;
+
+    // Skip the optional (context-specific) attributes and public key
+    while !private_key_info.is_empty() {
+        let (tag, _) = private_key_info.read_any().ok_or_else(malformed)?;
+        ensure!(
+            der::is_context_specific(tag),
+            FormatInvalid,
+            "trailing data in PKCS#8 key"
+        );
+    }
+
+    let algorithm_oid = algorithm_identifier
+        .read(der::OBJECT_IDENTIFIER)
+        .ok_or_else(malformed)?;
+
+    if algorithm_oid == oid::RSA_ENCRYPTION {
+        // Parameters must be NULL (or absent)
+        if !algorithm_identifier.is_empty() {
+            ensure!(
+                algorithm_identifier.read(der::NULL).map(<[u8]>::is_empty) == Some(true),
+                FormatInvalid,
+                "invalid rsaEncryption parameters"
+            );
+        }
+
+        pkcs1::decode(private_key)
+    } else if algorithm_oid == oid::EC_PUBLIC_KEY {
+        let curve_oid = algorithm_identifier
+            .read(der::OBJECT_IDENTIFIER)
+            .ok_or_else(|| err!(FormatInvalid, "missing named curve"))?;
+
+        let algorithm = oid::curve_algorithm(curve_oid)
+            .ok_or_else(|| err!(AlgorithmUnsupported, "unsupported elliptic curve"))?;
+
+        sec1::decode(private_key, Some(algorithm))
+    } else if algorithm_oid == oid::ED25519 {
+        ensure!(
+            algorithm_identifier.is_empty(),
+            FormatInvalid,
+            "unexpected Ed25519 parameters"
+        );
+
+        // The seed is wrapped in a `CurvePrivateKey` (RFC 8410 Section 7)
+        let mut curve_private_key = der::Decoder::new(private_key);
+        let seed = curve_private_key
+            .read(der::OCTET_STRING)
+            .ok_or_else(malformed)?;
+
+        ensure!(
+            curve_private_key.is_empty(),
+            FormatInvalid,
+            "trailing data in Ed25519 private key"
+        );
+
+        PrivateKey::ed25519(seed)
+    } else {
+        fail!(AlgorithmUnsupported, "unsupported private key algorithm");
+    }
+}
+
+/// Error for malformed DER
+fn malformed() -> ImportError {
+    err!(FormatInvalid, "malformed PKCS#8 private key")
+}
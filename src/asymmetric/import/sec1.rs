@@ -0,0 +1,107 @@
+//! SEC1 elliptic curve private keys (RFC 5915)
+
+use super::{
+    ImportError,
+    ImportErrorKind::{AlgorithmUnsupported, FormatInvalid},
+    PrivateKey,
+};
+use crate::{
+    asymmetric::{self, oid, PublicKey},
+    der,
+};
+
+/// `ECPrivateKey` version
+const VERSION: u8 = 1;
+
+/// Tag of the (explicitly tagged) `parameters` field: `[0]`
+const PARAMETERS_TAG: u8 = 0xa0;
+
+/// Tag of the (explicitly tagged) `publicKey` field: `[1]`
+const PUBLIC_KEY_TAG: u8 = 0xa1;
+
+/// Decode a DER `ECPrivateKey`. The curve is taken from the key's parameters
+/// unless it's already known (i.e. from a PKCS#8 algorithm identifier).
+pub(super) fn decode(
+    bytes: &[u8],
+    algorithm: Option<asymmetric::Algorithm>,
+) -> Result<PrivateKey, ImportError> {
+    let mut outer = der::Decoder::new(bytes);
+    let ec_private_key = outer.read(der::SEQUENCE);
+    ensure!(
+        outer.is_empty(),
+        FormatInvalid,
+        "trailing data after SEC1 key"
+    );
+
+    let mut ec_private_key = der::Decoder::new(ec_private_key.ok_or_else(malformed)?);
+    let version = ec_private_key.read(der::INTEGER).ok_or_else(malformed)?;
+
+    ensure!(
+        version == [VERSION],
+        FormatInvalid,
+        "unsupported SEC1 private key version"
+    );
+
+    let scalar = ec_private_key
+        .read(der::OCTET_STRING)
+        .ok_or_else(malformed)?;
+
+    let mut curve = algorithm;
+
+    if ec_private_key.peek_tag() == Some(PARAMETERS_TAG) {
+        let mut parameters =
+            der::Decoder::new(ec_private_key.read(PARAMETERS_TAG).ok_or_else(malformed)?);
+
+        let curve_oid = parameters.read(der::OBJECT_IDENTIFIER).ok_or_else(|| {
+            err!(
+                AlgorithmUnsupported,
+                "curve parameters must be a named curve"
+            )
+        })?;
+
+        let named_curve = oid::curve_algorithm(curve_oid)
+            .ok_or_else(|| err!(AlgorithmUnsupported, "unsupported elliptic curve"))?;
+
+        ensure!(
+            curve.map(|alg| alg == named_curve) != Some(false),
+            FormatInvalid,
+            "SEC1 parameters don't match PKCS#8 algorithm"
+        );
+
+        curve = Some(named_curve);
+    }
+
+    let public_key = if ec_private_key.peek_tag() == Some(PUBLIC_KEY_TAG) {
+        let mut public_key =
+            der::Decoder::new(ec_private_key.read(PUBLIC_KEY_TAG).ok_or_else(malformed)?);
+
+        Some(
+            public_key
+                .read(der::BIT_STRING)
+                .and_then(der::decode_bit_string)
+                .ok_or_else(malformed)?,
+        )
+    } else {
+        None
+    };
+
+    ensure!(
+        ec_private_key.is_empty(),
+        FormatInvalid,
+        "trailing data in SEC1 private key"
+    );
+
+    let algorithm = curve.ok_or_else(|| err!(FormatInvalid, "missing curve parameters"))?;
+    let mut private_key = PrivateKey::ecc(algorithm, scalar)?;
+
+    if let Some(point) = public_key {
+        private_key.public_key = Some(PublicKey::from_sec1(algorithm, point)?);
+    }
+
+    Ok(private_key)
+}
+
+/// Error for malformed DER
+fn malformed() -> ImportError {
+    err!(FormatInvalid, "malformed SEC1 private key")
+}
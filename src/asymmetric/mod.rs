@@ -8,7 +8,13 @@
 
 mod algorithm;
 pub(crate) mod commands;
+pub(crate) mod curve;
+pub mod import;
+pub(crate) mod oid;
 mod public_key;
 
-pub use self::{algorithm::Algorithm, public_key::PublicKey};
+pub use self::{
+    algorithm::Algorithm,
+    public_key::{Jwk, PublicKey, PublicKeyError, PublicKeyErrorKind},
+};
 pub use signatory::Signer;
@@ -0,0 +1,72 @@
+//! DER-encoded object identifiers for asymmetric key algorithms
+
+use crate::asymmetric;
+
+/// `rsaEncryption` (1.2.840.113549.1.1.1)
+pub(crate) const RSA_ENCRYPTION: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+
+/// `id-ecPublicKey` (1.2.840.10045.2.1)
+pub(crate) const EC_PUBLIC_KEY: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+
+/// `id-Ed25519` (1.3.101.112)
+pub(crate) const ED25519: &[u8] = &[0x2b, 0x65, 0x70];
+
+/// Named curve OIDs for ECC algorithms
+const CURVES: &[(asymmetric::Algorithm, &[u8])] = &[
+    // secp224r1 (1.3.132.0.33)
+    (
+        asymmetric::Algorithm::EC_P224,
+        &[0x2b, 0x81, 0x04, 0x00, 0x21],
+    ),
+    // prime256v1 (1.2.840.10045.3.1.7)
+    (
+        asymmetric::Algorithm::EC_P256,
+        &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07],
+    ),
+    // secp384r1 (1.3.132.0.34)
+    (
+        asymmetric::Algorithm::EC_P384,
+        &[0x2b, 0x81, 0x04, 0x00, 0x22],
+    ),
+    // secp521r1 (1.3.132.0.35)
+    (
+        asymmetric::Algorithm::EC_P521,
+        &[0x2b, 0x81, 0x04, 0x00, 0x23],
+    ),
+    // secp256k1 (1.3.132.0.10)
+    (
+        asymmetric::Algorithm::EC_K256,
+        &[0x2b, 0x81, 0x04, 0x00, 0x0a],
+    ),
+    // brainpoolP256r1 (1.3.36.3.3.2.8.1.1.7)
+    (
+        asymmetric::Algorithm::EC_BP256,
+        &[0x2b, 0x24, 0x03, 0x03, 0x02, 0x08, 0x01, 0x01, 0x07],
+    ),
+    // brainpoolP384r1 (1.3.36.3.3.2.8.1.1.11)
+    (
+        asymmetric::Algorithm::EC_BP384,
+        &[0x2b, 0x24, 0x03, 0x03, 0x02, 0x08, 0x01, 0x01, 0x0b],
+    ),
+    // brainpoolP512r1 (1.3.36.3.3.2.8.1.1.13)
+    (
+        asymmetric::Algorithm::EC_BP512,
+        &[0x2b, 0x24, 0x03, 0x03, 0x02, 0x08, 0x01, 0x01, 0x0d],
+    ),
+];
+
+/// Get the named curve OID for the given ECC algorithm
+pub(crate) fn curve(algorithm: asymmetric::Algorithm) -> Option<&'static [u8]> {
+    CURVES
+        .iter()
+        .find(|(alg, _)| *alg == algorithm)
+        .map(|(_, oid)| *oid)
+}
+
+/// Get the ECC algorithm for the given named curve OID
+pub(crate) fn curve_algorithm(oid: &[u8]) -> Option<asymmetric::Algorithm> {
+    CURVES
+        .iter()
+        .find(|(_, curve_oid)| *curve_oid == oid)
+        .map(|(alg, _)| *alg)
+}
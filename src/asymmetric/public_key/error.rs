@@ -0,0 +1,22 @@
+//! Public key encoding error types
+
+use crate::error::Error;
+
+/// Public key encoding/decoding errors
+pub type PublicKeyError = Error<PublicKeyErrorKind>;
+
+/// Public key encoding/decoding error kinds
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Fail)]
+pub enum PublicKeyErrorKind {
+    /// Key algorithm is not supported by the requested format
+    #[fail(display = "unsupported algorithm")]
+    AlgorithmUnsupported,
+
+    /// Malformed encoding (DER, PEM, OpenSSH, JWK, etc)
+    #[fail(display = "invalid format")]
+    FormatInvalid,
+
+    /// Key is the wrong size for its algorithm, or isn't a valid point
+    #[fail(display = "invalid key")]
+    KeyInvalid,
+}
@@ -0,0 +1,193 @@
+//! JSON Web Key (RFC 7517) encoding of public keys, using the key types from
+//! RFC 7518 (RSA, EC), RFC 8037 (OKP) and RFC 8812 (secp256k1)
+
+use super::{
+    sec1, PublicKey, PublicKeyError,
+    PublicKeyErrorKind::{AlgorithmUnsupported, FormatInvalid},
+    RSA_PUBLIC_EXPONENT,
+};
+use crate::asymmetric;
+use subtle_encoding::base64;
+
+/// JSON Web Key containing a public key.
+///
+/// This type can be (de)serialized with any `serde` format, e.g. JSON.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Jwk {
+    /// Key type (`RSA`, `EC`, or `OKP`)
+    pub kty: String,
+
+    /// Curve name (`EC` and `OKP` keys only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+
+    /// RSA modulus (base64url)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+
+    /// RSA public exponent (base64url)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+
+    /// Public x-coordinate (`EC`) or public key (`OKP`) (base64url)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+
+    /// Public y-coordinate (`EC` only) (base64url)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+}
+
+impl Jwk {
+    /// Serialize this key as JSON
+    #[cfg(feature = "serde_json")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Parse a key from JSON
+    #[cfg(feature = "serde_json")]
+    pub fn from_json(json: &str) -> Result<Self, PublicKeyError> {
+        serde_json::from_str(json).map_err(|e| err!(FormatInvalid, "invalid JWK: {}", e))
+    }
+}
+
+/// Get the JWK curve name for the given algorithm
+fn curve_name(algorithm: asymmetric::Algorithm) -> Option<&'static str> {
+    match algorithm {
+        asymmetric::Algorithm::EC_P256 => Some("P-256"),
+        asymmetric::Algorithm::EC_P384 => Some("P-384"),
+        asymmetric::Algorithm::EC_P521 => Some("P-521"),
+        asymmetric::Algorithm::EC_K256 => Some("secp256k1"),
+        asymmetric::Algorithm::Ed25519 => Some("Ed25519"),
+        _ => None,
+    }
+}
+
+/// Encode a public key as a JWK
+pub(super) fn encode(public_key: &PublicKey) -> Result<Jwk, PublicKeyError> {
+    Ok(match public_key.algorithm {
+        asymmetric::Algorithm::RSA_2048
+        | asymmetric::Algorithm::RSA_3072
+        | asymmetric::Algorithm::RSA_4096 => Jwk {
+            kty: "RSA".to_owned(),
+            n: Some(encode_base64url(&public_key.bytes)),
+            e: Some(encode_base64url(RSA_PUBLIC_EXPONENT)),
+            ..Jwk::default()
+        },
+        asymmetric::Algorithm::Ed25519 => Jwk {
+            kty: "OKP".to_owned(),
+            crv: curve_name(public_key.algorithm).map(str::to_owned),
+            x: Some(encode_base64url(&public_key.bytes)),
+            ..Jwk::default()
+        },
+        algorithm => {
+            let crv = curve_name(algorithm).ok_or_else(|| {
+                err!(
+                    AlgorithmUnsupported,
+                    "no registered JWK curve for {:?}",
+                    algorithm
+                )
+            })?;
+
+            let (x, y) = public_key.bytes.split_at(algorithm.key_len());
+
+            Jwk {
+                kty: "EC".to_owned(),
+                crv: Some(crv.to_owned()),
+                x: Some(encode_base64url(x)),
+                y: Some(encode_base64url(y)),
+                ..Jwk::default()
+            }
+        }
+    })
+}
+
+/// Decode a public key from a JWK
+pub(super) fn decode(jwk: &Jwk) -> Result<PublicKey, PublicKeyError> {
+    match jwk.kty.as_str() {
+        "RSA" => {
+            let exponent = decode_param(&jwk.e, "e")?;
+
+            ensure!(
+                exponent == RSA_PUBLIC_EXPONENT,
+                AlgorithmUnsupported,
+                "unsupported RSA public exponent"
+            );
+
+            PublicKey::rsa_from_modulus(&decode_param(&jwk.n, "n")?)
+        }
+        "EC" | "OKP" => {
+            let crv = jwk
+                .crv
+                .as_ref()
+                .ok_or_else(|| err!(FormatInvalid, "missing JWK parameter: crv"))?;
+
+            let algorithm = [
+                asymmetric::Algorithm::EC_P256,
+                asymmetric::Algorithm::EC_P384,
+                asymmetric::Algorithm::EC_P521,
+                asymmetric::Algorithm::EC_K256,
+                asymmetric::Algorithm::Ed25519,
+            ]
+            .iter()
+            .cloned()
+            .find(|&alg| curve_name(alg) == Some(crv.as_str()))
+            .ok_or_else(|| err!(AlgorithmUnsupported, "unsupported JWK curve: {}", crv))?;
+
+            let x = decode_param(&jwk.x, "x")?;
+
+            if algorithm == asymmetric::Algorithm::Ed25519 {
+                ensure!(jwk.kty == "OKP", FormatInvalid, "Ed25519 keys must be OKP");
+                return PublicKey::new(algorithm, x);
+            }
+
+            ensure!(jwk.kty == "EC", FormatInvalid, "{} keys must be EC", crv);
+
+            let mut point = vec![sec1::UNCOMPRESSED_TAG];
+            point.extend_from_slice(&x);
+            point.extend_from_slice(&decode_param(&jwk.y, "y")?);
+            sec1::decode(algorithm, &point)
+        }
+        kty => fail!(AlgorithmUnsupported, "unsupported JWK key type: {}", kty),
+    }
+}
+
+/// Decode a required base64url-encoded parameter
+fn decode_param(param: &Option<String>, name: &str) -> Result<Vec<u8>, PublicKeyError> {
+    let encoded = param
+        .as_ref()
+        .ok_or_else(|| err!(FormatInvalid, "missing JWK parameter: {}", name))?;
+
+    decode_base64url(encoded).ok_or_else(|| {
+        err!(
+            FormatInvalid,
+            "invalid base64url in JWK parameter: {}",
+            name
+        )
+    })
+}
+
+/// Encode bytes as unpadded base64url (RFC 4648 Section 5)
+fn encode_base64url(bytes: &[u8]) -> String {
+    String::from_utf8(base64::encode(bytes))
+        .unwrap()
+        .trim_end_matches('=')
+        .replace('+', "-")
+        .replace('/', "_")
+}
+
+/// Decode unpadded base64url (RFC 4648 Section 5)
+fn decode_base64url(encoded: &str) -> Option<Vec<u8>> {
+    if encoded.contains(|c| c == '+' || c == '/' || c == '=') {
+        return None;
+    }
+
+    let mut standard = encoded.replace('-', "+").replace('_', "/");
+
+    while standard.len() % 4 != 0 {
+        standard.push('=');
+    }
+
+    base64::decode(standard.as_bytes()).ok()
+}
@@ -0,0 +1,278 @@
+//! Public keys for use with asymmetric cryptography / signatures, along with
+//! encoders and parsers for common public key formats
+
+mod error;
+mod jwk;
+mod openssh;
+mod sec1;
+mod spki;
+
+pub use self::{
+    error::{PublicKeyError, PublicKeyErrorKind},
+    jwk::Jwk,
+};
+
+use self::PublicKeyErrorKind::KeyInvalid;
+use crate::{
+    asymmetric::{self, curve::Curve},
+    ecdsa, ed25519, rsa,
+};
+use num_bigint::BigUint;
+use signatory::{ecdsa::curve::WeierstrassCurveKind, generic_array::GenericArray};
+
+/// Big endian encoding of the RSA public exponent (i.e. 65537)
+const RSA_PUBLIC_EXPONENT: &[u8] = &[0x01, 0x00, 0x01];
+
+/// Response from `command::get_public_key`
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PublicKey {
+    /// Algorithm of the public key
+    pub algorithm: asymmetric::Algorithm,
+
+    /// The public key in raw bytes. Keys have the following structure:
+    ///
+    /// - RSA: Public modulus N (0x100 | 0x200 | 0x400 bytes)
+    /// - ECC (non-Ed25519):
+    ///   - Public point X (0x20 | 0x30 | 0x40 | 0x42 bytes)
+    ///   - Public point Y (0x20 | 0x30 | 0x40 | 0x42 bytes)
+    /// - Ed25519: Public point A, compressed (0x20 bytes)
+    ///
+    /// Note that ECC keys are not SEC1 points: use `PublicKey::to_sec1` to
+    /// obtain a tagged (and optionally compressed) point.
+    pub bytes: Vec<u8>,
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl PublicKey {
+    /// Create a public key from the raw bytes described above, checking that
+    /// they're the right size for the given algorithm and (for short
+    /// Weierstrass curves) that they're a point on the curve
+    pub fn new<B>(algorithm: asymmetric::Algorithm, bytes: B) -> Result<Self, PublicKeyError>
+    where
+        B: Into<Vec<u8>>,
+    {
+        let bytes = bytes.into();
+        validate(algorithm, &bytes)?;
+        Ok(Self { algorithm, bytes })
+    }
+
+    /// Parse a DER-encoded X.509 `SubjectPublicKeyInfo`
+    pub fn from_spki_der(der: &[u8]) -> Result<Self, PublicKeyError> {
+        spki::decode_der(der)
+    }
+
+    /// Parse a PEM-encoded X.509 `SubjectPublicKeyInfo`
+    /// (i.e. `-----BEGIN PUBLIC KEY-----`)
+    pub fn from_spki_pem(pem: &str) -> Result<Self, PublicKeyError> {
+        spki::decode_pem(pem)
+    }
+
+    /// Parse an OpenSSH public key (i.e. a line of an `authorized_keys` file)
+    pub fn from_openssh(line: &str) -> Result<Self, PublicKeyError> {
+        openssh::decode(line)
+    }
+
+    /// Parse an OpenSSH public key in SSH wire format
+    pub fn from_ssh_wire_format(bytes: &[u8]) -> Result<Self, PublicKeyError> {
+        openssh::decode_wire(bytes)
+    }
+
+    /// Parse a compressed or uncompressed SEC1 point on the curve of the
+    /// given algorithm. Compressed P-224 points are unsupported.
+    pub fn from_sec1(
+        algorithm: asymmetric::Algorithm,
+        point: &[u8],
+    ) -> Result<Self, PublicKeyError> {
+        sec1::decode(algorithm, point)
+    }
+
+    /// Parse an RFC 7517 JSON Web Key
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, PublicKeyError> {
+        jwk::decode(jwk)
+    }
+
+    /// Serialize this key as a DER-encoded X.509 `SubjectPublicKeyInfo`
+    pub fn to_spki_der(&self) -> Result<Vec<u8>, PublicKeyError> {
+        validate(self.algorithm, &self.bytes)?;
+        spki::encode_der(self)
+    }
+
+    /// Serialize this key as a PEM-encoded X.509 `SubjectPublicKeyInfo`
+    /// (i.e. `-----BEGIN PUBLIC KEY-----`)
+    pub fn to_spki_pem(&self) -> Result<String, PublicKeyError> {
+        validate(self.algorithm, &self.bytes)?;
+        spki::encode_pem(self)
+    }
+
+    /// Serialize this key as an OpenSSH public key (i.e. a line of an
+    /// `authorized_keys` file) with an optional comment.
+    ///
+    /// Only RSA, Ed25519, and NIST P-256/P-384/P-521 keys are supported.
+    pub fn to_openssh(&self, comment: Option<&str>) -> Result<String, PublicKeyError> {
+        validate(self.algorithm, &self.bytes)?;
+        openssh::encode(self, comment)
+    }
+
+    /// Serialize this key in SSH wire format (e.g. for use as the subject of
+    /// an `ssh::CertificateRequest`)
+    pub fn to_ssh_wire_format(&self) -> Result<Vec<u8>, PublicKeyError> {
+        validate(self.algorithm, &self.bytes)?;
+        openssh::encode_wire(self)
+    }
+
+    /// Serialize this (non-Ed25519 ECC) key as a SEC1 point, optionally
+    /// compressed
+    pub fn to_sec1(&self, compress: bool) -> Result<Vec<u8>, PublicKeyError> {
+        validate(self.algorithm, &self.bytes)?;
+        sec1::encode(self, compress)
+    }
+
+    /// Serialize this key as an RFC 7517 JSON Web Key.
+    ///
+    /// Only RSA, Ed25519, NIST P-256/P-384/P-521, and secp256k1 keys have
+    /// registered JWK representations.
+    pub fn to_jwk(&self) -> Result<Jwk, PublicKeyError> {
+        validate(self.algorithm, &self.bytes)?;
+        jwk::encode(self)
+    }
+
+    /// Unwrap inner byte vector
+    pub fn into_vec(self) -> Vec<u8> {
+        self.into()
+    }
+
+    /// Get length of the key
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Get slice of the inner byte vector
+    pub fn as_slice(&self) -> &[u8] {
+        self.as_ref()
+    }
+
+    /// Return the ECDSA public key of the given curve type if applicable
+    pub fn ecdsa<C>(&self) -> Option<ecdsa::PublicKey<C>>
+    where
+        C: ecdsa::curve::WeierstrassCurve,
+    {
+        match self.algorithm {
+            asymmetric::Algorithm::EC_P256 => {
+                if C::CURVE_KIND == WeierstrassCurveKind::NistP256 {
+                    Some(ecdsa::PublicKey::from_untagged_point(
+                        GenericArray::from_slice(&self.bytes),
+                    ))
+                } else {
+                    None
+                }
+            }
+            asymmetric::Algorithm::EC_P384 => {
+                if C::CURVE_KIND == WeierstrassCurveKind::NistP384 {
+                    Some(ecdsa::PublicKey::from_untagged_point(
+                        GenericArray::from_slice(&self.bytes),
+                    ))
+                } else {
+                    None
+                }
+            }
+            asymmetric::Algorithm::EC_K256 => {
+                if C::CURVE_KIND == WeierstrassCurveKind::Secp256k1 {
+                    Some(ecdsa::PublicKey::from_untagged_point(
+                        GenericArray::from_slice(&self.bytes),
+                    ))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Return the RSA public key if applicable
+    pub fn rsa(&self) -> Option<rsa::PublicKey> {
+        match self.algorithm {
+            asymmetric::Algorithm::RSA_2048
+            | asymmetric::Algorithm::RSA_3072
+            | asymmetric::Algorithm::RSA_4096 => rsa::PublicKey::from_modulus(&self.bytes),
+            _ => None,
+        }
+    }
+
+    /// Return the Ed25519 public key if applicable
+    pub fn ed25519(&self) -> Option<ed25519::PublicKey> {
+        if self.algorithm == asymmetric::Algorithm::Ed25519 {
+            ed25519::PublicKey::from_bytes(&self.bytes).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Create an RSA public key from its modulus, inferring the key size
+    fn rsa_from_modulus(modulus: &[u8]) -> Result<Self, PublicKeyError> {
+        let rsa_key = rsa::PublicKey::from_modulus(modulus)
+            .ok_or_else(|| err!(KeyInvalid, "invalid RSA modulus ({} bytes)", modulus.len()))?;
+
+        Ok(Self {
+            algorithm: rsa_key.algorithm(),
+            bytes: modulus.into(),
+        })
+    }
+}
+
+impl AsRef<[u8]> for PublicKey {
+    fn as_ref(&self) -> &[u8] {
+        self.bytes.as_ref()
+    }
+}
+
+impl Into<Vec<u8>> for PublicKey {
+    fn into(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Check that the given raw public key bytes are valid for the algorithm
+fn validate(algorithm: asymmetric::Algorithm, bytes: &[u8]) -> Result<(), PublicKeyError> {
+    match algorithm {
+        asymmetric::Algorithm::RSA_2048
+        | asymmetric::Algorithm::RSA_3072
+        | asymmetric::Algorithm::RSA_4096 => ensure!(
+            rsa::PublicKey::from_modulus(bytes).map(|key| key.algorithm()) == Some(algorithm),
+            KeyInvalid,
+            "invalid {:?} modulus",
+            algorithm
+        ),
+        asymmetric::Algorithm::Ed25519 => ensure!(
+            bytes.len() == algorithm.key_len(),
+            KeyInvalid,
+            "expected {}-byte Ed25519 key, got {}",
+            algorithm.key_len(),
+            bytes.len()
+        ),
+        _ => {
+            let size = algorithm.key_len();
+
+            ensure!(
+                bytes.len() == size * 2,
+                KeyInvalid,
+                "expected {}-byte {:?} point, got {}",
+                size * 2,
+                algorithm,
+                bytes.len()
+            );
+
+            let (x, y) = bytes.split_at(size);
+
+            ensure!(
+                Curve::new(algorithm)
+                    .unwrap()
+                    .is_on_curve(&BigUint::from_bytes_be(x), &BigUint::from_bytes_be(y)),
+                KeyInvalid,
+                "point is not on the {:?} curve",
+                algorithm
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,157 @@
+//! OpenSSH public key encoding (RFC 4253 Section 6.6 and RFC 5656 Section 3.1),
+//! as used in `authorized_keys` files
+
+use super::{
+    sec1, PublicKey, PublicKeyError,
+    PublicKeyErrorKind::{AlgorithmUnsupported, FormatInvalid},
+    RSA_PUBLIC_EXPONENT,
+};
+use crate::{
+    asymmetric,
+    ssh::encoding::{Decoder, Encoder},
+};
+use subtle_encoding::base64;
+
+/// Get the OpenSSH key type and curve identifier for the given algorithm
+fn openssh_key_type(
+    algorithm: asymmetric::Algorithm,
+) -> Result<(&'static str, &'static str), PublicKeyError> {
+    Ok(match algorithm {
+        asymmetric::Algorithm::RSA_2048
+        | asymmetric::Algorithm::RSA_3072
+        | asymmetric::Algorithm::RSA_4096 => ("ssh-rsa", ""),
+        asymmetric::Algorithm::Ed25519 => ("ssh-ed25519", ""),
+        asymmetric::Algorithm::EC_P256 => ("ecdsa-sha2-nistp256", "nistp256"),
+        asymmetric::Algorithm::EC_P384 => ("ecdsa-sha2-nistp384", "nistp384"),
+        asymmetric::Algorithm::EC_P521 => ("ecdsa-sha2-nistp521", "nistp521"),
+        _ => fail!(
+            AlgorithmUnsupported,
+            "OpenSSH doesn't support {:?} keys",
+            algorithm
+        ),
+    })
+}
+
+/// Encode a public key in SSH wire format
+pub(super) fn encode_wire(public_key: &PublicKey) -> Result<Vec<u8>, PublicKeyError> {
+    let (key_type, curve) = openssh_key_type(public_key.algorithm)?;
+    let mut encoder = Encoder::new();
+    encoder.string(key_type.as_bytes());
+
+    match public_key.algorithm {
+        asymmetric::Algorithm::RSA_2048
+        | asymmetric::Algorithm::RSA_3072
+        | asymmetric::Algorithm::RSA_4096 => {
+            encoder.mpint(RSA_PUBLIC_EXPONENT);
+            encoder.mpint(&public_key.bytes);
+        }
+        asymmetric::Algorithm::Ed25519 => encoder.string(&public_key.bytes),
+        _ => {
+            encoder.string(curve.as_bytes());
+            encoder.string(&sec1::encode(public_key, false)?);
+        }
+    }
+
+    Ok(encoder.into_vec())
+}
+
+/// Decode a public key in SSH wire format
+pub(super) fn decode_wire(bytes: &[u8]) -> Result<PublicKey, PublicKeyError> {
+    let mut decoder = Decoder::new(bytes);
+    let key_type = decoder.string().map_err(malformed)?;
+
+    let public_key = match key_type {
+        b"ssh-rsa" => {
+            let exponent = decoder.mpint().map_err(malformed)?;
+            let modulus = decoder.mpint().map_err(malformed)?;
+
+            ensure!(
+                exponent == RSA_PUBLIC_EXPONENT,
+                AlgorithmUnsupported,
+                "unsupported RSA public exponent"
+            );
+
+            PublicKey::rsa_from_modulus(modulus)?
+        }
+        b"ssh-ed25519" => PublicKey::new(
+            asymmetric::Algorithm::Ed25519,
+            decoder.string().map_err(malformed)?,
+        )?,
+        _ => {
+            let algorithm = [
+                asymmetric::Algorithm::EC_P256,
+                asymmetric::Algorithm::EC_P384,
+                asymmetric::Algorithm::EC_P521,
+            ]
+            .iter()
+            .cloned()
+            .find(|&alg| openssh_key_type(alg).unwrap().0.as_bytes() == key_type)
+            .ok_or_else(|| err!(AlgorithmUnsupported, "unsupported OpenSSH key type"))?;
+
+            let curve = decoder.string().map_err(malformed)?;
+
+            ensure!(
+                curve == openssh_key_type(algorithm)?.1.as_bytes(),
+                FormatInvalid,
+                "curve doesn't match key type"
+            );
+
+            sec1::decode(algorithm, decoder.string().map_err(malformed)?)?
+        }
+    };
+
+    ensure!(
+        decoder.remaining().is_empty(),
+        FormatInvalid,
+        "trailing data after OpenSSH public key"
+    );
+
+    Ok(public_key)
+}
+
+/// Encode a public key as an `authorized_keys` line (i.e.
+/// `<key type> <base64 data> [comment]`)
+pub(super) fn encode(
+    public_key: &PublicKey,
+    comment: Option<&str>,
+) -> Result<String, PublicKeyError> {
+    let (key_type, _) = openssh_key_type(public_key.algorithm)?;
+    let encoded = String::from_utf8(base64::encode(&encode_wire(public_key)?)).unwrap();
+
+    Ok(match comment {
+        Some(comment) => format!("{} {} {}", key_type, encoded, comment),
+        None => format!("{} {}", key_type, encoded),
+    })
+}
+
+/// Decode an `authorized_keys` line, ignoring the comment (if any)
+pub(super) fn decode(line: &str) -> Result<PublicKey, PublicKeyError> {
+    let mut fields = line.trim().split_whitespace();
+
+    let (key_type, encoded) = match (fields.next(), fields.next()) {
+        (Some(key_type), Some(encoded)) => (key_type, encoded),
+        _ => fail!(FormatInvalid, "expected `<key type> <base64 data>`"),
+    };
+
+    let bytes = base64::decode(encoded.as_bytes())
+        .map_err(|e| err!(FormatInvalid, "invalid OpenSSH base64: {}", e))?;
+
+    let public_key = decode_wire(&bytes)?;
+
+    ensure!(
+        openssh_key_type(public_key.algorithm)?.0 == key_type,
+        FormatInvalid,
+        "key type doesn't match encoded key"
+    );
+
+    Ok(public_key)
+}
+
+/// Error for malformed SSH wire format data
+fn malformed<E: ToString>(e: E) -> PublicKeyError {
+    err!(
+        FormatInvalid,
+        "malformed OpenSSH public key: {}",
+        e.to_string()
+    )
+}
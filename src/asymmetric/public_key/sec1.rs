@@ -0,0 +1,96 @@
+//! SEC1 elliptic curve point encoding (SEC 1 Section 2.3.3)
+
+use super::{
+    PublicKey, PublicKeyError,
+    PublicKeyErrorKind::{AlgorithmUnsupported, FormatInvalid, KeyInvalid},
+};
+use crate::asymmetric::{self, curve::Curve};
+use num_bigint::BigUint;
+
+/// Tag byte for a compressed point with an even y-coordinate
+const COMPRESSED_EVEN_TAG: u8 = 0x02;
+
+/// Tag byte for a compressed point with an odd y-coordinate
+const COMPRESSED_ODD_TAG: u8 = 0x03;
+
+/// Tag byte for an uncompressed point
+pub(super) const UNCOMPRESSED_TAG: u8 = 0x04;
+
+/// Encode an ECC public key as a SEC1 point
+pub(super) fn encode(public_key: &PublicKey, compress: bool) -> Result<Vec<u8>, PublicKeyError> {
+    ensure!(
+        Curve::new(public_key.algorithm).is_some(),
+        AlgorithmUnsupported,
+        "not a short Weierstrass curve key: {:?}",
+        public_key.algorithm
+    );
+
+    let (x, y) = public_key.bytes.split_at(public_key.algorithm.key_len());
+
+    let mut point = if compress {
+        if y[y.len() - 1] & 1 == 1 {
+            vec![COMPRESSED_ODD_TAG]
+        } else {
+            vec![COMPRESSED_EVEN_TAG]
+        }
+    } else {
+        vec![UNCOMPRESSED_TAG]
+    };
+
+    point.extend_from_slice(x);
+
+    if !compress {
+        point.extend_from_slice(y);
+    }
+
+    Ok(point)
+}
+
+/// Decode a compressed or uncompressed SEC1 point on the curve of the given
+/// algorithm. Compressed P-224 points are unsupported.
+pub(super) fn decode(
+    algorithm: asymmetric::Algorithm,
+    point: &[u8],
+) -> Result<PublicKey, PublicKeyError> {
+    let curve = Curve::new(algorithm).ok_or_else(|| {
+        err!(
+            AlgorithmUnsupported,
+            "not a short Weierstrass curve algorithm: {:?}",
+            algorithm
+        )
+    })?;
+
+    let size = algorithm.key_len();
+
+    match point.split_first() {
+        Some((&UNCOMPRESSED_TAG, coordinates)) => PublicKey::new(algorithm, coordinates),
+        Some((&tag, x)) if tag == COMPRESSED_EVEN_TAG || tag == COMPRESSED_ODD_TAG => {
+            ensure!(
+                x.len() == size,
+                KeyInvalid,
+                "expected {}-byte x-coordinate, got {}",
+                size,
+                x.len()
+            );
+
+            ensure!(
+                curve.supports_decompression(),
+                AlgorithmUnsupported,
+                "compressed {:?} points are unsupported",
+                algorithm
+            );
+
+            let y = curve
+                .y_coordinate(&BigUint::from_bytes_be(x), tag == COMPRESSED_ODD_TAG)
+                .ok_or_else(|| err!(KeyInvalid, "point is not on the curve"))?
+                .to_bytes_be();
+
+            let mut bytes = x.to_vec();
+            bytes.resize(size * 2 - y.len(), 0);
+            bytes.extend_from_slice(&y);
+
+            PublicKey::new(algorithm, bytes)
+        }
+        _ => fail!(FormatInvalid, "invalid SEC1 point tag"),
+    }
+}
@@ -0,0 +1,162 @@
+//! X.509 `SubjectPublicKeyInfo` (RFC 5280 Section 4.1.2.7) encoding, in both
+//! DER and PEM form
+
+use super::{
+    sec1, PublicKey, PublicKeyError,
+    PublicKeyErrorKind::{AlgorithmUnsupported, FormatInvalid},
+    RSA_PUBLIC_EXPONENT,
+};
+use crate::{
+    asymmetric::{self, oid},
+    der, pem, rsa,
+};
+
+/// PEM label for `SubjectPublicKeyInfo`
+const PEM_LABEL: &str = "PUBLIC KEY";
+
+/// Encode a public key as a DER `SubjectPublicKeyInfo`
+pub(super) fn encode_der(public_key: &PublicKey) -> Result<Vec<u8>, PublicKeyError> {
+    let (algorithm_identifier, subject_public_key) = match public_key.algorithm {
+        asymmetric::Algorithm::RSA_2048
+        | asymmetric::Algorithm::RSA_3072
+        | asymmetric::Algorithm::RSA_4096 => {
+            let mut algorithm_identifier = der::encode(der::OBJECT_IDENTIFIER, oid::RSA_ENCRYPTION);
+            algorithm_identifier.extend_from_slice(&der::encode(der::NULL, &[]));
+
+            let mut rsa_public_key = der::encode_integer(&public_key.bytes);
+            rsa_public_key.extend_from_slice(&der::encode_integer(RSA_PUBLIC_EXPONENT));
+
+            (
+                algorithm_identifier,
+                der::encode(der::SEQUENCE, &rsa_public_key),
+            )
+        }
+        asymmetric::Algorithm::Ed25519 => (
+            der::encode(der::OBJECT_IDENTIFIER, oid::ED25519),
+            public_key.bytes.clone(),
+        ),
+        algorithm => {
+            let curve_oid = oid::curve(algorithm)
+                .ok_or_else(|| err!(AlgorithmUnsupported, "no OID for {:?}", algorithm))?;
+
+            let mut algorithm_identifier = der::encode(der::OBJECT_IDENTIFIER, oid::EC_PUBLIC_KEY);
+            algorithm_identifier.extend_from_slice(&der::encode(der::OBJECT_IDENTIFIER, curve_oid));
+
+            (algorithm_identifier, sec1::encode(public_key, false)?)
+        }
+    };
+
+    let mut spki = der::encode(der::SEQUENCE, &algorithm_identifier);
+    spki.extend_from_slice(&der::encode_bit_string(&subject_public_key));
+
+    Ok(der::encode(der::SEQUENCE, &spki))
+}
+
+/// Decode a DER `SubjectPublicKeyInfo`
+pub(super) fn decode_der(bytes: &[u8]) -> Result<PublicKey, PublicKeyError> {
+    let mut outer = der::Decoder::new(bytes);
+    let spki = outer.read(der::SEQUENCE);
+    ensure!(outer.is_empty(), FormatInvalid, "trailing data after SPKI");
+
+    let mut spki = der::Decoder::new(spki.ok_or_else(malformed)?);
+    let mut algorithm_identifier =
+        der::Decoder::new(spki.read(der::SEQUENCE).ok_or_else(malformed)?);
+    let subject_public_key = spki
+        .read(der::BIT_STRING)
+        .and_then(der::decode_bit_string)
+        .ok_or_else(malformed)?;
+
+    ensure!(spki.is_empty(), FormatInvalid, "trailing data in SPKI");
+
+    let algorithm_oid = algorithm_identifier
+        .read(der::OBJECT_IDENTIFIER)
+        .ok_or_else(malformed)?;
+
+    if algorithm_oid == oid::RSA_ENCRYPTION {
+        // Parameters must be NULL (or absent)
+        if !algorithm_identifier.is_empty() {
+            ensure!(
+                algorithm_identifier.read(der::NULL).map(<[u8]>::is_empty) == Some(true),
+                FormatInvalid,
+                "invalid rsaEncryption parameters"
+            );
+        }
+
+        decode_rsa_public_key(subject_public_key)
+    } else if algorithm_oid == oid::EC_PUBLIC_KEY {
+        let curve_oid = algorithm_identifier
+            .read(der::OBJECT_IDENTIFIER)
+            .ok_or_else(|| err!(FormatInvalid, "missing named curve"))?;
+
+        let algorithm = oid::curve_algorithm(curve_oid)
+            .ok_or_else(|| err!(AlgorithmUnsupported, "unsupported elliptic curve"))?;
+
+        sec1::decode(algorithm, subject_public_key)
+    } else if algorithm_oid == oid::ED25519 {
+        ensure!(
+            algorithm_identifier.is_empty(),
+            FormatInvalid,
+            "unexpected Ed25519 parameters"
+        );
+
+        PublicKey::new(asymmetric::Algorithm::Ed25519, subject_public_key)
+    } else {
+        fail!(AlgorithmUnsupported, "unsupported public key algorithm");
+    }
+}
+
+/// Encode a public key as a PEM `SubjectPublicKeyInfo`
+pub(super) fn encode_pem(public_key: &PublicKey) -> Result<String, PublicKeyError> {
+    Ok(pem::encode(PEM_LABEL, &encode_der(public_key)?))
+}
+
+/// Decode a PEM `SubjectPublicKeyInfo`
+pub(super) fn decode_pem(pem: &str) -> Result<PublicKey, PublicKeyError> {
+    let mut documents = pem::decode(pem).ok_or_else(|| err!(FormatInvalid, "malformed PEM"))?;
+
+    ensure!(
+        documents.len() == 1 && documents[0].0 == PEM_LABEL,
+        FormatInvalid,
+        "expected a single `{}` PEM document",
+        PEM_LABEL
+    );
+
+    decode_der(&documents.pop().unwrap().1)
+}
+
+/// Decode a PKCS#1 `RSAPublicKey` (RFC 8017 Appendix A.1.1)
+fn decode_rsa_public_key(bytes: &[u8]) -> Result<PublicKey, PublicKeyError> {
+    let mut outer = der::Decoder::new(bytes);
+    let mut rsa_public_key = der::Decoder::new(outer.read(der::SEQUENCE).ok_or_else(malformed)?);
+
+    let modulus = rsa_public_key
+        .read(der::INTEGER)
+        .and_then(der::decode_integer)
+        .ok_or_else(malformed)?;
+
+    let exponent = rsa_public_key
+        .read(der::INTEGER)
+        .and_then(der::decode_integer)
+        .ok_or_else(malformed)?;
+
+    ensure!(
+        outer.is_empty() && rsa_public_key.is_empty(),
+        FormatInvalid,
+        "trailing data in RSA public key"
+    );
+
+    // The YubiHSM 2 only supports RSA keys with a public exponent of 65537
+    ensure!(
+        exponent == RSA_PUBLIC_EXPONENT,
+        AlgorithmUnsupported,
+        "unsupported RSA public exponent (expected {})",
+        rsa::PUBLIC_EXPONENT
+    );
+
+    PublicKey::rsa_from_modulus(modulus)
+}
+
+/// Error for malformed DER
+fn malformed() -> PublicKeyError {
+    err!(FormatInvalid, "malformed SubjectPublicKeyInfo")
+}
@@ -1,9 +1,115 @@
+use super::{
+    x509, AttestationError,
+    AttestationErrorKind::{ChainInvalid, FormatInvalid},
+    Info,
+};
+use crate::pem;
+
+/// PEM label for X.509 certificates
+const PEM_LABEL: &str = "CERTIFICATE";
+
 /// Attestation certificates (DER encoded X.509)
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Certificate(pub Vec<u8>);
 
 #[allow(clippy::len_without_is_empty)]
 impl Certificate {
+    /// Decode a single PEM-encoded certificate
+    pub fn from_pem(pem: &str) -> Result<Self, AttestationError> {
+        let mut certificates = Self::from_pem_bundle(pem)?;
+
+        ensure!(
+            certificates.len() == 1,
+            FormatInvalid,
+            "expected exactly one certificate, found {}",
+            certificates.len()
+        );
+
+        Ok(certificates.remove(0))
+    }
+
+    /// Decode a bundle of PEM-encoded certificates (e.g. root certificates)
+    pub fn from_pem_bundle(pem: &str) -> Result<Vec<Self>, AttestationError> {
+        let documents = pem::decode(pem).ok_or_else(|| err!(FormatInvalid, "malformed PEM"))?;
+        let mut certificates = vec![];
+
+        for (label, der) in documents {
+            ensure!(
+                label == PEM_LABEL,
+                FormatInvalid,
+                "expected {}, found {}",
+                PEM_LABEL,
+                label
+            );
+
+            certificates.push(Certificate(der));
+        }
+
+        Ok(certificates)
+    }
+
+    /// Encode this certificate as PEM
+    pub fn to_pem(&self) -> String {
+        pem::encode(PEM_LABEL, &self.0)
+    }
+
+    /// Parse this certificate, without verifying its signature.
+    ///
+    /// Use `Certificate::verify` to establish that the information it
+    /// contains is genuine.
+    pub fn parse(&self) -> Result<Info, AttestationError> {
+        Info::from_x509(&x509::Certificate::decode(&self.0)?)
+    }
+
+    /// Verify this certificate was signed by the given device attestation
+    /// certificate, which was in turn signed by one of the given trusted
+    /// root certificates, returning the information it contains.
+    ///
+    /// Issuers are matched by name and signatures are checked (against every
+    /// root with a matching name), however validity periods and other
+    /// constraints are not.
+    pub fn verify(
+        &self,
+        device_certificate: &Certificate,
+        roots: &[Certificate],
+    ) -> Result<Info, AttestationError> {
+        let attestation = x509::Certificate::decode(&self.0)?;
+        let device = x509::Certificate::decode(&device_certificate.0)?;
+
+        ensure!(
+            attestation.issuer == device.subject,
+            ChainInvalid,
+            "attestation certificate wasn't issued by device certificate {}",
+            x509::format_name(device.subject)?
+        );
+
+        attestation.verify_signature(&device.public_key)?;
+
+        // Several roots may share a name (e.g. across key rollovers), so try
+        // each of them until one verifies the device certificate
+        let mut result = Err(err!(
+            ChainInvalid,
+            "no root certificate for issuer {}",
+            x509::format_name(device.issuer).unwrap_or_default()
+        ));
+
+        for root_certificate in roots {
+            let root = x509::Certificate::decode(&root_certificate.0)?;
+
+            if root.subject == device.issuer {
+                result = device.verify_signature(&root.public_key);
+
+                if result.is_ok() {
+                    break;
+                }
+            }
+        }
+
+        result?;
+
+        Info::from_x509(&attestation)
+    }
+
     /// Unwrap inner byte vector
     pub fn into_vec(self) -> Vec<u8> {
         self.0
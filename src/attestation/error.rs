@@ -0,0 +1,43 @@
+//! Attestation certificate error types
+
+use crate::{
+    asymmetric::{PublicKeyError, PublicKeyErrorKind},
+    error::Error,
+};
+use std::error::Error as StdError;
+
+/// Attestation certificate errors
+pub type AttestationError = Error<AttestationErrorKind>;
+
+/// Attestation certificate error kinds
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Fail)]
+pub enum AttestationErrorKind {
+    /// Key or signature algorithm isn't supported by this library
+    #[fail(display = "unsupported algorithm")]
+    AlgorithmUnsupported,
+
+    /// Certificate chain doesn't lead to any of the given roots
+    #[fail(display = "invalid certificate chain")]
+    ChainInvalid,
+
+    /// Malformed certificate (DER, PEM, or extensions)
+    #[fail(display = "invalid format")]
+    FormatInvalid,
+
+    /// Certificate signature doesn't verify under its issuer's key
+    #[fail(display = "invalid signature")]
+    SignatureInvalid,
+}
+
+impl From<PublicKeyError> for AttestationError {
+    fn from(err: PublicKeyError) -> Self {
+        let kind = match err.kind() {
+            PublicKeyErrorKind::AlgorithmUnsupported => AttestationErrorKind::AlgorithmUnsupported,
+            PublicKeyErrorKind::FormatInvalid | PublicKeyErrorKind::KeyInvalid => {
+                AttestationErrorKind::FormatInvalid
+            }
+        };
+
+        err!(kind, err.description())
+    }
+}
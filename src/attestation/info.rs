@@ -0,0 +1,210 @@
+//! Information about attested keys, parsed from attestation certificates
+
+use super::{x509, AttestationError, AttestationErrorKind::FormatInvalid};
+use crate::{asymmetric::PublicKey, der, device::SerialNumber, object, Capability, Domain};
+use byteorder::{BigEndian, ByteOrder};
+
+/// Prefix of the OIDs of YubiHSM 2 attestation extensions (1.3.6.1.4.1.41482.4)
+const YUBIHSM_OID_PREFIX: &[u8] = &[0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0xc4, 0x0a, 0x04];
+
+/// Firmware version of the device which generated the attestation
+const FIRMWARE_VERSION_ARC: u8 = 1;
+
+/// Serial number of the device which generated the attestation
+const SERIAL_NUMBER_ARC: u8 = 2;
+
+/// Origin of the attested key
+const ORIGIN_ARC: u8 = 3;
+
+/// Domains of the attested key
+const DOMAINS_ARC: u8 = 4;
+
+/// Capabilities of the attested key
+const CAPABILITIES_ARC: u8 = 5;
+
+/// Object ID of the attested key
+const OBJECT_ID_ARC: u8 = 6;
+
+/// Label of the attested key
+const LABEL_ARC: u8 = 9;
+
+/// Information contained in an attestation certificate.
+///
+/// The YubiHSM 2 specific fields are `None` if the corresponding extension
+/// is absent (e.g. in device attestation or root certificates).
+#[derive(Clone, Debug)]
+pub struct Info {
+    /// Subject name, e.g. `CN=YubiHSM Attestation id:0x0064`
+    pub subject: String,
+
+    /// Issuer name, i.e. the subject of the device attestation certificate
+    pub issuer: String,
+
+    /// Attested public key
+    pub public_key: PublicKey,
+
+    /// Firmware version of the device as `(major, minor, build)`
+    pub firmware_version: Option<(u8, u8, u8)>,
+
+    /// Serial number of the device
+    pub serial_number: Option<SerialNumber>,
+
+    /// How the attested key was created
+    pub origin: Option<object::Origin>,
+
+    /// Domains of the attested key
+    pub domains: Option<Domain>,
+
+    /// Capabilities of the attested key
+    pub capabilities: Option<Capability>,
+
+    /// Object ID of the attested key
+    pub object_id: Option<object::Id>,
+
+    /// Label of the attested key
+    pub label: Option<object::Label>,
+}
+
+impl Info {
+    /// Parse the information in a decoded X.509 certificate
+    pub(super) fn from_x509(certificate: &x509::Certificate<'_>) -> Result<Self, AttestationError> {
+        let extension = |arc| {
+            let mut oid = YUBIHSM_OID_PREFIX.to_vec();
+            oid.push(arc);
+            certificate.extension(&oid)
+        };
+
+        let firmware_version = match extension(FIRMWARE_VERSION_ARC) {
+            Some(value) => {
+                let version = decode(value, der::OCTET_STRING)?;
+                ensure!(
+                    version.len() == 3,
+                    FormatInvalid,
+                    "invalid firmware version length: {}",
+                    version.len()
+                );
+                Some((version[0], version[1], version[2]))
+            }
+            None => None,
+        };
+
+        let serial_number = match extension(SERIAL_NUMBER_ARC) {
+            Some(value) => Some(SerialNumber::from(decode_u32(value)?)),
+            None => None,
+        };
+
+        let origin = match extension(ORIGIN_ARC) {
+            Some(value) => {
+                let byte = decode_bit_string(value, 1)?[0];
+                Some(object::Origin::from_u8(byte).map_err(|e| err!(FormatInvalid, e))?)
+            }
+            None => None,
+        };
+
+        let domains = match extension(DOMAINS_ARC) {
+            Some(value) => {
+                let bits = BigEndian::read_u16(&decode_bit_string(value, 2)?);
+                Some(
+                    Domain::from_bits(bits)
+                        .ok_or_else(|| err!(FormatInvalid, "invalid domains: 0x{:04x}", bits))?,
+                )
+            }
+            None => None,
+        };
+
+        let capabilities =
+            match extension(CAPABILITIES_ARC) {
+                Some(value) => {
+                    let bits = BigEndian::read_u64(&decode_bit_string(value, 8)?);
+                    Some(Capability::from_bits(bits).ok_or_else(|| {
+                        err!(FormatInvalid, "invalid capabilities: 0x{:016x}", bits)
+                    })?)
+                }
+                None => None,
+            };
+
+        let object_id = match extension(OBJECT_ID_ARC) {
+            Some(value) => {
+                let id = decode_u32(value)?;
+                ensure!(
+                    id <= u32::from(object::Id::max_value()),
+                    FormatInvalid,
+                    "invalid object ID: {}",
+                    id
+                );
+                Some(id as object::Id)
+            }
+            None => None,
+        };
+
+        let label = match extension(LABEL_ARC) {
+            Some(value) => Some(
+                object::Label::from_bytes(decode(value, der::UTF8_STRING)?)
+                    .map_err(|e| err!(FormatInvalid, e))?,
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            subject: x509::format_name(certificate.subject)?,
+            issuer: x509::format_name(certificate.issuer)?,
+            public_key: certificate.public_key.clone(),
+            firmware_version,
+            serial_number,
+            origin,
+            domains,
+            capabilities,
+            object_id,
+            label,
+        })
+    }
+}
+
+/// Decode an extension value consisting of a single value with the given tag
+fn decode(value: &[u8], tag: u8) -> Result<&[u8], AttestationError> {
+    let mut decoder = der::Decoder::new(value);
+    let result = decoder.read(tag);
+
+    ensure!(
+        decoder.is_empty(),
+        FormatInvalid,
+        "trailing data in attestation extension"
+    );
+
+    result.ok_or_else(|| err!(FormatInvalid, "malformed attestation extension"))
+}
+
+/// Decode an extension value containing an `INTEGER` which fits in a `u32`
+fn decode_u32(value: &[u8]) -> Result<u32, AttestationError> {
+    let bytes = der::decode_integer(decode(value, der::INTEGER)?)
+        .ok_or_else(|| err!(FormatInvalid, "negative integer in attestation extension"))?;
+
+    ensure!(
+        bytes.len() <= 4,
+        FormatInvalid,
+        "integer too large in attestation extension"
+    );
+
+    Ok(bytes
+        .iter()
+        .fold(0u32, |result, &byte| (result << 8) | u32::from(byte)))
+}
+
+/// Decode an extension value containing a `BIT STRING` of (at most) the
+/// given size in bytes. Trailing zero bits may have been omitted, so the
+/// result is padded to the full size.
+fn decode_bit_string(value: &[u8], size: usize) -> Result<Vec<u8>, AttestationError> {
+    let bit_string = decode(value, der::BIT_STRING)?;
+
+    match bit_string.split_first() {
+        Some((&unused_bits, bytes)) if unused_bits < 8 && bytes.len() <= size => {
+            let mut result = bytes.to_vec();
+            result.resize(size, 0);
+            Ok(result)
+        }
+        _ => Err(err!(
+            FormatInvalid,
+            "malformed bit string in attestation extension"
+        )),
+    }
+}
@@ -1,7 +1,19 @@
 //! Attestation Certificates: generate an X.509 certificate which attests that
 //! a key generated with a YubiHSM is genuine
+//!
+//! Attestation certificates are signed by the device's attestation key, whose
+//! certificate is in turn signed by Yubico. Use `Certificate::verify` with the
+//! device attestation certificate and Yubico's root certificate(s) to check
+//! the chain and obtain the attested key's `Info`.
 
 mod certificate;
 pub(crate) mod commands;
+mod error;
+mod info;
+mod x509;
 
-pub use self::certificate::Certificate;
+pub use self::{
+    certificate::Certificate,
+    error::{AttestationError, AttestationErrorKind},
+    info::Info,
+};
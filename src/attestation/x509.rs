@@ -0,0 +1,447 @@
+//! Minimal X.509 certificate parser (RFC 5280), sufficient for verifying
+//! attestation certificate chains.
+//!
+//! Only the fields needed to verify a chain (i.e. names, subject public key,
+//! and signature) and to read extensions are parsed. Validity periods and
+//! other constraints are not checked.
+
+use super::{
+    AttestationError,
+    AttestationErrorKind::{AlgorithmUnsupported, FormatInvalid, SignatureInvalid},
+};
+use crate::{
+    asymmetric::{self, PublicKey},
+    der,
+};
+use ring::signature::{
+    self as ring_signature, ECDSA_P256_SHA256_ASN1, ECDSA_P256_SHA384_ASN1, ECDSA_P384_SHA256_ASN1,
+    ECDSA_P384_SHA384_ASN1, RSA_PKCS1_2048_8192_SHA256, RSA_PKCS1_2048_8192_SHA384,
+    RSA_PKCS1_2048_8192_SHA512,
+};
+
+/// Tag of the (explicitly tagged) `version` field: `[0]`
+const VERSION_TAG: u8 = 0xa0;
+
+/// Tag of the (implicitly tagged) `issuerUniqueID` field: `[1]`
+const ISSUER_UNIQUE_ID_TAG: u8 = 0x81;
+
+/// Tag of the (implicitly tagged) `subjectUniqueID` field: `[2]`
+const SUBJECT_UNIQUE_ID_TAG: u8 = 0x82;
+
+/// Tag of the (explicitly tagged) `extensions` field: `[3]`
+const EXTENSIONS_TAG: u8 = 0xa3;
+
+/// X.509 v3 (encoded as 2)
+const VERSION_3: u8 = 2;
+
+/// String types which may appear in names
+const STRING_TAGS: &[u8] = &[
+    der::UTF8_STRING,
+    0x13, // PrintableString
+    0x14, // TeletexString
+    0x16, // IA5String
+];
+
+/// Short names of common name attributes (RFC 4514 Section 3)
+const ATTRIBUTE_NAMES: &[(&[u8], &str)] = &[
+    // commonName (2.5.4.3)
+    (&[0x55, 0x04, 0x03], "CN"),
+    // countryName (2.5.4.6)
+    (&[0x55, 0x04, 0x06], "C"),
+    // localityName (2.5.4.7)
+    (&[0x55, 0x04, 0x07], "L"),
+    // stateOrProvinceName (2.5.4.8)
+    (&[0x55, 0x04, 0x08], "ST"),
+    // organizationName (2.5.4.10)
+    (&[0x55, 0x04, 0x0a], "O"),
+    // organizationalUnitName (2.5.4.11)
+    (&[0x55, 0x04, 0x0b], "OU"),
+];
+
+/// Certificate signature algorithms
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum SignatureAlgorithm {
+    /// `ecdsa-with-SHA256` (1.2.840.10045.4.3.2)
+    EcdsaSha256,
+
+    /// `ecdsa-with-SHA384` (1.2.840.10045.4.3.3)
+    EcdsaSha384,
+
+    /// `ecdsa-with-SHA512` (1.2.840.10045.4.3.4)
+    EcdsaSha512,
+
+    /// `sha256WithRSAEncryption` (1.2.840.113549.1.1.11)
+    RsaSha256,
+
+    /// `sha384WithRSAEncryption` (1.2.840.113549.1.1.12)
+    RsaSha384,
+
+    /// `sha512WithRSAEncryption` (1.2.840.113549.1.1.13)
+    RsaSha512,
+}
+
+/// Object identifiers for supported signature algorithms
+const SIGNATURE_ALGORITHMS: &[(SignatureAlgorithm, &[u8])] = &[
+    (
+        SignatureAlgorithm::EcdsaSha256,
+        &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02],
+    ),
+    (
+        SignatureAlgorithm::EcdsaSha384,
+        &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x03],
+    ),
+    (
+        SignatureAlgorithm::EcdsaSha512,
+        &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x04],
+    ),
+    (
+        SignatureAlgorithm::RsaSha256,
+        &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b],
+    ),
+    (
+        SignatureAlgorithm::RsaSha384,
+        &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0c],
+    ),
+    (
+        SignatureAlgorithm::RsaSha512,
+        &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0d],
+    ),
+];
+
+impl SignatureAlgorithm {
+    /// Decode an `AlgorithmIdentifier` for a signature algorithm
+    fn decode(algorithm_identifier: &[u8]) -> Result<Self, AttestationError> {
+        let mut decoder = der::Decoder::new(algorithm_identifier);
+        let oid = decoder.read(der::OBJECT_IDENTIFIER).ok_or_else(malformed)?;
+
+        let algorithm = SIGNATURE_ALGORITHMS
+            .iter()
+            .find(|(_, alg_oid)| *alg_oid == oid)
+            .map(|(alg, _)| *alg)
+            .ok_or_else(|| err!(AlgorithmUnsupported, "unsupported signature algorithm"))?;
+
+        // ECDSA parameters must be absent, and RSA parameters NULL (or absent)
+        if !decoder.is_empty() {
+            ensure!(
+                !algorithm.is_ecdsa()
+                    && decoder.read(der::NULL).map(<[u8]>::is_empty) == Some(true)
+                    && decoder.is_empty(),
+                FormatInvalid,
+                "invalid signature algorithm parameters"
+            );
+        }
+
+        Ok(algorithm)
+    }
+
+    /// Is this an ECDSA signature algorithm?
+    fn is_ecdsa(self) -> bool {
+        match self {
+            SignatureAlgorithm::EcdsaSha256
+            | SignatureAlgorithm::EcdsaSha384
+            | SignatureAlgorithm::EcdsaSha512 => true,
+            _ => false,
+        }
+    }
+}
+
+/// Parsed X.509 certificate, borrowing from its DER encoding
+#[derive(Clone, Debug)]
+pub(super) struct Certificate<'a> {
+    /// DER encoding of the signed `TBSCertificate`
+    tbs_certificate: &'a [u8],
+
+    /// Algorithm used by the issuer to sign this certificate
+    signature_algorithm: SignatureAlgorithm,
+
+    /// Signature over the `TBSCertificate`
+    signature: &'a [u8],
+
+    /// DER encoding of the issuer's name
+    pub issuer: &'a [u8],
+
+    /// DER encoding of the subject's name
+    pub subject: &'a [u8],
+
+    /// Subject public key
+    pub public_key: PublicKey,
+
+    /// Extensions as `(extnID, extnValue)` pairs
+    extensions: Vec<(&'a [u8], &'a [u8])>,
+}
+
+impl<'a> Certificate<'a> {
+    /// Decode a DER-encoded X.509 v3 certificate
+    pub fn decode(bytes: &'a [u8]) -> Result<Self, AttestationError> {
+        let mut outer = der::Decoder::new(bytes);
+        let certificate = outer.read(der::SEQUENCE);
+        ensure!(
+            outer.is_empty(),
+            FormatInvalid,
+            "trailing data after certificate"
+        );
+
+        let mut certificate = der::Decoder::new(certificate.ok_or_else(malformed)?);
+        let tbs_certificate = certificate
+            .read_encoded(der::SEQUENCE)
+            .ok_or_else(malformed)?;
+        let signature_algorithm =
+            SignatureAlgorithm::decode(certificate.read(der::SEQUENCE).ok_or_else(malformed)?)?;
+        let signature = certificate
+            .read(der::BIT_STRING)
+            .and_then(der::decode_bit_string)
+            .ok_or_else(malformed)?;
+
+        ensure!(
+            certificate.is_empty(),
+            FormatInvalid,
+            "trailing data in certificate"
+        );
+
+        let mut tbs = der::Decoder::new(
+            der::Decoder::new(tbs_certificate)
+                .read(der::SEQUENCE)
+                .ok_or_else(malformed)?,
+        );
+
+        let version = der::Decoder::new(tbs.read(VERSION_TAG).ok_or_else(malformed)?)
+            .read(der::INTEGER)
+            .ok_or_else(malformed)?;
+
+        ensure!(
+            version == [VERSION_3],
+            FormatInvalid,
+            "unsupported X.509 version"
+        );
+
+        let _serial_number = tbs.read(der::INTEGER).ok_or_else(malformed)?;
+
+        ensure!(
+            SignatureAlgorithm::decode(tbs.read(der::SEQUENCE).ok_or_else(malformed)?)?
+                == signature_algorithm,
+            FormatInvalid,
+            "mismatched signature algorithms"
+        );
+
+        let issuer = tbs.read_encoded(der::SEQUENCE).ok_or_else(malformed)?;
+        let _validity = tbs.read(der::SEQUENCE).ok_or_else(malformed)?;
+        let subject = tbs.read_encoded(der::SEQUENCE).ok_or_else(malformed)?;
+        let public_key =
+            PublicKey::from_spki_der(tbs.read_encoded(der::SEQUENCE).ok_or_else(malformed)?)?;
+
+        for &tag in &[ISSUER_UNIQUE_ID_TAG, SUBJECT_UNIQUE_ID_TAG] {
+            if tbs.peek_tag() == Some(tag) {
+                tbs.read(tag).ok_or_else(malformed)?;
+            }
+        }
+
+        let mut extensions = vec![];
+
+        if tbs.peek_tag() == Some(EXTENSIONS_TAG) {
+            let mut extensions_field =
+                der::Decoder::new(tbs.read(EXTENSIONS_TAG).ok_or_else(malformed)?);
+            let mut decoder =
+                der::Decoder::new(extensions_field.read(der::SEQUENCE).ok_or_else(malformed)?);
+
+            ensure!(
+                extensions_field.is_empty(),
+                FormatInvalid,
+                "trailing data after extensions"
+            );
+
+            while !decoder.is_empty() {
+                let mut extension =
+                    der::Decoder::new(decoder.read(der::SEQUENCE).ok_or_else(malformed)?);
+                let oid = extension
+                    .read(der::OBJECT_IDENTIFIER)
+                    .ok_or_else(malformed)?;
+
+                if extension.peek_tag() == Some(der::BOOLEAN) {
+                    extension.read(der::BOOLEAN).ok_or_else(malformed)?;
+                }
+
+                let value = extension.read(der::OCTET_STRING).ok_or_else(malformed)?;
+
+                ensure!(
+                    extension.is_empty(),
+                    FormatInvalid,
+                    "trailing data in extension"
+                );
+
+                extensions.push((oid, value));
+            }
+        }
+
+        ensure!(
+            tbs.is_empty(),
+            FormatInvalid,
+            "trailing data in TBSCertificate"
+        );
+
+        Ok(Self {
+            tbs_certificate,
+            signature_algorithm,
+            signature,
+            issuer,
+            subject,
+            public_key,
+            extensions,
+        })
+    }
+
+    /// Get the value of the extension with the given OID (if present)
+    pub fn extension(&self, oid: &[u8]) -> Option<&'a [u8]> {
+        self.extensions
+            .iter()
+            .find(|(ext_oid, _)| *ext_oid == oid)
+            .map(|(_, value)| *value)
+    }
+
+    /// Verify this certificate's signature under its issuer's public key
+    pub fn verify_signature(&self, issuer_key: &PublicKey) -> Result<(), AttestationError> {
+        let valid = if self.signature_algorithm.is_ecdsa() {
+            self.verify_ecdsa(issuer_key)?
+        } else {
+            self.verify_rsa(issuer_key)?
+        };
+
+        ensure!(valid, SignatureInvalid, "certificate signature is invalid");
+        Ok(())
+    }
+
+    /// Verify this certificate's RSASSA-PKCS#1v1.5 signature under an RSA
+    /// issuer key using *ring*
+    fn verify_rsa(&self, issuer_key: &PublicKey) -> Result<bool, AttestationError> {
+        let rsa_key = issuer_key.rsa().ok_or_else(|| {
+            err!(
+                SignatureInvalid,
+                "issuer key is not an RSA key: {:?}",
+                issuer_key.algorithm
+            )
+        })?;
+
+        let algorithm = match self.signature_algorithm {
+            SignatureAlgorithm::RsaSha256 => &RSA_PKCS1_2048_8192_SHA256,
+            SignatureAlgorithm::RsaSha384 => &RSA_PKCS1_2048_8192_SHA384,
+            SignatureAlgorithm::RsaSha512 => &RSA_PKCS1_2048_8192_SHA512,
+            signature_algorithm => fail!(
+                AlgorithmUnsupported,
+                "unsupported RSA signature algorithm: {:?}",
+                signature_algorithm
+            ),
+        };
+
+        Ok(rsa_key.verify(algorithm, self.tbs_certificate, self.signature))
+    }
+
+    /// Verify this certificate's (DER-encoded `ECDSA-Sig-Value`) signature
+    /// under an ECDSA issuer key using *ring*
+    fn verify_ecdsa(&self, issuer_key: &PublicKey) -> Result<bool, AttestationError> {
+        let algorithm: &dyn ring_signature::VerificationAlgorithm =
+            match (issuer_key.algorithm, self.signature_algorithm) {
+                (asymmetric::Algorithm::EC_P256, SignatureAlgorithm::EcdsaSha256) => {
+                    &ECDSA_P256_SHA256_ASN1
+                }
+                (asymmetric::Algorithm::EC_P256, SignatureAlgorithm::EcdsaSha384) => {
+                    &ECDSA_P256_SHA384_ASN1
+                }
+                (asymmetric::Algorithm::EC_P384, SignatureAlgorithm::EcdsaSha256) => {
+                    &ECDSA_P384_SHA256_ASN1
+                }
+                (asymmetric::Algorithm::EC_P384, SignatureAlgorithm::EcdsaSha384) => {
+                    &ECDSA_P384_SHA384_ASN1
+                }
+                (key_algorithm, signature_algorithm) => fail!(
+                    AlgorithmUnsupported,
+                    "unsupported ECDSA issuer key/signature algorithm: {:?}/{:?}",
+                    key_algorithm,
+                    signature_algorithm
+                ),
+            };
+
+        // ring expects the public key as an uncompressed SEC1 point
+        let mut point = Vec::with_capacity(1 + issuer_key.len());
+        point.push(0x04);
+        point.extend_from_slice(issuer_key.as_slice());
+
+        Ok(ring_signature::verify(
+            algorithm,
+            untrusted::Input::from(&point),
+            untrusted::Input::from(self.tbs_certificate),
+            untrusted::Input::from(self.signature),
+        )
+        .is_ok())
+    }
+}
+
+/// Format a DER-encoded name as a string, e.g. `CN=Example, O=Example Inc`
+pub(super) fn format_name(name: &[u8]) -> Result<String, AttestationError> {
+    let mut rdn_sequence = der::Decoder::new(
+        der::Decoder::new(name)
+            .read(der::SEQUENCE)
+            .ok_or_else(malformed)?,
+    );
+    let mut attributes = vec![];
+
+    while !rdn_sequence.is_empty() {
+        let mut rdn = der::Decoder::new(rdn_sequence.read(der::SET).ok_or_else(malformed)?);
+
+        while !rdn.is_empty() {
+            let mut attribute = der::Decoder::new(rdn.read(der::SEQUENCE).ok_or_else(malformed)?);
+            let oid = attribute
+                .read(der::OBJECT_IDENTIFIER)
+                .ok_or_else(malformed)?;
+            let (tag, value) = attribute.read_any().ok_or_else(malformed)?;
+
+            let name = ATTRIBUTE_NAMES
+                .iter()
+                .find(|(attr_oid, _)| *attr_oid == oid)
+                .map(|(_, name)| name.to_string())
+                .unwrap_or_else(|| format_oid(oid));
+
+            let value = if STRING_TAGS.contains(&tag) {
+                String::from_utf8_lossy(value).into_owned()
+            } else {
+                // Other values are hex-encoded (RFC 4514 Section 2.4)
+                let hex: String = value.iter().map(|byte| format!("{:02x}", byte)).collect();
+                format!("#{}", hex)
+            };
+
+            attributes.push(format!("{}={}", name, value));
+        }
+    }
+
+    Ok(attributes.join(", "))
+}
+
+/// Format a DER-encoded object identifier in dotted decimal notation
+fn format_oid(oid: &[u8]) -> String {
+    let mut arcs = vec![];
+    let mut arc = 0u64;
+
+    for &byte in oid {
+        arc = (arc << 7) | u64::from(byte & 0x7f);
+
+        if byte & 0x80 == 0 {
+            if arcs.is_empty() {
+                let first = if arc < 80 { arc / 40 } else { 2 };
+                arcs.push(first);
+                arcs.push(arc - first * 40);
+            } else {
+                arcs.push(arc);
+            }
+
+            arc = 0;
+        }
+    }
+
+    arcs.iter()
+        .map(u64::to_string)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Error for malformed DER
+fn malformed() -> AttestationError {
+    err!(FormatInvalid, "malformed X.509 certificate")
+}
@@ -0,0 +1,139 @@
+//! Key usage analysis of audit log entries.
+//!
+//! Entries should be verified with `audit::verify_chain` before they're
+//! analyzed, so the results can be traced back to the HSM's tamper-evident
+//! log rather than trusting application-level logging.
+
+use super::LogEntry;
+use crate::{command, object, response};
+use std::{collections::BTreeMap, iter::FromIterator};
+
+/// Key ID recorded in log entries for commands which don't act on a key
+const NO_KEY: object::Id = 0xffff;
+
+/// Usage statistics for a key (or pair of keys)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Usage {
+    /// Number of operations performed, by command type
+    pub operations: BTreeMap<command::Code, usize>,
+
+    /// Number of failed operations, by response code
+    pub failures: BTreeMap<response::Code, usize>,
+
+    /// Device tick of the first logged use
+    pub first_tick: u32,
+
+    /// Device tick of the last logged use
+    pub last_tick: u32,
+}
+
+impl Usage {
+    /// Create empty usage statistics, starting at the given tick
+    fn new(tick: u32) -> Self {
+        Usage {
+            operations: BTreeMap::new(),
+            failures: BTreeMap::new(),
+            first_tick: tick,
+            last_tick: tick,
+        }
+    }
+
+    /// Add a log entry to these statistics
+    fn add(&mut self, entry: &LogEntry) {
+        *self.operations.entry(entry.cmd).or_insert(0) += 1;
+
+        if entry.result.is_err() {
+            *self.failures.entry(entry.result).or_insert(0) += 1;
+        }
+
+        self.last_tick = entry.tick;
+    }
+
+    /// Total number of operations performed
+    pub fn total_operations(&self) -> usize {
+        self.operations.values().sum()
+    }
+
+    /// Total number of failed operations
+    pub fn total_failures(&self) -> usize {
+        self.failures.values().sum()
+    }
+}
+
+/// Usage statistics aggregated from audit log entries
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Analysis {
+    /// Usage by the authentication key of the session performing the command
+    pub session_keys: BTreeMap<object::Id, Usage>,
+
+    /// Usage by the key the command acted on
+    pub target_keys: BTreeMap<object::Id, Usage>,
+
+    /// Usage by `(session_key, target_key)` pair, e.g. to find which
+    /// authentication keys signed with a particular key
+    pub key_pairs: BTreeMap<(object::Id, object::Id), Usage>,
+}
+
+impl Analysis {
+    /// Create a new, empty analysis
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a (verified) log entry to this analysis
+    pub fn add(&mut self, entry: &LogEntry) {
+        record(&mut self.session_keys, entry.session_key, entry);
+
+        if entry.target_key != NO_KEY {
+            record(&mut self.target_keys, entry.target_key, entry);
+            record(
+                &mut self.key_pairs,
+                (entry.session_key, entry.target_key),
+                entry,
+            );
+        }
+    }
+
+    /// Authentication keys which performed the given command using the given
+    /// target key, along with how many times they did so
+    pub fn session_keys_for(
+        &self,
+        target_key: object::Id,
+        command_type: command::Code,
+    ) -> Vec<(object::Id, usize)> {
+        self.key_pairs
+            .iter()
+            .filter(|&(&(_, target), _)| target == target_key)
+            .filter_map(|(&(session_key, _), usage)| {
+                usage
+                    .operations
+                    .get(&command_type)
+                    .map(|&count| (session_key, count))
+            })
+            .collect()
+    }
+}
+
+impl<'a> Extend<&'a LogEntry> for Analysis {
+    fn extend<I: IntoIterator<Item = &'a LogEntry>>(&mut self, entries: I) {
+        for entry in entries {
+            self.add(entry);
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a LogEntry> for Analysis {
+    fn from_iter<I: IntoIterator<Item = &'a LogEntry>>(entries: I) -> Self {
+        let mut analysis = Analysis::new();
+        analysis.extend(entries);
+        analysis
+    }
+}
+
+/// Record a log entry in the usage statistics for the given key
+fn record<K: Ord>(usages: &mut BTreeMap<K, Usage>, key: K, entry: &LogEntry) {
+    usages
+        .entry(key)
+        .or_insert_with(|| Usage::new(entry.tick))
+        .add(entry);
+}
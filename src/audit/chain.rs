@@ -0,0 +1,113 @@
+//! Audit log hash chain verification.
+//!
+//! Each log entry carries a digest over its own fields and the digest of the
+//! previous entry, so a verified entry vouches for every entry before it.
+
+use super::{
+    AuditError,
+    AuditErrorKind::{DigestMismatch, Gap, Reset},
+    LogDigest, LogEntries, LogEntry,
+};
+
+/// Last verified position in the audit log. Persist this between reads so
+/// subsequent entries can be verified as a continuation of the same chain.
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Checkpoint {
+    /// Item number of the last verified entry
+    pub item: u16,
+
+    /// Digest of the last verified entry
+    pub digest: LogDigest,
+}
+
+impl<'a> From<&'a LogEntry> for Checkpoint {
+    fn from(entry: &'a LogEntry) -> Checkpoint {
+        Checkpoint {
+            item: entry.item,
+            digest: entry.digest,
+        }
+    }
+}
+
+/// Verify the digest chain of the given log entries, returning a checkpoint
+/// for the last entry (or `None` if there are no entries to verify).
+///
+/// If a checkpoint from a previous read is given, the entries must continue
+/// its chain. Entries up to and including the checkpoint (i.e. those not yet
+/// acknowledged with `Client::set_log_index`) are skipped after checking the
+/// checkpoint's entry still has the same digest. Otherwise the first entry is
+/// trusted as-is, since its predecessor isn't available.
+pub fn verify_chain(
+    checkpoint: Option<&Checkpoint>,
+    log_entries: &LogEntries,
+) -> Result<Option<Checkpoint>, AuditError> {
+    let entries = &log_entries.entries;
+
+    let (mut previous, remaining) = match checkpoint {
+        Some(checkpoint) => {
+            if let Some(entry) = entries.iter().find(|entry| entry.item == checkpoint.item) {
+                ensure!(
+                    entry.digest == checkpoint.digest,
+                    DigestMismatch,
+                    "log item {} doesn't match checkpoint",
+                    checkpoint.item
+                );
+            }
+
+            (*checkpoint, new_entries(Some(checkpoint), entries))
+        }
+        None => match entries.split_first() {
+            Some((first, remaining)) => (Checkpoint::from(first), remaining),
+            None => return Ok(None),
+        },
+    };
+
+    for entry in remaining {
+        let expected_item = previous.item.wrapping_add(1);
+
+        if entry.item != expected_item {
+            if entry.item <= previous.item {
+                fail!(
+                    Reset,
+                    "log restarted at item {} after item {}",
+                    entry.item,
+                    previous.item
+                );
+            }
+
+            fail!(
+                Gap,
+                "expected log item {}, got {}",
+                expected_item,
+                entry.item
+            );
+        }
+
+        ensure!(
+            entry.digest == entry.compute_digest(&previous.digest),
+            DigestMismatch,
+            "digest mismatch for log item {}",
+            entry.item
+        );
+
+        previous = Checkpoint::from(entry);
+    }
+
+    Ok(Some(previous))
+}
+
+/// Entries which follow the given checkpoint, i.e. all of them if there's no
+/// checkpoint or it's no longer present in the log
+pub(super) fn new_entries<'a>(
+    checkpoint: Option<&Checkpoint>,
+    entries: &'a [LogEntry],
+) -> &'a [LogEntry] {
+    match checkpoint.and_then(|checkpoint| {
+        entries
+            .iter()
+            .position(|entry| entry.item == checkpoint.item)
+    }) {
+        Some(pos) => &entries[(pos + 1)..],
+        None => entries,
+    }
+}
@@ -0,0 +1,205 @@
+//! Audit log draining.
+//!
+//! When the force audit option is enabled, the HSM refuses audited commands
+//! once its log is full, so entries must be regularly copied somewhere
+//! durable and then acknowledged with `Client::set_log_index`.
+
+use super::{
+    chain::{new_entries, verify_chain},
+    AuditError, Checkpoint, LogEntry,
+};
+use crate::Client;
+use std::{
+    cmp,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::Sender,
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+#[cfg(feature = "serde_json")]
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+/// Longest delay between retries after background draining fails (unless
+/// the draining interval is even longer)
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Durable storage for audit log entries
+pub trait Sink {
+    /// Append verified entries to the sink (possibly buffering them)
+    fn append(&mut self, entries: &[LogEntry]) -> Result<(), AuditError>;
+
+    /// Ensure all appended entries have been durably stored. Entries are
+    /// only acknowledged on the HSM after this returns successfully.
+    fn flush(&mut self) -> Result<(), AuditError>;
+}
+
+/// Sink which writes each entry as a line of JSON to a file
+#[cfg(feature = "serde_json")]
+pub struct JsonLinesSink {
+    /// Buffered writer for the underlying file
+    writer: BufWriter<File>,
+}
+
+#[cfg(feature = "serde_json")]
+impl JsonLinesSink {
+    /// Open (or create) the given file, appending entries to it
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, AuditError> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        Ok(Self {
+            writer: BufWriter::new(file),
+        })
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl Sink for JsonLinesSink {
+    fn append(&mut self, entries: &[LogEntry]) -> Result<(), AuditError> {
+        for entry in entries {
+            serde_json::to_writer(&mut self.writer, entry)?;
+            self.writer.write_all(b"\n")?;
+        }
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), AuditError> {
+        self.writer.flush()?;
+        self.writer.get_ref().sync_data()?;
+        Ok(())
+    }
+}
+
+/// Copies verified audit log entries from the HSM into a `Sink`, then
+/// acknowledges them so the HSM can reuse their space.
+///
+/// Entries are delivered at least once: if flushing the sink fails, the same
+/// entries will be appended again by the next drain.
+pub struct Drainer<S: Sink> {
+    /// Client for the HSM whose log is being drained
+    client: Client,
+
+    /// State shared between clones of this drainer
+    state: Arc<Mutex<DrainerState<S>>>,
+
+    /// Flag used to stop background draining
+    stop: Arc<AtomicBool>,
+}
+
+/// Mutable state of a `Drainer`
+struct DrainerState<S: Sink> {
+    /// Sink entries are written to
+    sink: S,
+
+    /// Last entry written to the sink
+    checkpoint: Option<Checkpoint>,
+
+    /// Last item acknowledged with `Client::set_log_index`
+    acknowledged: Option<u16>,
+}
+
+impl<S: Sink> Drainer<S> {
+    /// Create a new drainer. The checkpoint of the last entry previously
+    /// written to the sink (if any) should be provided so the chain can be
+    /// verified across restarts.
+    pub fn new(client: Client, sink: S, checkpoint: Option<Checkpoint>) -> Self {
+        Self {
+            client,
+            state: Arc::new(Mutex::new(DrainerState {
+                sink,
+                checkpoint,
+                acknowledged: None,
+            })),
+            stop: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Get the checkpoint of the last entry written to the sink
+    pub fn checkpoint(&self) -> Option<Checkpoint> {
+        self.state.lock().unwrap().checkpoint
+    }
+
+    /// Read the log, verify it, write new entries to the sink, and once
+    /// they've been flushed, acknowledge them on the HSM.
+    ///
+    /// Returns the number of entries written to the sink.
+    pub fn drain(&self) -> Result<usize, AuditError> {
+        let mut guard = self.state.lock().unwrap();
+        let state = &mut *guard;
+
+        let log_entries = self.client.get_log_entries()?;
+        let checkpoint = verify_chain(state.checkpoint.as_ref(), &log_entries)?;
+        let entries = new_entries(state.checkpoint.as_ref(), &log_entries.entries);
+
+        if !entries.is_empty() {
+            state.sink.append(entries)?;
+            state.sink.flush()?;
+            state.checkpoint = checkpoint;
+        }
+
+        if let Some(checkpoint) = state.checkpoint {
+            if state.acknowledged != Some(checkpoint.item) {
+                self.client.set_log_index(checkpoint.item)?;
+                state.acknowledged = Some(checkpoint.item);
+            }
+        }
+
+        Ok(entries.len())
+    }
+
+    /// Drain the log every `interval` on a background thread until `stop` is
+    /// called.
+    ///
+    /// Errors (e.g. a temporary connection failure) don't stop draining, as
+    /// the HSM refuses audited commands once its log fills up under the force
+    /// audit option. Instead each error is logged and sent to `errors`, and
+    /// draining is retried with exponential backoff.
+    pub fn spawn(&self, interval: Duration, errors: Sender<AuditError>) -> JoinHandle<()>
+    where
+        S: Send + 'static,
+    {
+        let drainer = self.clone();
+        let max_delay = cmp::max(interval, MAX_RETRY_DELAY);
+
+        thread::spawn(move || {
+            let mut delay = interval;
+
+            while !drainer.stop.load(Ordering::SeqCst) {
+                match drainer.drain() {
+                    Ok(_) => delay = interval,
+                    Err(e) => {
+                        delay = cmp::min(delay * 2, max_delay);
+                        error!("error draining audit log (retrying in {:?}): {}", delay, e);
+
+                        // Keep draining even if nobody is listening for errors
+                        let _ = errors.send(e);
+                    }
+                }
+
+                thread::sleep(delay);
+            }
+        })
+    }
+
+    /// Stop background draining (after the current drain completes)
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+impl<S: Sink> Clone for Drainer<S> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            state: Arc::clone(&self.state),
+            stop: Arc::clone(&self.stop),
+        }
+    }
+}
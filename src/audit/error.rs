@@ -0,0 +1,58 @@
+//! Audit log error types
+
+use crate::{
+    client::{ClientError, ClientErrorKind},
+    error::Error,
+};
+use std::{error::Error as StdError, io};
+
+/// Audit log errors
+pub type AuditError = Error<AuditErrorKind>;
+
+/// Audit log error kinds
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Fail)]
+pub enum AuditErrorKind {
+    /// Error communicating with the HSM while reading the log
+    #[fail(display = "client error: {}", kind)]
+    ClientError {
+        /// Client error kind
+        kind: ClientErrorKind,
+    },
+
+    /// Log entry digest doesn't match the entry and its predecessor
+    /// (i.e. the log has been tampered with)
+    #[fail(display = "log digest mismatch")]
+    DigestMismatch,
+
+    /// Log entries are missing from the chain
+    #[fail(display = "gap in log")]
+    Gap,
+
+    /// Error writing log entries to a sink
+    #[fail(display = "I/O error")]
+    IoError,
+
+    /// Log item numbers restarted (e.g. the device was reset)
+    #[fail(display = "log reset")]
+    Reset,
+}
+
+impl From<ClientError> for AuditError {
+    fn from(err: ClientError) -> Self {
+        let kind = AuditErrorKind::ClientError { kind: err.kind() };
+        err!(kind, err.description())
+    }
+}
+
+impl From<io::Error> for AuditError {
+    fn from(err: io::Error) -> Self {
+        err!(AuditErrorKind::IoError, err.to_string())
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl From<serde_json::Error> for AuditError {
+    fn from(err: serde_json::Error) -> Self {
+        err!(AuditErrorKind::IoError, err.to_string())
+    }
+}
@@ -0,0 +1,105 @@
+//! Audit log entries (as returned by `Client::get_log_entries`)
+
+use crate::{command, object, response};
+use byteorder::{BigEndian, ByteOrder};
+use sha2::{Digest, Sha256};
+use std::fmt::{self, Debug};
+
+/// Size of the serialized fields of a log entry (i.e. excluding its digest)
+const LOG_ENTRY_FIELDS_SIZE: usize = 16;
+
+/// Response from `command::get_log_entries`
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct LogEntries {
+    /// Number of boot events which weren't logged (if buffer is full and audit enforce is set)
+    pub unlogged_boot_events: u16,
+
+    /// Number of unlogged authentication events (if buffer is full and audit enforce is set)
+    pub unlogged_auth_events: u16,
+
+    /// Number of entries in the response
+    pub num_entries: u8,
+
+    /// Entries in the log
+    pub entries: Vec<LogEntry>,
+}
+
+/// Entry in the log response
+#[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct LogEntry {
+    /// Entry number
+    pub item: u16,
+
+    /// Command type
+    pub cmd: command::Code,
+
+    /// Command length
+    pub length: u16,
+
+    /// Session key ID
+    pub session_key: object::Id,
+
+    /// Target key ID
+    pub target_key: object::Id,
+
+    /// Second key affected
+    pub second_key: object::Id,
+
+    /// Result of the operation
+    pub result: response::Code,
+
+    /// Tick count of the HSM's internal clock
+    pub tick: u32,
+
+    /// 16-byte truncated SHA-256 digest of this log entry and the digest of the previous entry
+    pub digest: LogDigest,
+}
+
+impl LogEntry {
+    /// Compute the digest of this entry, chained to the previous entry's
+    /// digest: the truncated SHA-256 digest of this entry's serialized fields
+    /// (excluding its digest) followed by the previous digest.
+    pub fn compute_digest(&self, previous_digest: &LogDigest) -> LogDigest {
+        let mut fields = [0u8; LOG_ENTRY_FIELDS_SIZE];
+        BigEndian::write_u16(&mut fields[0..2], self.item);
+        fields[2] = self.cmd.to_u8();
+        BigEndian::write_u16(&mut fields[3..5], self.length);
+        BigEndian::write_u16(&mut fields[5..7], self.session_key);
+        BigEndian::write_u16(&mut fields[7..9], self.target_key);
+        BigEndian::write_u16(&mut fields[9..11], self.second_key);
+        fields[11] = self.result.to_u8();
+        BigEndian::write_u32(&mut fields[12..16], self.tick);
+
+        let mut hasher = Sha256::new();
+        hasher.input(&fields);
+        hasher.input(previous_digest);
+
+        let mut digest = [0u8; LOG_DIGEST_SIZE];
+        digest.copy_from_slice(&hasher.result()[..LOG_DIGEST_SIZE]);
+        LogDigest(digest)
+    }
+}
+
+/// Size of a truncated digest in the log
+pub const LOG_DIGEST_SIZE: usize = 16;
+
+/// Truncated SHA-256 digest of a log entry and the previous log digest
+#[derive(Copy, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct LogDigest(pub [u8; LOG_DIGEST_SIZE]);
+
+impl AsRef<[u8]> for LogDigest {
+    fn as_ref(&self) -> &[u8] {
+        &self.0[..]
+    }
+}
+
+impl Debug for LogDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LogDigest(")?;
+        for (i, byte) in self.0.iter().enumerate() {
+            write!(f, "{:02x}", byte)?;
+            write!(f, "{}", if i == LOG_DIGEST_SIZE - 1 { ")" } else { ":" })?;
+        }
+        Ok(())
+    }
+}
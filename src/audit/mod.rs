@@ -1,6 +1,22 @@
-//! Auditing options (for use with the `get_option` and `put_option` command)
+//! Auditing options (for use with the `get_option` and `put_option` command),
+//! audit log verification and analysis, and draining the log into durable
+//! storage
 
+pub mod analysis;
+mod chain;
 pub(crate) mod commands;
+mod drainer;
+mod error;
+mod log;
+
+#[cfg(feature = "serde_json")]
+pub use self::drainer::JsonLinesSink;
+pub use self::{
+    chain::{verify_chain, Checkpoint},
+    drainer::{Drainer, Sink},
+    error::{AuditError, AuditErrorKind},
+    log::{LogDigest, LogEntries, LogEntry, LOG_DIGEST_SIZE},
+};
 
 use crate::command;
 use failure::Error;
@@ -0,0 +1,38 @@
+//! Change the authentication key used to establish the current session
+//!
+//! <https://developers.yubico.com/YubiHSM2/Commands/Change_Authentication_Key.html>
+
+use crate::{
+    authentication,
+    command::{self, Command},
+    object,
+    response::Response,
+};
+
+/// Request parameters for `command::change_authentication_key`
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct ChangeAuthenticationKeyCommand {
+    /// Object ID of the authentication key
+    pub key_id: object::Id,
+
+    /// Authentication key algorithm
+    pub algorithm: authentication::Algorithm,
+
+    /// New authentication key
+    pub authentication_key: authentication::Key,
+}
+
+impl Command for ChangeAuthenticationKeyCommand {
+    type ResponseType = ChangeAuthenticationKeyResponse;
+}
+
+/// Response from `command::change_authentication_key`
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct ChangeAuthenticationKeyResponse {
+    /// Object ID of the authentication key
+    pub key_id: object::Id,
+}
+
+impl Response for ChangeAuthenticationKeyResponse {
+    const COMMAND_CODE: command::Code = command::Code::ChangeAuthenticationKey;
+}
@@ -0,0 +1,6 @@
+//! Authentication key commands
+
+mod change;
+mod put;
+
+pub(crate) use self::{change::*, put::*};
@@ -6,7 +6,7 @@ use hmac_crate::Hmac;
 #[cfg(feature = "pbkdf2")]
 use pbkdf2::pbkdf2;
 use rand_os::{rand_core::RngCore, OsRng};
-#[cfg(feature = "sha2")]
+#[cfg(feature = "passwords")]
 use sha2::Sha256;
 use std::fmt::{self, Debug};
 use zeroize::Zeroize;
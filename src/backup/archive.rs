@@ -0,0 +1,270 @@
+//! Backup archives: wrapped objects along with a manifest describing them
+
+use super::{BackupError, BackupErrorKind::FormatInvalid};
+use crate::{
+    algorithm::Algorithm,
+    object::{self, LABEL_SIZE},
+    wrap, Capability, Domain,
+};
+use byteorder::{ReadBytesExt, WriteBytesExt, BE};
+use std::io::{Cursor, Read, Write};
+
+/// Magic bytes at the start of a serialized archive
+const ARCHIVE_MAGIC: &[u8] = b"yubihsm.rs backup";
+
+/// Version of the archive format
+const ARCHIVE_VERSION: u8 = 1;
+
+/// Information about a backed up object, used to check it was restored intact
+#[derive(Clone, Debug, PartialEq)]
+pub struct Manifest {
+    /// Object identifier
+    pub object_id: object::Id,
+
+    /// Object type
+    pub object_type: object::Type,
+
+    /// Label of object
+    pub label: object::Label,
+
+    /// Algorithm the object is intended to be used with
+    pub algorithm: Algorithm,
+
+    /// Domains from which the object is accessible
+    pub domains: Domain,
+
+    /// Capabilities of the object
+    pub capabilities: Capability,
+}
+
+impl Manifest {
+    /// Serialize this manifest entry
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), BackupError> {
+        writer.write_u16::<BE>(self.object_id)?;
+        writer.write_u8(self.object_type.to_u8())?;
+        writer.write_all(self.label.as_ref())?;
+        writer.write_u8(self.algorithm.to_u8())?;
+        writer.write_u16::<BE>(self.domains.bits())?;
+        writer.write_u64::<BE>(self.capabilities.bits())?;
+        Ok(())
+    }
+
+    /// Deserialize a manifest entry
+    fn deserialize<R: Read>(reader: &mut R) -> Result<Self, BackupError> {
+        let object_id = reader.read_u16::<BE>()?;
+        let object_type =
+            object::Type::from_u8(reader.read_u8()?).map_err(|e| err!(FormatInvalid, e))?;
+
+        let mut label_bytes = [0u8; LABEL_SIZE];
+        reader.read_exact(&mut label_bytes)?;
+
+        let algorithm =
+            Algorithm::from_u8(reader.read_u8()?).map_err(|e| err!(FormatInvalid, e))?;
+        let domains = Domain::from_bits(reader.read_u16::<BE>()?)
+            .ok_or_else(|| err!(FormatInvalid, "invalid domain bitflags"))?;
+        let capabilities = Capability::from_bits(reader.read_u64::<BE>()?)
+            .ok_or_else(|| err!(FormatInvalid, "invalid capability bitflags"))?;
+
+        Ok(Self {
+            object_id,
+            object_type,
+            label: object::Label(label_bytes),
+            algorithm,
+            domains,
+            capabilities,
+        })
+    }
+}
+
+impl<'a> From<&'a object::Info> for Manifest {
+    fn from(info: &'a object::Info) -> Manifest {
+        Manifest {
+            object_id: info.object_id,
+            object_type: info.object_type,
+            label: info.label.clone(),
+            algorithm: info.algorithm,
+            domains: info.domains,
+            capabilities: info.capabilities,
+        }
+    }
+}
+
+/// Object which was backed up
+#[derive(Clone, Debug)]
+pub struct Entry {
+    /// Information about the object
+    pub manifest: Manifest,
+
+    /// Object encrypted under the backup wrap key
+    pub wrapped: wrap::Message,
+}
+
+/// Reasons objects couldn't be backed up
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SkipReason {
+    /// Object lacks the `EXPORTABLE_UNDER_WRAP` capability
+    NotExportable,
+
+    /// Object is the wrap key used for the backup
+    WrapKey,
+
+    /// The HSM refused to export the object (e.g. the wrap key's delegated
+    /// capabilities don't cover it)
+    ExportFailed,
+}
+
+impl SkipReason {
+    /// Convert an unsigned byte into a `SkipReason` (if valid)
+    fn from_u8(byte: u8) -> Result<Self, BackupError> {
+        Ok(match byte {
+            0x01 => SkipReason::NotExportable,
+            0x02 => SkipReason::WrapKey,
+            0x03 => SkipReason::ExportFailed,
+            _ => fail!(FormatInvalid, "invalid skip reason: 0x{:02x}", byte),
+        })
+    }
+
+    /// Serialize this reason as a byte
+    fn to_u8(self) -> u8 {
+        match self {
+            SkipReason::NotExportable => 0x01,
+            SkipReason::WrapKey => 0x02,
+            SkipReason::ExportFailed => 0x03,
+        }
+    }
+}
+
+/// Object which couldn't be backed up
+#[derive(Clone, Debug)]
+pub struct Skipped {
+    /// Information about the object
+    pub manifest: Manifest,
+
+    /// Why the object wasn't backed up
+    pub reason: SkipReason,
+}
+
+/// Backup archive containing wrapped objects and a manifest of their
+/// attributes, along with any objects which couldn't be backed up
+#[derive(Clone, Debug)]
+pub struct Archive {
+    /// ID of the wrap key the objects were exported under
+    pub wrap_key_id: object::Id,
+
+    /// IDs of the wrap keys this archive was previously encrypted under
+    /// (oldest first), i.e. the wrap keys it was re-wrapped from
+    pub previous_wrap_key_ids: Vec<object::Id>,
+
+    /// Objects which were backed up
+    pub entries: Vec<Entry>,
+
+    /// Objects which couldn't be backed up
+    pub skipped: Vec<Skipped>,
+}
+
+impl Archive {
+    /// Create a new, empty archive for the given wrap key
+    pub fn new(wrap_key_id: object::Id) -> Self {
+        Self {
+            wrap_key_id,
+            previous_wrap_key_ids: vec![],
+            entries: vec![],
+            skipped: vec![],
+        }
+    }
+
+    /// Parse a serialized archive
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BackupError> {
+        let mut reader = Cursor::new(bytes);
+
+        let mut magic = vec![0u8; ARCHIVE_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        ensure!(
+            magic == ARCHIVE_MAGIC,
+            FormatInvalid,
+            "not a backup archive"
+        );
+
+        let version = reader.read_u8()?;
+        ensure!(
+            version == ARCHIVE_VERSION,
+            FormatInvalid,
+            "unsupported archive version: {}",
+            version
+        );
+
+        let mut archive = Archive::new(reader.read_u16::<BE>()?);
+
+        for _ in 0..reader.read_u16::<BE>()? {
+            archive.previous_wrap_key_ids.push(reader.read_u16::<BE>()?);
+        }
+
+        for _ in 0..reader.read_u16::<BE>()? {
+            let manifest = Manifest::deserialize(&mut reader)?;
+            let wrapped_len = reader.read_u32::<BE>()? as usize;
+            ensure!(
+                wrapped_len <= bytes.len() - reader.position() as usize,
+                FormatInvalid,
+                "truncated backup archive"
+            );
+
+            let mut wrapped = vec![0u8; wrapped_len];
+            reader.read_exact(&mut wrapped)?;
+
+            archive.entries.push(Entry {
+                manifest,
+                wrapped: wrap::Message::from_vec(wrapped).map_err(|e| err!(FormatInvalid, e))?,
+            });
+        }
+
+        for _ in 0..reader.read_u16::<BE>()? {
+            let manifest = Manifest::deserialize(&mut reader)?;
+            let reason = SkipReason::from_u8(reader.read_u8()?)?;
+            archive.skipped.push(Skipped { manifest, reason });
+        }
+
+        ensure!(
+            reader.position() == bytes.len() as u64,
+            FormatInvalid,
+            "trailing data in backup archive"
+        );
+
+        Ok(archive)
+    }
+
+    /// Serialize this archive
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.serialize(vec![]).unwrap()
+    }
+
+    /// Serialize this archive into the given writer
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<W, BackupError> {
+        writer.write_all(ARCHIVE_MAGIC)?;
+        writer.write_u8(ARCHIVE_VERSION)?;
+        writer.write_u16::<BE>(self.wrap_key_id)?;
+        writer.write_u16::<BE>(self.previous_wrap_key_ids.len() as u16)?;
+
+        for &wrap_key_id in &self.previous_wrap_key_ids {
+            writer.write_u16::<BE>(wrap_key_id)?;
+        }
+
+        writer.write_u16::<BE>(self.entries.len() as u16)?;
+
+        for entry in &self.entries {
+            entry.manifest.serialize(&mut writer)?;
+
+            let wrapped = entry.wrapped.clone().into_vec();
+            writer.write_u32::<BE>(wrapped.len() as u32)?;
+            writer.write_all(&wrapped)?;
+        }
+
+        writer.write_u16::<BE>(self.skipped.len() as u16)?;
+
+        for skipped in &self.skipped {
+            skipped.manifest.serialize(&mut writer)?;
+            writer.write_u8(skipped.reason.to_u8())?;
+        }
+
+        Ok(writer)
+    }
+}
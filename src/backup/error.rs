@@ -0,0 +1,46 @@
+//! Backup error types
+
+use crate::{
+    client::{ClientError, ClientErrorKind},
+    error::Error,
+};
+use std::{error::Error as StdError, io};
+
+/// Backup errors
+pub type BackupError = Error<BackupErrorKind>;
+
+/// Backup error kinds
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Fail)]
+pub enum BackupErrorKind {
+    /// Error communicating with the HSM
+    #[fail(display = "client error: {}", kind)]
+    ClientError {
+        /// Client error kind
+        kind: ClientErrorKind,
+    },
+
+    /// Malformed backup archive
+    #[fail(display = "invalid format")]
+    FormatInvalid,
+
+    /// Restored object doesn't match its manifest entry
+    #[fail(display = "manifest mismatch")]
+    ManifestMismatch,
+
+    /// Object to be imported already exists in the HSM
+    #[fail(display = "object exists")]
+    ObjectExists,
+}
+
+impl From<ClientError> for BackupError {
+    fn from(err: ClientError) -> Self {
+        let kind = BackupErrorKind::ClientError { kind: err.kind() };
+        err!(kind, err.description())
+    }
+}
+
+impl From<io::Error> for BackupError {
+    fn from(err: io::Error) -> Self {
+        err!(BackupErrorKind::FormatInvalid, err.to_string())
+    }
+}
@@ -0,0 +1,181 @@
+//! Encrypted backup and restore of all exportable objects in an HSM.
+//!
+//! Every object marked `EXPORTABLE_UNDER_WRAP` is exported under a chosen
+//! wrap key into an `Archive`, along with a manifest of its attributes. The
+//! archive can then be restored to any HSM holding the same wrap key, or
+//! objects can be replicated directly between two HSMs. Archives can also be
+//! re-wrapped under a new wrap key when the old one is rotated.
+
+mod archive;
+mod error;
+mod replication;
+mod rotation;
+
+pub use self::{
+    archive::{Archive, Entry, Manifest, SkipReason, Skipped},
+    error::{BackupError, BackupErrorKind},
+    replication::{replicate, Outcome, Replication},
+    rotation::{rewrap, Rewrapped},
+};
+
+use self::error::BackupErrorKind::{ManifestMismatch, ObjectExists};
+use crate::{client::ClientError, device::DeviceErrorKind, object, wrap, Capability, Client};
+
+/// Result of restoring an archive
+#[derive(Clone, Debug)]
+pub struct Restored {
+    /// Objects which were restored
+    pub objects: Vec<object::Handle>,
+
+    /// Objects which already existed in the HSM with identical attributes,
+    /// and were left untouched
+    pub existing: Vec<object::Handle>,
+
+    /// Objects which weren't included in the archive
+    pub skipped: Vec<Skipped>,
+}
+
+/// Export every exportable object in the HSM under the given wrap key.
+///
+/// Objects which can't be exported are recorded in the archive's `skipped`
+/// list rather than causing the backup to fail.
+pub fn backup(client: &Client, wrap_key_id: object::Id) -> Result<Archive, BackupError> {
+    let mut archive = Archive::new(wrap_key_id);
+
+    for entry in client.list_objects(&[])? {
+        let info = client.get_object_info(entry.object_id, entry.object_type)?;
+        let manifest = Manifest::from(&info);
+
+        match export(client, wrap_key_id, &info) {
+            Ok(wrapped) => archive.entries.push(Entry { manifest, wrapped }),
+            Err(reason) => archive.skipped.push(Skipped { manifest, reason }),
+        }
+    }
+
+    Ok(archive)
+}
+
+/// Import the objects in an archive under the given wrap key, checking each
+/// restored object matches its manifest entry.
+///
+/// Objects which already exist in the HSM with identical attributes (e.g. the
+/// default authentication key) are skipped. If an object exists with
+/// different attributes, restoring stops with an `ObjectExists` error.
+pub fn restore(
+    client: &Client,
+    wrap_key_id: object::Id,
+    archive: &Archive,
+) -> Result<Restored, BackupError> {
+    let mut objects = vec![];
+    let mut existing = vec![];
+
+    for entry in &archive.entries {
+        let manifest = &entry.manifest;
+
+        if let Some(info) = find_object(client, manifest.object_id, manifest.object_type)? {
+            ensure!(
+                Manifest::from(&info) == *manifest,
+                ObjectExists,
+                "{:?} object {} already exists in the HSM with different attributes",
+                manifest.object_type,
+                manifest.object_id
+            );
+
+            existing.push(object::Handle::new(
+                manifest.object_id,
+                manifest.object_type,
+            ));
+            continue;
+        }
+
+        objects.push(import(
+            client,
+            wrap_key_id,
+            entry.wrapped.clone(),
+            &entry.manifest,
+        )?);
+    }
+
+    Ok(Restored {
+        objects,
+        existing,
+        skipped: archive.skipped.clone(),
+    })
+}
+
+/// Export an object under the given wrap key, or determine why it can't be
+fn export(
+    client: &Client,
+    wrap_key_id: object::Id,
+    info: &object::Info,
+) -> Result<wrap::Message, SkipReason> {
+    if info.object_type == object::Type::WrapKey && info.object_id == wrap_key_id {
+        return Err(SkipReason::WrapKey);
+    }
+
+    if !info
+        .capabilities
+        .contains(Capability::EXPORTABLE_UNDER_WRAP)
+    {
+        return Err(SkipReason::NotExportable);
+    }
+
+    client
+        .export_wrapped(wrap_key_id, info.object_type, info.object_id)
+        .map_err(|e| {
+            debug!(
+                "error exporting {:?} object {}: {}",
+                info.object_type, info.object_id, e
+            );
+            SkipReason::ExportFailed
+        })
+}
+
+/// Import a wrapped object, checking it matches its manifest entry
+fn import(
+    client: &Client,
+    wrap_key_id: object::Id,
+    wrapped: wrap::Message,
+    manifest: &Manifest,
+) -> Result<object::Handle, BackupError> {
+    let handle = client.import_wrapped(wrap_key_id, wrapped)?;
+    verify(client, &handle, manifest)?;
+    Ok(handle)
+}
+
+/// Check an object in the HSM matches its manifest entry
+fn verify(
+    client: &Client,
+    handle: &object::Handle,
+    manifest: &Manifest,
+) -> Result<(), BackupError> {
+    let info = client.get_object_info(handle.object_id, handle.object_type)?;
+
+    ensure!(
+        Manifest::from(&info) == *manifest,
+        ManifestMismatch,
+        "imported {:?} object {} doesn't match manifest",
+        handle.object_type,
+        handle.object_id
+    );
+
+    Ok(())
+}
+
+/// Get information about an object, or `None` if it doesn't exist
+fn find_object(
+    client: &Client,
+    object_id: object::Id,
+    object_type: object::Type,
+) -> Result<Option<object::Info>, ClientError> {
+    match client.get_object_info(object_id, object_type) {
+        Ok(info) => Ok(Some(info)),
+        Err(e) => {
+            if e.kind().device_error() == Some(DeviceErrorKind::ObjectNotFound) {
+                Ok(None)
+            } else {
+                Err(e)
+            }
+        }
+    }
+}
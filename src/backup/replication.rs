@@ -0,0 +1,108 @@
+//! Live replication of objects between two HSMs
+
+use super::{export, find_object, verify, BackupError, BackupErrorKind, Manifest, SkipReason};
+use crate::{object, Client};
+
+/// Outcome of replicating a particular object
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    /// Object was copied to the destination
+    Replicated,
+
+    /// Object already exists in the destination with identical metadata
+    Unchanged,
+
+    /// Object already exists in the destination with different metadata,
+    /// and was left untouched
+    Conflict,
+
+    /// Object couldn't be exported from the source
+    Skipped(SkipReason),
+
+    /// Object couldn't be imported into the destination
+    Failed(BackupErrorKind),
+
+    /// Object was imported into the destination but doesn't match the
+    /// source, and the imported copy couldn't be removed. It must be cleaned
+    /// up manually before replicating again.
+    Duplicate(BackupErrorKind),
+}
+
+/// Result of replicating a particular object
+#[derive(Clone, Debug)]
+pub struct Replication {
+    /// Information about the object in the source HSM
+    pub manifest: Manifest,
+
+    /// What happened to the object
+    pub outcome: Outcome,
+}
+
+/// Copy objects matching the given filters from the source HSM into the
+/// destination HSM, exporting each one under the given wrap key (which must
+/// be present in both HSMs) and importing it directly.
+///
+/// Failures replicating individual objects are recorded in the returned
+/// report; an error is only returned if the source objects can't be listed.
+/// Imported objects which don't match the source are removed from the
+/// destination, or reported as `Outcome::Duplicate` if that isn't possible.
+pub fn replicate(
+    source: &Client,
+    destination: &Client,
+    wrap_key_id: object::Id,
+    filters: &[object::Filter],
+) -> Result<Vec<Replication>, BackupError> {
+    let mut report = vec![];
+
+    for entry in source.list_objects(filters)? {
+        let info = source.get_object_info(entry.object_id, entry.object_type)?;
+        let manifest = Manifest::from(&info);
+        let outcome = replicate_object(source, destination, wrap_key_id, &info, &manifest);
+
+        report.push(Replication { manifest, outcome });
+    }
+
+    Ok(report)
+}
+
+/// Replicate a single object
+fn replicate_object(
+    source: &Client,
+    destination: &Client,
+    wrap_key_id: object::Id,
+    info: &object::Info,
+    manifest: &Manifest,
+) -> Outcome {
+    match find_object(destination, info.object_id, info.object_type) {
+        Ok(Some(existing)) => {
+            return if Manifest::from(&existing) == *manifest {
+                Outcome::Unchanged
+            } else {
+                Outcome::Conflict
+            };
+        }
+        Ok(None) => (),
+        Err(e) => return Outcome::Failed(BackupError::from(e).kind()),
+    }
+
+    let wrapped = match export(source, wrap_key_id, info) {
+        Ok(wrapped) => wrapped,
+        Err(reason) => return Outcome::Skipped(reason),
+    };
+
+    let handle = match destination.import_wrapped(wrap_key_id, wrapped) {
+        Ok(handle) => handle,
+        Err(e) => return Outcome::Failed(BackupError::from(e).kind()),
+    };
+
+    match verify(destination, &handle, manifest) {
+        Ok(()) => Outcome::Replicated,
+        Err(e) => {
+            // Don't leave a mismatched copy behind in the destination
+            match destination.delete_object(handle.object_id, handle.object_type) {
+                Ok(()) => Outcome::Failed(e.kind()),
+                Err(_) => Outcome::Duplicate(e.kind()),
+            }
+        }
+    }
+}
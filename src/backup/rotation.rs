@@ -0,0 +1,114 @@
+//! Re-wrapping backup archives under a new wrap key (i.e. wrap key rotation)
+
+use super::{
+    find_object, verify, Archive, BackupError, BackupErrorKind::ObjectExists, Entry, Manifest,
+};
+use crate::{object, wrap, Client};
+
+/// Result of re-wrapping an archive
+#[derive(Clone, Debug)]
+pub struct Rewrapped {
+    /// Archive re-wrapped under the new wrap key
+    pub archive: Archive,
+
+    /// Objects which already existed in the HSM with identical attributes,
+    /// and were exported directly rather than being temporarily imported
+    pub existing: Vec<object::Handle>,
+
+    /// Temporarily imported objects which couldn't be deleted afterwards,
+    /// and remain in the HSM. They must be cleaned up manually.
+    pub duplicates: Vec<object::Handle>,
+}
+
+/// Re-wrap every object in an archive under a new wrap key, producing a new
+/// archive which records the old wrap key ID in its `previous_wrap_key_ids`.
+///
+/// The HSM must hold both the old and new wrap keys. Objects which already
+/// exist in the HSM with identical attributes are exported under the new
+/// wrap key directly. Other objects are temporarily imported using the
+/// archive's wrap key, exported under the new one, and then deleted again.
+/// If an object exists in the HSM with different attributes, re-wrapping
+/// stops with an `ObjectExists` error.
+pub fn rewrap(
+    client: &Client,
+    archive: &Archive,
+    new_wrap_key_id: object::Id,
+) -> Result<Rewrapped, BackupError> {
+    let mut rewrapped_archive = Archive::new(new_wrap_key_id);
+    rewrapped_archive.previous_wrap_key_ids = archive.previous_wrap_key_ids.clone();
+    rewrapped_archive
+        .previous_wrap_key_ids
+        .push(archive.wrap_key_id);
+    rewrapped_archive.skipped = archive.skipped.clone();
+
+    let mut rewrapped = Rewrapped {
+        archive: rewrapped_archive,
+        existing: vec![],
+        duplicates: vec![],
+    };
+
+    for entry in &archive.entries {
+        let wrapped = rewrap_entry(
+            client,
+            archive.wrap_key_id,
+            new_wrap_key_id,
+            entry,
+            &mut rewrapped,
+        )?;
+
+        rewrapped.archive.entries.push(Entry {
+            manifest: entry.manifest.clone(),
+            wrapped,
+        });
+    }
+
+    Ok(rewrapped)
+}
+
+/// Re-wrap a single archive entry
+fn rewrap_entry(
+    client: &Client,
+    old_wrap_key_id: object::Id,
+    new_wrap_key_id: object::Id,
+    entry: &Entry,
+    rewrapped: &mut Rewrapped,
+) -> Result<wrap::Message, BackupError> {
+    let manifest = &entry.manifest;
+
+    if let Some(info) = find_object(client, manifest.object_id, manifest.object_type)? {
+        ensure!(
+            Manifest::from(&info) == *manifest,
+            ObjectExists,
+            "{:?} object {} already exists in the HSM with different attributes",
+            manifest.object_type,
+            manifest.object_id
+        );
+
+        let wrapped =
+            client.export_wrapped(new_wrap_key_id, manifest.object_type, manifest.object_id)?;
+
+        rewrapped.existing.push(object::Handle::new(
+            manifest.object_id,
+            manifest.object_type,
+        ));
+
+        return Ok(wrapped);
+    }
+
+    let handle = client.import_wrapped(old_wrap_key_id, entry.wrapped.clone())?;
+
+    let result = verify(client, &handle, manifest).and_then(|()| {
+        Ok(client.export_wrapped(new_wrap_key_id, handle.object_type, handle.object_id)?)
+    });
+
+    // Remove the temporary copy even if re-wrapping failed
+    if let Err(e) = client.delete_object(handle.object_id, handle.object_type) {
+        warn!(
+            "error deleting temporary copy of {:?} object {}: {}",
+            handle.object_type, handle.object_id, e
+        );
+        rewrapped.duplicates.push(handle);
+    }
+
+    result
+}
@@ -0,0 +1,239 @@
+//! AES-CCM (RFC 3610): the authenticated encryption mode used by the
+//! YubiHSM 2 for wrapped objects and Yubico OTP AEADs.
+
+use aes::{
+    block_cipher_trait::{generic_array::GenericArray, BlockCipher},
+    Aes128, Aes192, Aes256,
+};
+use subtle::ConstantTimeEq;
+
+/// Size of an AES block
+const AES_BLOCK_SIZE: usize = 16;
+
+/// Associated data lengths below this are encoded in 2 bytes
+const SHORT_AAD_LIMIT: usize = 0xff00;
+
+/// AES cipher keyed with an AES-128, AES-192, or AES-256 key
+enum Cipher {
+    /// AES-128
+    Aes128(Aes128),
+
+    /// AES-192
+    Aes192(Aes192),
+
+    /// AES-256
+    Aes256(Aes256),
+}
+
+/// AES-CCM cipher with a particular key and MAC size
+pub(crate) struct Ccm {
+    /// Underlying block cipher
+    cipher: Cipher,
+
+    /// Size of the message authentication code
+    mac_size: usize,
+}
+
+impl Ccm {
+    /// Create a new AES-CCM cipher, returning `None` if the key size or MAC
+    /// size is invalid
+    pub fn new(key: &[u8], mac_size: usize) -> Option<Self> {
+        if mac_size < 4 || mac_size > AES_BLOCK_SIZE || mac_size % 2 != 0 {
+            return None;
+        }
+
+        let cipher = match key.len() {
+            16 => Cipher::Aes128(Aes128::new_varkey(key).unwrap()),
+            24 => Cipher::Aes192(Aes192::new_varkey(key).unwrap()),
+            32 => Cipher::Aes256(Aes256::new_varkey(key).unwrap()),
+            _ => return None,
+        };
+
+        Some(Self { cipher, mac_size })
+    }
+
+    /// Encrypt a message, returning the ciphertext followed by its MAC
+    pub fn seal(&self, nonce: &[u8], associated_data: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let mut message = plaintext.to_vec();
+        let mac = self.mac(nonce, associated_data, plaintext);
+        self.apply_keystream(nonce, &mut message);
+        message.extend_from_slice(&mac);
+        message
+    }
+
+    /// Decrypt a message sealed with `seal`, returning `None` if the MAC is invalid
+    pub fn open(&self, nonce: &[u8], associated_data: &[u8], ciphertext: &[u8]) -> Option<Vec<u8>> {
+        if ciphertext.len() < self.mac_size {
+            return None;
+        }
+
+        let (ciphertext, mac) = ciphertext.split_at(ciphertext.len() - self.mac_size);
+        let mut message = ciphertext.to_vec();
+        self.apply_keystream(nonce, &mut message);
+
+        if self
+            .mac(nonce, associated_data, &message)
+            .ct_eq(mac)
+            .unwrap_u8()
+            == 1
+        {
+            Some(message)
+        } else {
+            None
+        }
+    }
+
+    /// Encrypt a single AES block in-place
+    fn encrypt_block(&self, block: &mut [u8; AES_BLOCK_SIZE]) {
+        let block = GenericArray::from_mut_slice(block);
+
+        match self.cipher {
+            Cipher::Aes128(ref cipher) => cipher.encrypt_block(block),
+            Cipher::Aes192(ref cipher) => cipher.encrypt_block(block),
+            Cipher::Aes256(ref cipher) => cipher.encrypt_block(block),
+        }
+    }
+
+    /// Compute the counter block with the given index
+    fn counter_block(nonce: &[u8], index: u64) -> [u8; AES_BLOCK_SIZE] {
+        let length_size = AES_BLOCK_SIZE - 1 - nonce.len();
+        let mut block = [0u8; AES_BLOCK_SIZE];
+        block[0] = (length_size - 1) as u8;
+        block[1..=nonce.len()].copy_from_slice(nonce);
+        encode_length(&mut block[(1 + nonce.len())..], index);
+        block
+    }
+
+    /// XOR the CTR mode keystream (starting at counter 1) into the message
+    fn apply_keystream(&self, nonce: &[u8], message: &mut [u8]) {
+        for (i, chunk) in message.chunks_mut(AES_BLOCK_SIZE).enumerate() {
+            let mut keystream = Self::counter_block(nonce, i as u64 + 1);
+            self.encrypt_block(&mut keystream);
+
+            for (byte, key_byte) in chunk.iter_mut().zip(keystream.iter()) {
+                *byte ^= key_byte;
+            }
+        }
+    }
+
+    /// Compute the encrypted CBC-MAC of the associated data and plaintext
+    fn mac(&self, nonce: &[u8], associated_data: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        assert!(nonce.len() >= 7 && nonce.len() <= 13, "bad CCM nonce size");
+        let length_size = AES_BLOCK_SIZE - 1 - nonce.len();
+
+        let mut flags = (((self.mac_size - 2) / 2) << 3) | (length_size - 1);
+
+        if !associated_data.is_empty() {
+            flags |= 0x40;
+        }
+
+        let mut state = [0u8; AES_BLOCK_SIZE];
+        state[0] = flags as u8;
+        state[1..=nonce.len()].copy_from_slice(nonce);
+        encode_length(&mut state[(1 + nonce.len())..], plaintext.len() as u64);
+        self.encrypt_block(&mut state);
+
+        if !associated_data.is_empty() {
+            let (mut encoded, length_offset) = if associated_data.len() < SHORT_AAD_LIMIT {
+                (vec![0u8; 2], 0)
+            } else {
+                (vec![0xff, 0xfe, 0, 0, 0, 0], 2)
+            };
+
+            encode_length(&mut encoded[length_offset..], associated_data.len() as u64);
+            encoded.extend_from_slice(associated_data);
+            self.cbc_mac(&mut state, &encoded);
+        }
+
+        self.cbc_mac(&mut state, plaintext);
+
+        let mut mask = Self::counter_block(nonce, 0);
+        self.encrypt_block(&mut mask);
+
+        state
+            .iter()
+            .zip(mask.iter())
+            .take(self.mac_size)
+            .map(|(byte, mask_byte)| byte ^ mask_byte)
+            .collect()
+    }
+
+    /// Absorb data (zero padded to a multiple of the block size) into the
+    /// CBC-MAC state
+    fn cbc_mac(&self, state: &mut [u8; AES_BLOCK_SIZE], data: &[u8]) {
+        for chunk in data.chunks(AES_BLOCK_SIZE) {
+            for (byte, data_byte) in state.iter_mut().zip(chunk) {
+                *byte ^= data_byte;
+            }
+
+            self.encrypt_block(state);
+        }
+    }
+}
+
+/// Encode a length (or counter) as a big endian integer filling the buffer
+fn encode_length(buffer: &mut [u8], value: u64) {
+    let len = buffer.len();
+
+    for (i, byte) in buffer.iter_mut().enumerate() {
+        let shift = 8 * (len - 1 - i);
+        *byte = (value >> shift) as u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ccm;
+
+    /// AES-CCM test vector
+    pub struct EncryptionTestVector {
+        pub key: &'static [u8],
+        pub nonce: &'static [u8],
+        pub ptlen: usize,
+        pub plaintext: &'static [u8],
+        pub ciphertext: &'static [u8],
+    }
+
+    mod vectors {
+        include!("../tests/test_vectors/aesccm.rs");
+    }
+
+    use self::vectors::AESCCM_TEST_VECTORS;
+
+    /// Split a test vector into a cipher, associated data, message, and sealed message
+    fn split(vector: &EncryptionTestVector) -> (Ccm, &[u8], &[u8], &[u8]) {
+        let mac_size = vector.ciphertext.len() - vector.plaintext.len();
+        let cipher = Ccm::new(vector.key, mac_size).unwrap();
+        let (associated_data, message) = vector.plaintext.split_at(vector.ptlen);
+        (
+            cipher,
+            associated_data,
+            message,
+            &vector.ciphertext[vector.ptlen..],
+        )
+    }
+
+    #[test]
+    fn open_test_vectors() {
+        for vector in AESCCM_TEST_VECTORS {
+            let (cipher, associated_data, message, sealed) = split(vector);
+            let opened = cipher.open(vector.nonce, associated_data, sealed).unwrap();
+            assert_eq!(opened.as_slice(), message);
+
+            let mut tampered = sealed.to_vec();
+            tampered[0] ^= 1;
+            assert!(cipher
+                .open(vector.nonce, associated_data, &tampered)
+                .is_none());
+        }
+    }
+
+    #[test]
+    fn seal_test_vectors() {
+        for vector in AESCCM_TEST_VECTORS {
+            let (cipher, associated_data, message, sealed) = split(vector);
+            let ciphertext = cipher.seal(vector.nonce, associated_data, message);
+            assert_eq!(ciphertext.as_slice(), sealed);
+        }
+    }
+}
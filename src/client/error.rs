@@ -41,6 +41,11 @@ pub enum ClientErrorKind {
         kind: DeviceErrorKind,
     },
 
+    /// Peer public key is malformed, not on the curve of the HSM-backed key,
+    /// or in an unsupported form (e.g. a compressed P-224 point)
+    #[fail(display = "invalid peer public key")]
+    InvalidPeerKey,
+
     /// Protocol error occurred
     #[fail(display = "protocol error")]
     ProtocolError,
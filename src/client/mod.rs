@@ -14,6 +14,7 @@ pub use self::error::{ClientError, ClientErrorKind};
 
 use self::error::ClientErrorKind::*;
 use crate::{
+    algorithm::Algorithm,
     asymmetric::{self, commands::*, PublicKey},
     attestation::{self, commands::*},
     audit::{commands::*, *},
@@ -26,24 +27,23 @@ use crate::{
     ecdsa::{self, commands::*},
     ed25519::{self, commands::*},
     hmac::{self, commands::*},
+    kex::{self, commands::*},
     object::{self, commands::*, generate},
     opaque::{self, commands::*},
     otp::{self, commands::*},
+    rsa::{self, oaep::commands::*, pkcs1::commands::*, pss::commands::*},
     serialization::{deserialize, serialize},
     session::{self, Session},
+    ssh::{self, commands::*},
+    template::{self, commands::*},
     uuid,
     wrap::{self, commands::*},
 };
+use rand_os::{rand_core::RngCore, OsRng};
 use std::{
     sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
-#[cfg(feature = "rsa-preview")]
-use {
-    crate::rsa::{self, pkcs1::commands::*, pss::commands::*},
-    byteorder::{BigEndian, ByteOrder},
-    sha2::{Digest, Sha256},
-};
 
 /// YubiHSM client: main API in this crate for accessing functions of the
 /// HSM hardware device.
@@ -55,8 +55,9 @@ pub struct Client {
     /// Encrypted session with the HSM (if we have one open)
     session: Arc<Mutex<Option<Session>>>,
 
-    /// Cached `Credentials` for reconnecting closed sessions
-    credentials: Option<Credentials>,
+    /// Cached `Credentials` for reconnecting closed sessions (shared between
+    /// clones, like the session, so they see changes to the session's key)
+    credentials: Arc<Mutex<Option<Credentials>>>,
 }
 
 impl Client {
@@ -72,12 +73,12 @@ impl Client {
         credentials: Credentials,
         reconnect: bool,
     ) -> Result<Self, ClientError> {
-        let mut client = Self::create(connector, credentials)?;
+        let client = Self::create(connector, credentials)?;
         client.connect()?;
 
         // Clear credentials if reconnecting has been disabled
         if !reconnect {
-            client.credentials = None;
+            *client.credentials.lock().unwrap() = None;
         }
 
         Ok(client)
@@ -88,12 +89,17 @@ impl Client {
         let client = Self {
             connector,
             session: Arc::new(Mutex::new(None)),
-            credentials: Some(credentials),
+            credentials: Arc::new(Mutex::new(Some(credentials))),
         };
 
         Ok(client)
     }
 
+    /// Get the connector this client communicates with the HSM over
+    pub(crate) fn connector(&self) -> &Connector {
+        &self.connector
+    }
+
     /// Try to make a clone of this client
 
     /// Connect to the HSM (idempotently, i.e. returns success if we have
@@ -119,6 +125,8 @@ impl Client {
         let session = Session::open(
             self.connector.clone(),
             self.credentials
+                .lock()
+                .unwrap()
                 .as_ref()
                 .ok_or_else(|| err!(AuthenticationError, "session reconnection disabled"))?,
             session::Timeout::default(),
@@ -164,6 +172,174 @@ impl Client {
         Ok(())
     }
 
+    /// Change the authentication key used to establish the current session,
+    /// keeping its object ID and other attributes intact. Requires the
+    /// `CHANGE_AUTHENTICATION_KEY` capability.
+    ///
+    /// The current session remains open. If the changed key is the one this
+    /// client authenticates with, its cached `Credentials` are updated so
+    /// reconnecting uses the new key.
+    ///
+    /// <https://developers.yubico.com/YubiHSM2/Commands/Change_Authentication_Key.html>
+    pub fn change_authentication_key<K>(
+        &self,
+        key_id: object::Id,
+        authentication_key: K,
+    ) -> Result<(), ClientError>
+    where
+        K: Into<authentication::Key>,
+    {
+        let authentication_key = authentication_key.into();
+
+        self.send_command(ChangeAuthenticationKeyCommand {
+            key_id,
+            algorithm: authentication::Algorithm::YUBICO_AES,
+            authentication_key: authentication_key.clone(),
+        })?;
+
+        if let Some(ref mut credentials) = *self.credentials.lock().unwrap() {
+            if credentials.authentication_key_id == key_id {
+                credentials.authentication_key = authentication_key;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Create a Yubico OTP AEAD from a YubiKey's OTP key and private ID,
+    /// encrypted under the OTP AEAD key with the given ID.
+    ///
+    /// <https://developers.yubico.com/YubiHSM2/Commands/Create_Otp_Aead.html>
+    pub fn create_otp_aead(
+        &self,
+        key_id: object::Id,
+        otp_key: &[u8],
+        private_id: &[u8],
+    ) -> Result<otp::Aead, ClientError> {
+        ensure!(
+            otp_key.len() == otp::KEY_SIZE,
+            ProtocolError,
+            "invalid OTP key length: {} (expected {})",
+            otp_key.len(),
+            otp::KEY_SIZE
+        );
+
+        ensure!(
+            private_id.len() == otp::PRIVATE_ID_SIZE,
+            ProtocolError,
+            "invalid private ID length: {} (expected {})",
+            private_id.len(),
+            otp::PRIVATE_ID_SIZE
+        );
+
+        let mut data = otp_key.to_vec();
+        data.extend_from_slice(private_id);
+
+        Ok(self.send_command(CreateOtpAeadCommand { key_id, data })?.0)
+    }
+
+    /// Decrypt a (binary, i.e. ModHex-decoded) Yubico OTP using the given
+    /// AEAD, which is encrypted under the OTP AEAD key with the given ID.
+    ///
+    /// Returns an error if the OTP is invalid (i.e. fails to authenticate or
+    /// doesn't match the AEAD's private ID). It's up to the caller to check
+    /// the returned counters to prevent replay attacks.
+    ///
+    /// <https://developers.yubico.com/YubiHSM2/Commands/Decrypt_Otp.html>
+    pub fn decrypt_otp(
+        &self,
+        key_id: object::Id,
+        aead: &otp::Aead,
+        otp: &[u8],
+    ) -> Result<otp::DecryptedOtp, ClientError> {
+        ensure!(
+            aead.len() == otp::AEAD_SIZE,
+            ProtocolError,
+            "invalid AEAD length: {} (expected {})",
+            aead.len(),
+            otp::AEAD_SIZE
+        );
+
+        ensure!(
+            otp.len() == otp::OTP_SIZE,
+            ProtocolError,
+            "invalid OTP length: {} (expected {})",
+            otp.len(),
+            otp::OTP_SIZE
+        );
+
+        let mut data = aead.as_slice().to_vec();
+        data.extend_from_slice(otp);
+
+        Ok(self
+            .send_command(DecryptOtpCommand { key_id, data })?
+            .into())
+    }
+
+    /// Decrypt data which was encrypted (using RSAES-OAEP) under the public
+    /// key of the given RSA key ID.
+    ///
+    /// The `label_hash` is the digest of the OAEP label (which can be computed
+    /// using `rsa::oaep::hash_label`). Its length selects the OAEP hash
+    /// function (SHA-1, SHA-256, SHA-384 or SHA-512), which may differ from
+    /// the hash function used by MGF1.
+    ///
+    /// <https://developers.yubico.com/YubiHSM2/Commands/Decrypt_Oaep.html>
+    pub fn decrypt_rsa_oaep<C>(
+        &self,
+        key_id: object::Id,
+        mgf1_hash_alg: rsa::mgf::Algorithm,
+        label_hash: &[u8],
+        ciphertext: C,
+    ) -> Result<Vec<u8>, ClientError>
+    where
+        C: Into<Vec<u8>>,
+    {
+        match label_hash.len() {
+            20 | 32 | 48 | 64 => (),
+            other => fail!(
+                ProtocolError,
+                "invalid OAEP label hash length: {} (expected 20, 32, 48, or 64 bytes)",
+                other
+            ),
+        }
+
+        // The label hash is sent immediately after the ciphertext
+        let mut data: Vec<u8> = ciphertext.into();
+        data.extend_from_slice(label_hash);
+
+        Ok(self
+            .send_command(DecryptOaepCommand {
+                key_id,
+                mgf1_hash_alg,
+                data,
+            })?
+            .0)
+    }
+
+    /// Decrypt data which was encrypted (using RSAES-PKCS#1v1.5) under the
+    /// public key of the given RSA key ID.
+    ///
+    /// **WARNING**: PKCS#1v1.5 encryption is a legacy scheme which is
+    /// susceptible to padding oracle attacks. Prefer RSA-OAEP where possible.
+    ///
+    /// <https://developers.yubico.com/YubiHSM2/Commands/Decrypt_Pkcs1.html>
+    pub fn decrypt_rsa_pkcs1v15<C>(
+        &self,
+        key_id: object::Id,
+        ciphertext: C,
+    ) -> Result<Vec<u8>, ClientError>
+    where
+        C: Into<Vec<u8>>,
+    {
+        Ok(self
+            .send_command(DecryptPkcs1Command {
+                key_id,
+                data: ciphertext.into(),
+            })?
+            .0)
+    }
+
     /// Delete an object of the given ID and type.
     ///
     /// <https://developers.yubico.com/YubiHSM2/Commands/Delete_Object.html>
@@ -179,6 +355,62 @@ impl Client {
         Ok(())
     }
 
+    /// Compute an Elliptic Curve Diffie-Hellman shared secret using the given
+    /// HSM-backed key and a peer's public key.
+    ///
+    /// The peer's public key must be a point on the same curve as the HSM-backed
+    /// key (NIST P-256/P-384/P-521 or secp256k1), either as a compressed or
+    /// uncompressed SEC1 point (i.e. `0x02/0x03 || X` or `0x04 || X || Y`), or
+    /// in the untagged `X || Y` form returned by `get_public_key`. Compressed
+    /// points are decompressed before being sent to the HSM.
+    ///
+    /// Returns an `InvalidPeerKey` error if the peer's public key is in none of
+    /// these forms, or a compressed point can't be decompressed.
+    ///
+    /// <https://developers.yubico.com/YubiHSM2/Commands/Derive_Ecdh.html>
+    pub fn derive_ecdh<K>(
+        &self,
+        key_id: object::Id,
+        peer_public_key: K,
+    ) -> Result<kex::SharedSecret, ClientError>
+    where
+        K: AsRef<[u8]>,
+    {
+        let point = peer_public_key.as_ref();
+        let mut public_key = Vec::with_capacity(point.len() + 1);
+
+        match point.len() {
+            // Untagged `X || Y` for P-256/secp256k1, P-384, and P-521 respectively
+            64 | 96 | 132 => {
+                public_key.push(0x04);
+                public_key.extend_from_slice(point);
+            }
+            65 | 97 | 133 if point[0] == 0x04 => public_key.extend_from_slice(point),
+            33 | 49 | 67 if point[0] == 0x02 || point[0] == 0x03 => {
+                // Decompressing requires knowing the curve, which is ambiguous
+                // from the length alone (i.e. P-256 vs secp256k1)
+                let algorithm = self.get_public_key(key_id)?.algorithm;
+
+                let decompressed = PublicKey::from_sec1(algorithm, point).map_err(|e| {
+                    err!(
+                        InvalidPeerKey,
+                        "couldn't decompress {:?} peer public key: {}",
+                        algorithm,
+                        e
+                    )
+                })?;
+
+                public_key.push(0x04);
+                public_key.extend_from_slice(decompressed.as_slice());
+            }
+            other => fail!(InvalidPeerKey, "invalid peer public key length: {}", other),
+        }
+
+        Ok(self
+            .send_command(DeriveEcdhCommand { key_id, public_key })?
+            .0)
+    }
+
     /// Get information about the HSM device.
     ///
     /// <https://developers.yubico.com/YubiHSM2/Commands/Device_Info.html>
@@ -262,6 +494,34 @@ impl Client {
             .key_id)
     }
 
+    /// Generate a new OTP AEAD key within the HSM. The nonce ID is included
+    /// in the nonce of every AEAD created with the key, and must be unique
+    /// for each HSM the key is shared with.
+    ///
+    /// <https://developers.yubico.com/YubiHSM2/Commands/Generate_Otp_Aead_Key.html>
+    pub fn generate_otp_aead_key(
+        &self,
+        key_id: object::Id,
+        label: object::Label,
+        domains: Domain,
+        capabilities: Capability,
+        algorithm: otp::Algorithm,
+        nonce_id: otp::NonceId,
+    ) -> Result<object::Id, ClientError> {
+        Ok(self
+            .send_command(GenOtpAeadKeyCommand {
+                params: generate::Params {
+                    key_id,
+                    label,
+                    domains,
+                    capabilities,
+                    algorithm: algorithm.into(),
+                },
+                nonce_id,
+            })?
+            .key_id)
+    }
+
     /// Generate a new wrap key within the HSM.
     ///
     /// Delegated capabilities are the set of `Capability` bits that an object is allowed to have
@@ -400,6 +660,14 @@ impl Client {
         Ok(self.send_command(GetStorageInfoCommand {})?.into())
     }
 
+    /// Get an SSH template stored in the HSM. Parse it with
+    /// `template::SshTemplate::from_bytes`.
+    ///
+    /// <https://developers.yubico.com/YubiHSM2/Commands/Get_Template.html>
+    pub fn get_template(&self, object_id: object::Id) -> Result<Vec<u8>, ClientError> {
+        Ok(self.send_command(GetTemplateCommand { object_id })?.data)
+    }
+
     /// Import an encrypted object from the HSM using the given key-wrapping key.
     ///
     /// <https://developers.yubico.com/YubiHSM2/Commands/Import_Wrapped.html>
@@ -587,7 +855,11 @@ impl Client {
             .object_id)
     }
 
-    /// Put an existing OTP AEAD key into the HSM.
+    /// Put an existing OTP AEAD key into the HSM with a randomly generated
+    /// nonce ID.
+    ///
+    /// Use `put_otp_aead_key_with_nonce_id` to choose the nonce ID, e.g. to
+    /// ensure it's unique among HSMs the key is shared with.
     ///
     /// <https://developers.yubico.com/YubiHSM2/Commands/Put_Otp_Aead_Key.html>
     pub fn put_otp_aead_key<K>(
@@ -599,6 +871,37 @@ impl Client {
         algorithm: otp::Algorithm,
         key_bytes: K,
     ) -> Result<object::Id, ClientError>
+    where
+        K: Into<Vec<u8>>,
+    {
+        let nonce_id = OsRng::new().unwrap().next_u32();
+
+        self.put_otp_aead_key_with_nonce_id(
+            key_id,
+            label,
+            domains,
+            capabilities,
+            algorithm,
+            nonce_id,
+            key_bytes,
+        )
+    }
+
+    /// Put an existing OTP AEAD key into the HSM. The nonce ID is included
+    /// in the nonce of every AEAD created with the key, and must be unique
+    /// for each HSM the key is shared with.
+    ///
+    /// <https://developers.yubico.com/YubiHSM2/Commands/Put_Otp_Aead_Key.html>
+    pub fn put_otp_aead_key_with_nonce_id<K>(
+        &self,
+        key_id: object::Id,
+        label: object::Label,
+        domains: Domain,
+        capabilities: Capability,
+        algorithm: otp::Algorithm,
+        nonce_id: otp::NonceId,
+        key_bytes: K,
+    ) -> Result<object::Id, ClientError>
     where
         K: Into<Vec<u8>>,
     {
@@ -623,11 +926,41 @@ impl Client {
                     capabilities,
                     algorithm: algorithm.into(),
                 },
+                nonce_id,
                 data,
             })?
             .key_id)
     }
 
+    /// Put an SSH template (e.g. a serialized `template::SshTemplate`) into the HSM.
+    ///
+    /// <https://developers.yubico.com/YubiHSM2/Commands/Put_Template.html>
+    pub fn put_template<T>(
+        &self,
+        object_id: object::Id,
+        label: object::Label,
+        domains: Domain,
+        capabilities: Capability,
+        algorithm: template::Algorithm,
+        template_data: T,
+    ) -> Result<object::Id, ClientError>
+    where
+        T: Into<Vec<u8>>,
+    {
+        Ok(self
+            .send_command(PutTemplateCommand {
+                params: object::import::Params {
+                    id: object_id,
+                    label,
+                    domains,
+                    capabilities,
+                    algorithm: algorithm.into(),
+                },
+                data: template_data.into(),
+            })?
+            .object_id)
+    }
+
     /// Put an existing wrap key into the HSM.
     ///
     /// <https://developers.yubico.com/YubiHSM2/Commands/Put_Wrap_Key.html>
@@ -671,6 +1004,14 @@ impl Client {
             .key_id)
     }
 
+    /// Create a Yubico OTP AEAD from a randomly generated OTP key and private
+    /// ID, encrypted under the OTP AEAD key with the given ID.
+    ///
+    /// <https://developers.yubico.com/YubiHSM2/Commands/Randomize_Otp_Aead.html>
+    pub fn randomize_otp_aead(&self, key_id: object::Id) -> Result<otp::Aead, ClientError> {
+        Ok(self.send_command(RandomizeOtpAeadCommand { key_id })?.0)
+    }
+
     /// Reset the HSM to a factory default state and reboot, clearing all
     /// stored objects and restoring the default auth key.
     ///
@@ -691,6 +1032,24 @@ impl Client {
         Ok(())
     }
 
+    /// Re-encrypt a Yubico OTP AEAD from one OTP AEAD key to another.
+    ///
+    /// <https://developers.yubico.com/YubiHSM2/Commands/Rewrap_Otp_Aead.html>
+    pub fn rewrap_otp_aead(
+        &self,
+        from_key_id: object::Id,
+        to_key_id: object::Id,
+        aead: otp::Aead,
+    ) -> Result<otp::Aead, ClientError> {
+        Ok(self
+            .send_command(RewrapOtpAeadCommand {
+                from_key_id,
+                to_key_id,
+                aead,
+            })?
+            .0)
+    }
+
     /// Configure the audit policy settings for a particular command, e.g. auditing
     /// should be `On`, `Off`, or `Fix` (i.e. fixed permanently on).
     ///
@@ -822,63 +1181,161 @@ impl Client {
             .into())
     }
 
-    /// Compute an RSASSA-PKCS#1v1.5 signature of the SHA-256 hash of the given data.
+    /// Compute an RSASSA-PKCS#1v1.5 signature of the given message, which is
+    /// hashed using the digest of the given `rsa::Algorithm::PKCS1_*` algorithm.
     ///
-    /// **WARNING**: This method has not been tested and is not confirmed to actually work! Use at your
-    /// own risk!
+    /// <https://developers.yubico.com/YubiHSM2/Commands/Sign_Pkcs1.html>
+    pub fn sign_rsa_pkcs1v15(
+        &self,
+        key_id: object::Id,
+        algorithm: rsa::Algorithm,
+        msg: &[u8],
+    ) -> Result<rsa::pkcs1::Signature, ClientError> {
+        self.sign_rsa_pkcs1v15_prehashed(key_id, algorithm, &algorithm.digest(msg))
+    }
+
+    /// Compute an RSASSA-PKCS#1v1.5 signature of a digest computed with the
+    /// hash function of the given `rsa::Algorithm::PKCS1_*` algorithm.
     ///
     /// <https://developers.yubico.com/YubiHSM2/Commands/Sign_Pkcs1.html>
-    #[cfg(feature = "rsa-preview")]
-    pub fn sign_rsa_pkcs1v15_sha256(
+    pub fn sign_rsa_pkcs1v15_prehashed(
         &self,
         key_id: object::Id,
-        data: &[u8],
+        algorithm: rsa::Algorithm,
+        digest: &[u8],
     ) -> Result<rsa::pkcs1::Signature, ClientError> {
+        ensure!(
+            algorithm.is_pkcs1(),
+            ProtocolError,
+            "not an RSASSA-PKCS#1v1.5 algorithm: {:?}",
+            algorithm
+        );
+
+        ensure!(
+            digest.len() == algorithm.digest_len(),
+            ProtocolError,
+            "invalid digest length for {:?}: {} (expected {})",
+            algorithm,
+            digest.len(),
+            algorithm.digest_len()
+        );
+
         Ok(self
             .send_command(SignPkcs1Command {
                 key_id,
-                digest: Sha256::digest(data).as_slice().into(),
+                digest: digest.into(),
             })?
             .into())
     }
 
-    /// Compute an RSASSA-PSS signature of the SHA-256 hash of the given data with the given key ID.
+    /// Compute an RSASSA-PKCS#1v1.5 signature of the SHA-256 hash of the given data.
+    ///
+    /// <https://developers.yubico.com/YubiHSM2/Commands/Sign_Pkcs1.html>
+    pub fn sign_rsa_pkcs1v15_sha256(
+        &self,
+        key_id: object::Id,
+        data: &[u8],
+    ) -> Result<rsa::pkcs1::Signature, ClientError> {
+        self.sign_rsa_pkcs1v15(key_id, rsa::Algorithm::PKCS1_SHA256, data)
+    }
+
+    /// Compute an RSASSA-PSS signature of the given message, which is hashed
+    /// using the digest of the given `rsa::Algorithm::PSS_*` algorithm.
     ///
-    /// **WARNING**: This method has not been tested and is not confirmed to actually work! Use at your
-    /// own risk!
+    /// MGF1 uses the same hash function, and the salt is the size of the digest.
     ///
     /// <https://developers.yubico.com/YubiHSM2/Commands/Sign_Pss.html>
-    #[cfg(feature = "rsa-preview")]
-    pub fn sign_rsa_pss_sha256(
+    pub fn sign_rsa_pss(
         &self,
         key_id: object::Id,
-        data: &[u8],
+        algorithm: rsa::Algorithm,
+        msg: &[u8],
+    ) -> Result<rsa::pss::Signature, ClientError> {
+        self.sign_rsa_pss_prehashed(key_id, algorithm, &algorithm.digest(msg))
+    }
+
+    /// Compute an RSASSA-PSS signature of a digest computed with the hash
+    /// function of the given `rsa::Algorithm::PSS_*` algorithm.
+    ///
+    /// MGF1 uses the same hash function, and the salt is the size of the digest.
+    ///
+    /// <https://developers.yubico.com/YubiHSM2/Commands/Sign_Pss.html>
+    pub fn sign_rsa_pss_prehashed(
+        &self,
+        key_id: object::Id,
+        algorithm: rsa::Algorithm,
+        digest: &[u8],
     ) -> Result<rsa::pss::Signature, ClientError> {
         ensure!(
-            data.len() > rsa::pss::MAX_MESSAGE_SIZE,
+            algorithm.is_pss(),
             ProtocolError,
-            "message too large to be signed (max: {})",
-            rsa::pss::MAX_MESSAGE_SIZE
+            "not an RSASSA-PSS algorithm: {:?}",
+            algorithm
         );
 
-        let mut hasher = Sha256::default();
-
-        let mut length = [0u8; 2];
-        BigEndian::write_u16(&mut length, data.len() as u16);
-        hasher.input(&length);
-        hasher.input(data);
-        let digest = hasher.result();
+        ensure!(
+            digest.len() == algorithm.digest_len(),
+            ProtocolError,
+            "invalid digest length for {:?}: {} (expected {})",
+            algorithm,
+            digest.len(),
+            algorithm.digest_len()
+        );
 
         Ok(self
             .send_command(SignPssCommand {
                 key_id,
-                mgf1_hash_alg: rsa::mgf::Algorithm::SHA256,
-                salt_len: digest.as_slice().len() as u16,
-                digest: digest.as_slice().into(),
+                mgf1_hash_alg: algorithm.mgf1_hash_alg(),
+                salt_len: digest.len() as u16,
+                digest: digest.into(),
             })?
             .into())
     }
 
+    /// Compute an RSASSA-PSS signature of the SHA-256 hash of the given data with the given key ID.
+    ///
+    /// <https://developers.yubico.com/YubiHSM2/Commands/Sign_Pss.html>
+    pub fn sign_rsa_pss_sha256(
+        &self,
+        key_id: object::Id,
+        data: &[u8],
+    ) -> Result<rsa::pss::Signature, ClientError> {
+        self.sign_rsa_pss(key_id, rsa::Algorithm::PSS_SHA256, data)
+    }
+
+    /// Sign an OpenSSH certificate with the given CA key ID, subject to the
+    /// constraints of the given SSH template.
+    ///
+    /// The request must be authorized by a timestamp signed with the
+    /// template's timestamp key (see `ssh::CertificateRequest`). Supported
+    /// algorithms are Ed25519, ECDSA (P-256/P-384/P-521), and RSASSA-PKCS#1v1.5
+    /// (SHA-1/SHA-256/SHA-512), and must match the type of the CA key.
+    ///
+    /// <https://developers.yubico.com/YubiHSM2/Commands/Sign_Ssh_Certificate.html>
+    pub fn sign_ssh_certificate<A>(
+        &self,
+        key_id: object::Id,
+        template_id: object::Id,
+        algorithm: A,
+        request: &ssh::SignedRequest,
+    ) -> Result<ssh::Certificate, ClientError>
+    where
+        A: Into<Algorithm>,
+    {
+        let algorithm = algorithm.into();
+
+        let signature = self
+            .send_command(SignSshCertificateCommand {
+                key_id,
+                template_id,
+                algorithm,
+                request: request.to_bytes(),
+            })?
+            .0;
+
+        ssh::Certificate::new(request, algorithm, &signature).map_err(|e| err!(ProtocolError, e))
+    }
+
     /// Decrypt data which was encrypted (using AES-CCM) under a wrap key.
     ///
     /// <https://developers.yubico.com/YubiHSM2/Commands/Unwrap_Data.html>
@@ -3,6 +3,10 @@ use serde::{
     de::{Deserialize, Deserializer, Error as DeError},
     ser::{Serialize, Serializer},
 };
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
 
 /// Command IDs for `YubiHSM 2` operations
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -133,6 +137,85 @@ impl Code {
     pub fn to_u8(self) -> u8 {
         self as u8
     }
+
+    /// Name of this command (e.g. `sign-eddsa`)
+    pub fn name(self) -> &'static str {
+        match self {
+            Code::Unknown => "unknown",
+            Code::Echo => "echo",
+            Code::CreateSession => "create-session",
+            Code::AuthenticateSession => "authenticate-session",
+            Code::SessionMessage => "session-message",
+            Code::DeviceInfo => "device-info",
+            Code::Bsl => "bsl",
+            Code::ResetDevice => "reset-device",
+            Code::Command9 => "command9",
+            Code::CloseSession => "close-session",
+            Code::GetStorageInfo => "get-storage-info",
+            Code::PutOpaqueObject => "put-opaque-object",
+            Code::GetOpaqueObject => "get-opaque-object",
+            Code::PutAuthenticationKey => "put-authentication-key",
+            Code::PutAsymmetricKey => "put-asymmetric-key",
+            Code::GenerateAsymmetricKey => "generate-asymmetric-key",
+            Code::SignPkcs1 => "sign-pkcs1",
+            Code::ListObjects => "list-objects",
+            Code::DecryptPkcs1 => "decrypt-pkcs1",
+            Code::ExportWrapped => "export-wrapped",
+            Code::ImportWrapped => "import-wrapped",
+            Code::PutWrapKey => "put-wrap-key",
+            Code::GetLogEntries => "get-log-entries",
+            Code::GetObjectInfo => "get-object-info",
+            Code::SetOption => "set-option",
+            Code::GetOption => "get-option",
+            Code::GetPseudoRandom => "get-pseudo-random",
+            Code::PutHmacKey => "put-hmac-key",
+            Code::SignHmac => "sign-hmac",
+            Code::GetPublicKey => "get-public-key",
+            Code::SignPss => "sign-pss",
+            Code::SignEcdsa => "sign-ecdsa",
+            Code::DeriveEcdh => "derive-ecdh",
+            Code::DeleteObject => "delete-object",
+            Code::DecryptOaep => "decrypt-oaep",
+            Code::GenerateHmacKey => "generate-hmac-key",
+            Code::GenerateWrapKey => "generate-wrap-key",
+            Code::VerifyHmac => "verify-hmac",
+            Code::SignSshCertificate => "sign-ssh-certificate",
+            Code::PutTemplate => "put-template",
+            Code::GetTemplate => "get-template",
+            Code::DecryptOtp => "decrypt-otp",
+            Code::CreateOtpAead => "create-otp-aead",
+            Code::RandomizeOtpAead => "randomize-otp-aead",
+            Code::RewrapOtpAead => "rewrap-otp-aead",
+            Code::SignAttestationCertificate => "sign-attestation-certificate",
+            Code::PutOtpAead => "put-otp-aead",
+            Code::GenerateOtpAead => "generate-otp-aead",
+            Code::SetLogIndex => "set-log-index",
+            Code::WrapData => "wrap-data",
+            Code::UnwrapData => "unwrap-data",
+            Code::SignEddsa => "sign-eddsa",
+            Code::BlinkDevice => "blink-device",
+            Code::ChangeAuthenticationKey => "change-authentication-key",
+            Code::Error => "error",
+        }
+    }
+}
+
+impl Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Code {
+    type Err = Error;
+
+    /// Parse a command from its name (e.g. `sign-eddsa`)
+    fn from_str(s: &str) -> Result<Self, Error> {
+        (0..=0xff)
+            .filter_map(|byte| Code::from_u8(byte).ok())
+            .find(|code| code.name() == s)
+            .ok_or_else(|| format_err!("unknown command: {}", s))
+    }
 }
 
 impl Serialize for Code {
@@ -153,3 +236,20 @@ impl<'de> Deserialize<'de> for Code {
             .or_else(|e| Err(D::Error::custom(format!("{}", e))))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_round_trip() {
+        for byte in 0..=0xff {
+            if let Ok(code) = Code::from_u8(byte) {
+                assert_eq!(code.name().parse::<Code>().unwrap(), code);
+            }
+        }
+
+        assert_eq!(Code::SignEddsa.to_string(), "sign-eddsa");
+        assert!("sign-everything".parse::<Code>().is_err());
+    }
+}
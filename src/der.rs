@@ -0,0 +1,189 @@
+//! Minimal ASN.1 DER encoder and decoder, sufficient for public keys
+//! (i.e. `SubjectPublicKeyInfo`) and X.509 certificates.
+
+/// `BOOLEAN` tag
+pub(crate) const BOOLEAN: u8 = 0x01;
+
+/// `INTEGER` tag
+pub(crate) const INTEGER: u8 = 0x02;
+
+/// `BIT STRING` tag
+pub(crate) const BIT_STRING: u8 = 0x03;
+
+/// `OCTET STRING` tag
+pub(crate) const OCTET_STRING: u8 = 0x04;
+
+/// `NULL` tag
+pub(crate) const NULL: u8 = 0x05;
+
+/// `OBJECT IDENTIFIER` tag
+pub(crate) const OBJECT_IDENTIFIER: u8 = 0x06;
+
+/// `UTF8String` tag
+pub(crate) const UTF8_STRING: u8 = 0x0c;
+
+/// `SEQUENCE` tag (constructed)
+pub(crate) const SEQUENCE: u8 = 0x30;
+
+/// `SET` tag (constructed)
+pub(crate) const SET: u8 = 0x31;
+
+/// Is the given tag context-specific (i.e. `[n]`)?
+pub(crate) fn is_context_specific(tag: u8) -> bool {
+    tag & 0xc0 == 0x80
+}
+
+/// Encode a tag-length-value triple
+pub(crate) fn encode(tag: u8, value: &[u8]) -> Vec<u8> {
+    let mut result = vec![tag];
+
+    if value.len() < 0x80 {
+        result.push(value.len() as u8);
+    } else {
+        let len_bytes: Vec<u8> = (0..4)
+            .rev()
+            .map(|i| (value.len() >> (i * 8)) as u8)
+            .skip_while(|&byte| byte == 0)
+            .collect();
+
+        result.push(0x80 | len_bytes.len() as u8);
+        result.extend_from_slice(&len_bytes);
+    }
+
+    result.extend_from_slice(value);
+    result
+}
+
+/// Encode an unsigned big endian integer as an `INTEGER`
+pub(crate) fn encode_integer(bytes: &[u8]) -> Vec<u8> {
+    let leading_zeroes = bytes.iter().take_while(|&&byte| byte == 0).count();
+    let magnitude = &bytes[leading_zeroes..];
+
+    // Positive integers with the high bit set need a leading zero byte
+    let mut value = vec![];
+
+    if magnitude
+        .first()
+        .map(|&byte| byte & 0x80 != 0)
+        .unwrap_or(true)
+    {
+        value.push(0);
+    }
+
+    value.extend_from_slice(magnitude);
+    encode(INTEGER, &value)
+}
+
+/// Encode a `BIT STRING` with no unused bits
+pub(crate) fn encode_bit_string(bytes: &[u8]) -> Vec<u8> {
+    let mut value = vec![0];
+    value.extend_from_slice(bytes);
+    encode(BIT_STRING, &value)
+}
+
+/// Decode the value of an unsigned `INTEGER`, stripping any leading zeroes
+pub(crate) fn decode_integer(value: &[u8]) -> Option<&[u8]> {
+    // Negative integers are never valid in the contexts we parse
+    if value.is_empty() || value[0] & 0x80 != 0 {
+        return None;
+    }
+
+    let leading_zeroes = value.iter().take_while(|&&byte| byte == 0).count();
+    Some(&value[leading_zeroes..])
+}
+
+/// Decode the value of a `BIT STRING`, which must have no unused bits
+pub(crate) fn decode_bit_string(value: &[u8]) -> Option<&[u8]> {
+    match value.split_first() {
+        Some((&0, bytes)) => Some(bytes),
+        _ => None,
+    }
+}
+
+/// Decoder for a sequence of DER-encoded values
+#[derive(Debug)]
+pub(crate) struct Decoder<'a>(&'a [u8]);
+
+impl<'a> Decoder<'a> {
+    /// Create a new decoder for the given DER data
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Decoder(bytes)
+    }
+
+    /// Get the tag of the next value without consuming it
+    pub fn peek_tag(&self) -> Option<u8> {
+        self.0.first().cloned()
+    }
+
+    /// Read the next value, returning its tag and value
+    pub fn read_any(&mut self) -> Option<(u8, &'a [u8])> {
+        let (&tag, remaining) = self.0.split_first()?;
+
+        // Multi-byte tags aren't supported
+        if tag & 0x1f == 0x1f {
+            return None;
+        }
+
+        let (&first_len_byte, remaining) = remaining.split_first()?;
+
+        let (len, remaining) = if first_len_byte < 0x80 {
+            (usize::from(first_len_byte), remaining)
+        } else {
+            let len_size = usize::from(first_len_byte & 0x7f);
+
+            if len_size == 0 || len_size > 4 || remaining.len() < len_size {
+                return None;
+            }
+
+            let (len_bytes, remaining) = remaining.split_at(len_size);
+
+            // Lengths must be minimally encoded
+            if len_bytes[0] == 0 {
+                return None;
+            }
+
+            let len = len_bytes
+                .iter()
+                .fold(0usize, |len, &byte| (len << 8) | usize::from(byte));
+
+            if len < 0x80 {
+                return None;
+            }
+
+            (len, remaining)
+        };
+
+        if remaining.len() < len {
+            return None;
+        }
+
+        let (value, remaining) = remaining.split_at(len);
+        self.0 = remaining;
+
+        Some((tag, value))
+    }
+
+    /// Read the next value, which must have the given tag
+    pub fn read(&mut self, expected_tag: u8) -> Option<&'a [u8]> {
+        let (tag, value) = self.read_any()?;
+
+        if tag == expected_tag {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Read the next value, which must have the given tag, returning its
+    /// complete encoding (i.e. including the tag and length)
+    pub fn read_encoded(&mut self, expected_tag: u8) -> Option<&'a [u8]> {
+        let bytes = self.0;
+        self.read(expected_tag)?;
+        Some(&bytes[..(bytes.len() - self.0.len())])
+    }
+
+    /// Have all values been decoded?
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
@@ -19,6 +19,12 @@ impl Display for Number {
     }
 }
 
+impl From<u32> for Number {
+    fn from(number: u32) -> Number {
+        Number(number)
+    }
+}
+
 impl FromStr for Number {
     type Err = Error;
 
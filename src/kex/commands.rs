@@ -0,0 +1,31 @@
+//! Key exchange commands
+//!
+//! <https://developers.yubico.com/YubiHSM2/Commands/Derive_Ecdh.html>
+
+use crate::{
+    command::{self, Command},
+    kex, object,
+    response::Response,
+};
+
+/// Request parameters for `command::derive_ecdh`
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct DeriveEcdhCommand {
+    /// ID of the key to perform ECDH with
+    pub key_id: object::Id,
+
+    /// Peer's public key (uncompressed SEC1 point)
+    pub public_key: Vec<u8>,
+}
+
+impl Command for DeriveEcdhCommand {
+    type ResponseType = DeriveEcdhResponse;
+}
+
+/// ECDH shared secret
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct DeriveEcdhResponse(pub(crate) kex::SharedSecret);
+
+impl Response for DeriveEcdhResponse {
+    const COMMAND_CODE: command::Code = command::Code::DeriveEcdh;
+}
@@ -1,5 +1,7 @@
 //! Key exchange support
 
 mod algorithm;
+pub(crate) mod commands;
+mod shared_secret;
 
-pub use self::algorithm::Algorithm;
+pub use self::{algorithm::Algorithm, shared_secret::SharedSecret};
@@ -0,0 +1,50 @@
+//! Shared secrets computed by key exchange algorithms
+
+use std::fmt::{self, Debug};
+use zeroize::Zeroize;
+
+/// Shared secret resulting from a key exchange (e.g. the x-coordinate of
+/// an ECDH result). The secret is zeroized when dropped.
+///
+/// Raw shared secrets are not uniformly random, so they should be passed
+/// through a KDF (e.g. HKDF) before being used as keys.
+#[derive(Deserialize, Serialize)]
+pub struct SharedSecret(Vec<u8>);
+
+#[allow(clippy::len_without_is_empty)]
+impl SharedSecret {
+    /// Create a new shared secret from the given bytes
+    #[cfg(feature = "mockhsm")]
+    pub(crate) fn new(bytes: Vec<u8>) -> Self {
+        SharedSecret(bytes)
+    }
+
+    /// Get length of the shared secret
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Get slice of the inner byte vector
+    pub fn as_slice(&self) -> &[u8] {
+        self.as_ref()
+    }
+}
+
+impl AsRef<[u8]> for SharedSecret {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl Debug for SharedSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Avoid leaking secrets in debug messages
+        write!(f, "yubihsm::kex::SharedSecret(...)")
+    }
+}
+
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
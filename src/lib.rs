@@ -89,11 +89,16 @@ pub mod error;
 #[macro_use]
 mod serialization;
 
+mod ccm;
+mod der;
+mod pem;
+
 pub mod algorithm;
 pub mod asymmetric;
 pub mod attestation;
 pub mod audit;
 pub mod authentication;
+pub mod backup;
 pub mod capability;
 pub mod client;
 pub mod command;
@@ -114,6 +119,7 @@ pub mod rsa;
 pub mod session;
 #[cfg(feature = "setup")]
 pub mod setup;
+pub mod ssh;
 pub mod template;
 mod uuid;
 pub mod wrap;
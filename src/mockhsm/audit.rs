@@ -1,9 +1,56 @@
 //! (Partial) support for audit logging within the MockHsm
 //!
-//! No logging is performed and these settings are not yet enforced
+//! Session commands are logged according to the per-command audit options,
+//! and the force audit option blocks audited commands while the log is full.
 
-use crate::{audit::*, command, serialization::serialize};
-use std::collections::BTreeMap;
+use crate::{
+    audit::*,
+    command::{self, Code},
+    object, response,
+    serialization::serialize,
+};
+use byteorder::{BigEndian, ByteOrder};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::Instant,
+};
+
+/// Number of entries the audit log can hold before it's full
+pub const LOG_CAPACITY: usize = 62;
+
+/// Placeholder for unused key IDs in log entries
+const NO_KEY: object::Id = 0xffff;
+
+/// Commands whose data begins with the ID of the (target) object they act on
+const TARGETED_COMMANDS: &[Code] = &[
+    Code::DecryptOaep,
+    Code::DecryptPkcs1,
+    Code::DeleteObject,
+    Code::DeriveEcdh,
+    Code::ExportWrapped,
+    Code::GenerateAsymmetricKey,
+    Code::GenerateHmacKey,
+    Code::GenerateWrapKey,
+    Code::GetObjectInfo,
+    Code::GetOpaqueObject,
+    Code::GetPublicKey,
+    Code::GetTemplate,
+    Code::ImportWrapped,
+    Code::PutAsymmetricKey,
+    Code::PutAuthenticationKey,
+    Code::PutHmacKey,
+    Code::PutOpaqueObject,
+    Code::PutTemplate,
+    Code::PutWrapKey,
+    Code::SignAttestationCertificate,
+    Code::SignEcdsa,
+    Code::SignEddsa,
+    Code::SignHmac,
+    Code::SignPkcs1,
+    Code::SignPss,
+    Code::SignSshCertificate,
+    Code::VerifyHmac,
+];
 
 /// Default per-command auditing options
 pub const DEFAULT_COMMAND_AUDIT_OPTIONS: &[AuditCommand] = &[
@@ -77,6 +124,14 @@ impl CommandAuditOptions {
         serialize(&audit_command).unwrap()
     }
 
+    /// Get the setting for a particular command
+    pub fn get(&self, command_type: command::Code) -> AuditOption {
+        self.0
+            .get(&command_type)
+            .cloned()
+            .unwrap_or(AuditOption::Off)
+    }
+
     /// Change a setting for a particular command
     pub fn put(&mut self, command_type: command::Code, audit_option: AuditOption) {
         self.0.insert(command_type, audit_option);
@@ -94,3 +149,92 @@ impl Default for CommandAuditOptions {
         CommandAuditOptions(result)
     }
 }
+
+/// Audit log of commands performed by the MockHsm
+#[derive(Debug)]
+pub struct AuditLog {
+    /// Entries which haven't been consumed via `SetLogIndex`
+    entries: VecDeque<LogEntry>,
+
+    /// Item number of the most recent entry
+    last_item: u16,
+
+    /// Digest of the most recent entry
+    last_digest: LogDigest,
+
+    /// Time the log was created (used to compute ticks)
+    created_at: Instant,
+}
+
+impl AuditLog {
+    /// Is the log full?
+    pub fn is_full(&self) -> bool {
+        self.entries.len() >= LOG_CAPACITY
+    }
+
+    /// Log a command and its result, discarding the oldest entry if full
+    pub fn push(
+        &mut self,
+        command: &command::Message,
+        session_key: object::Id,
+        result: response::Code,
+    ) {
+        let target_key =
+            if TARGETED_COMMANDS.contains(&command.command_type) && command.data.len() >= 2 {
+                BigEndian::read_u16(&command.data)
+            } else {
+                NO_KEY
+            };
+
+        let elapsed = self.created_at.elapsed();
+        let tick = elapsed.as_secs() * 1000 + u64::from(elapsed.subsec_millis());
+
+        let mut entry = LogEntry {
+            item: self.last_item.wrapping_add(1),
+            cmd: command.command_type,
+            length: command.data.len() as u16,
+            session_key,
+            target_key,
+            second_key: NO_KEY,
+            result,
+            tick: tick as u32,
+            digest: self.last_digest,
+        };
+
+        entry.digest = entry.compute_digest(&self.last_digest);
+        self.last_item = entry.item;
+        self.last_digest = entry.digest;
+
+        if self.is_full() {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back(entry);
+    }
+
+    /// Get the unconsumed entries in the log
+    pub fn entries(&self) -> LogEntries {
+        LogEntries {
+            unlogged_boot_events: 0,
+            unlogged_auth_events: 0,
+            num_entries: self.entries.len() as u8,
+            entries: self.entries.iter().cloned().collect(),
+        }
+    }
+
+    /// Mark entries up to and including the given index as consumed
+    pub fn set_index(&mut self, index: u16) {
+        self.entries.retain(|entry| entry.item > index);
+    }
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        AuditLog {
+            entries: VecDeque::with_capacity(LOG_CAPACITY),
+            last_item: 0,
+            last_digest: LogDigest([0u8; LOG_DIGEST_SIZE]),
+            created_at: Instant::now(),
+        }
+    }
+}
@@ -1,6 +1,13 @@
 //! Commands supported by the `MockHsm`
 
-use super::{object::Payload, state::State, MOCK_SERIAL_NUMBER};
+use super::{
+    ecc,
+    object::Payload,
+    otp::{aead_key_payload, decrypt_otp as decrypt_otp_block, AeadKey},
+    ssh::Request as SshRequest,
+    state::State,
+    MOCK_SERIAL_NUMBER,
+};
 use crate::{
     algorithm::*,
     asymmetric::{self, commands::*, PublicKey},
@@ -12,22 +19,23 @@ use crate::{
     ecdsa,
     ed25519::commands::*,
     hmac::{self, commands::*},
-    kex,
+    kex::{self, commands::*},
     object::{self, commands::*},
     opaque::{self, commands::*},
-    otp,
+    otp::{self, commands::*},
     response::{self, Response},
-    rsa,
+    rsa::{self, oaep::commands::*, pkcs1::commands::*, pss::commands::*},
     serialization::deserialize,
     session::{self, commands::*},
-    template,
+    ssh::commands::*,
+    template::{self, commands::*, SshTemplate},
     wrap::{self, commands::*},
     Capability,
 };
 use hmac_crate::{Hmac, Mac};
 use rand_os::{rand_core::RngCore, OsRng};
 use ring::signature::Ed25519KeyPair;
-use sha2::Sha256;
+use sha2::{Digest, Sha256, Sha384, Sha512};
 use std::{io::Cursor, str::FromStr};
 use subtle::ConstantTimeEq;
 use untrusted;
@@ -85,17 +93,40 @@ pub(crate) fn session_message(
         .get_session(session_id)?
         .decrypt_command(encrypted_command);
 
+    // Consuming the log is always permitted, even when it's full
+    let audited = command.command_type != Code::GetLogEntries
+        && command.command_type != Code::SetLogIndex
+        && state.command_audit_options.get(command.command_type) != AuditOption::Off;
+
+    if audited && state.force_audit != AuditOption::Off && state.audit_log.is_full() {
+        debug!("audit log is full: refusing {:?}", command.command_type);
+
+        return Ok(state
+            .get_session(session_id)?
+            .encrypt_response(DeviceErrorKind::LogFull.into())
+            .into());
+    }
+
     let response = match command.command_type {
         Code::BlinkDevice => BlinkDeviceResponse {}.serialize(),
+        Code::ChangeAuthenticationKey => {
+            change_authentication_key(state, session_id, &command.data)
+        }
         Code::CloseSession => return close_session(state, session_id),
+        Code::CreateOtpAead => create_otp_aead(state, &command.data),
+        Code::DecryptOaep => decrypt_oaep(state, &command.data),
+        Code::DecryptPkcs1 => decrypt_pkcs1(state, &command.data),
+        Code::DecryptOtp => decrypt_otp(state, &command.data),
         Code::DeleteObject => delete_object(state, &command.data),
+        Code::DeriveEcdh => derive_ecdh(state, &command.data),
         Code::DeviceInfo => device_info(),
         Code::Echo => echo(&command.data),
         Code::ExportWrapped => export_wrapped(state, &command.data),
         Code::GenerateAsymmetricKey => gen_asymmetric_key(state, &command.data),
         Code::GenerateHmacKey => gen_hmac_key(state, &command.data),
+        Code::GenerateOtpAead => gen_otp_aead_key(state, &command.data),
         Code::GenerateWrapKey => gen_wrap_key(state, &command.data),
-        Code::GetLogEntries => get_log_entries(),
+        Code::GetLogEntries => get_log_entries(state),
         Code::GetObjectInfo => get_object_info(state, &command.data),
         Code::GetOpaqueObject => get_opaque(state, &command.data),
         Code::GetOption => get_option(state, &command.data),
@@ -108,22 +139,72 @@ pub(crate) fn session_message(
         Code::PutAuthenticationKey => put_authentication_key(state, &command.data),
         Code::PutHmacKey => put_hmac_key(state, &command.data),
         Code::PutOpaqueObject => put_opaque(state, &command.data),
+        Code::PutOtpAead => put_otp_aead_key(state, &command.data),
         Code::SetOption => put_option(state, &command.data),
+        Code::PutTemplate => put_template(state, &command.data),
         Code::PutWrapKey => put_wrap_key(state, &command.data),
+        Code::RandomizeOtpAead => randomize_otp_aead(state, &command.data),
         Code::ResetDevice => return Ok(reset_device(state, session_id)),
-        Code::SetLogIndex => SetLogIndexResponse {}.serialize(),
+        Code::RewrapOtpAead => rewrap_otp_aead(state, &command.data),
+        Code::SetLogIndex => set_log_index(state, &command.data),
         Code::SignEddsa => sign_eddsa(state, &command.data),
+        Code::SignPkcs1 => sign_pkcs1(state, &command.data),
+        Code::SignPss => sign_pss(state, &command.data),
+        Code::SignSshCertificate => sign_ssh_certificate(state, &command.data),
         Code::GetStorageInfo => get_storage_info(),
+        Code::GetTemplate => get_template(state, &command.data),
         Code::VerifyHmac => verify_hmac(state, &command.data),
         unsupported => panic!("unsupported command type: {:?}", unsupported),
     };
 
+    if audited {
+        let session_key = state.get_session(session_id)?.authentication_key_id;
+        state.audit_log.push(&command, session_key, response.code);
+    }
+
     Ok(state
         .get_session(session_id)?
         .encrypt_response(response)
         .into())
 }
 
+/// Change the authentication key used to establish the current session
+fn change_authentication_key(
+    state: &mut State,
+    session_id: session::Id,
+    cmd_data: &[u8],
+) -> response::Message {
+    let ChangeAuthenticationKeyCommand {
+        key_id,
+        authentication_key,
+        ..
+    } = deserialize(cmd_data)
+        .unwrap_or_else(|e| panic!("error parsing Code::ChangeAuthenticationKey: {:?}", e));
+
+    let session_key_id = state.get_session(session_id).unwrap().authentication_key_id;
+
+    if key_id != session_key_id {
+        debug!(
+            "can't change authentication key {:?} (session key: {:?})",
+            key_id, session_key_id
+        );
+        return DeviceErrorKind::InvalidId.into();
+    }
+
+    match state
+        .objects
+        .get_mut(key_id, object::Type::AuthenticationKey)
+    {
+        Some(obj) => obj.payload = Payload::AuthenticationKey(authentication_key),
+        None => {
+            debug!("no such authentication key: {:?}", key_id);
+            return DeviceErrorKind::ObjectNotFound.into();
+        }
+    }
+
+    ChangeAuthenticationKeyResponse { key_id }.serialize()
+}
+
 /// Close an active session
 fn close_session(state: &mut State, session_id: session::Id) -> Result<Vec<u8>, ConnectionError> {
     let response = state
@@ -134,6 +215,124 @@ fn close_session(state: &mut State, session_id: session::Id) -> Result<Vec<u8>,
     Ok(response.into())
 }
 
+/// Create a Yubico OTP AEAD from the given OTP key and private ID
+fn create_otp_aead(state: &State, cmd_data: &[u8]) -> response::Message {
+    let command: CreateOtpAeadCommand = deserialize(cmd_data)
+        .unwrap_or_else(|e| panic!("error parsing Code::CreateOtpAead: {:?}", e));
+
+    if command.data.len() != otp::KEY_SIZE + otp::PRIVATE_ID_SIZE {
+        debug!("bad CreateOtpAead data length: {}", command.data.len());
+        return DeviceErrorKind::WrongLength.into();
+    }
+
+    match get_otp_aead_key(state, command.key_id) {
+        Ok(key) => CreateOtpAeadResponse(key.seal(&command.data)).serialize(),
+        Err(response) => response,
+    }
+}
+
+/// Decrypt data using RSAES-OAEP
+fn decrypt_oaep(state: &State, cmd_data: &[u8]) -> response::Message {
+    let command: DecryptOaepCommand = deserialize(cmd_data)
+        .unwrap_or_else(|e| panic!("error parsing Code::DecryptOaep: {:?}", e));
+
+    if let Some(obj) = state
+        .objects
+        .get(command.key_id, object::Type::AsymmetricKey)
+    {
+        if let Payload::RsaKeyPair(_, ref keypair) = obj.payload {
+            // The label hash is sent immediately after the ciphertext
+            if command.data.len() <= keypair.modulus_len() {
+                debug!("missing OAEP label hash");
+                return DeviceErrorKind::WrongLength.into();
+            }
+
+            let (ciphertext, label_hash) = command.data.split_at(keypair.modulus_len());
+
+            match keypair.decrypt_oaep(ciphertext, label_hash, command.mgf1_hash_alg) {
+                Some(plaintext) => DecryptOaepResponse(plaintext).serialize(),
+                None => {
+                    debug!("RSAES-OAEP decryption failed");
+                    DeviceErrorKind::InvalidData.into()
+                }
+            }
+        } else {
+            debug!("not an RSA key: {:?}", obj.algorithm());
+            DeviceErrorKind::InvalidCommand.into()
+        }
+    } else {
+        debug!("no such object ID: {:?}", command.key_id);
+        DeviceErrorKind::ObjectNotFound.into()
+    }
+}
+
+/// Decrypt data using RSAES-PKCS#1v1.5
+fn decrypt_pkcs1(state: &State, cmd_data: &[u8]) -> response::Message {
+    let command: DecryptPkcs1Command = deserialize(cmd_data)
+        .unwrap_or_else(|e| panic!("error parsing Code::DecryptPkcs1: {:?}", e));
+
+    if let Some(obj) = state
+        .objects
+        .get(command.key_id, object::Type::AsymmetricKey)
+    {
+        if let Payload::RsaKeyPair(_, ref keypair) = obj.payload {
+            match keypair.decrypt_pkcs1v15(&command.data) {
+                Some(plaintext) => DecryptPkcs1Response(plaintext).serialize(),
+                None => {
+                    debug!("RSAES-PKCS#1v1.5 decryption failed");
+                    DeviceErrorKind::InvalidData.into()
+                }
+            }
+        } else {
+            debug!("not an RSA key: {:?}", obj.algorithm());
+            DeviceErrorKind::InvalidCommand.into()
+        }
+    } else {
+        debug!("no such object ID: {:?}", command.key_id);
+        DeviceErrorKind::ObjectNotFound.into()
+    }
+}
+
+/// Decrypt a Yubico OTP using the OTP key and private ID in the given AEAD
+fn decrypt_otp(state: &State, cmd_data: &[u8]) -> response::Message {
+    let command: DecryptOtpCommand =
+        deserialize(cmd_data).unwrap_or_else(|e| panic!("error parsing Code::DecryptOtp: {:?}", e));
+
+    let key = match get_otp_aead_key(state, command.key_id) {
+        Ok(key) => key,
+        Err(response) => return response,
+    };
+
+    if command.data.len() != otp::AEAD_SIZE + otp::OTP_SIZE {
+        debug!("bad DecryptOtp data length: {}", command.data.len());
+        return DeviceErrorKind::WrongLength.into();
+    }
+
+    let (aead, otp_bytes) = command.data.split_at(otp::AEAD_SIZE);
+
+    let key_and_id = match key.open(&otp::Aead::from(aead)) {
+        Some(key_and_id) => key_and_id,
+        None => {
+            debug!("error decrypting OTP AEAD");
+            return DeviceErrorKind::InvalidData.into();
+        }
+    };
+
+    match decrypt_otp_block(&key_and_id, otp_bytes) {
+        Some(decrypted) => DecryptOtpResponse {
+            use_counter: decrypted.use_counter,
+            session_counter: decrypted.session_counter,
+            timestamp_high: (decrypted.timestamp >> 16) as u8,
+            timestamp_low: decrypted.timestamp as u16,
+        }
+        .serialize(),
+        None => {
+            debug!("invalid OTP");
+            DeviceErrorKind::InvalidOtp.into()
+        }
+    }
+}
+
 /// Delete an object
 fn delete_object(state: &mut State, cmd_data: &[u8]) -> response::Message {
     let command: DeleteObjectCommand = deserialize(cmd_data)
@@ -151,6 +350,37 @@ fn delete_object(state: &mut State, cmd_data: &[u8]) -> response::Message {
     }
 }
 
+/// Compute an ECDH shared secret
+fn derive_ecdh(state: &State, cmd_data: &[u8]) -> response::Message {
+    let command: DeriveEcdhCommand =
+        deserialize(cmd_data).unwrap_or_else(|e| panic!("error parsing Code::DeriveEcdh: {:?}", e));
+
+    if let Some(obj) = state
+        .objects
+        .get(command.key_id, object::Type::AsymmetricKey)
+    {
+        if let Payload::EccKeyPair(alg, ref scalar) = obj.payload {
+            let curve = ecc::Curve::new(alg).unwrap();
+
+            match curve.ecdh(scalar, &command.public_key) {
+                Some(shared_secret) => {
+                    DeriveEcdhResponse(kex::SharedSecret::new(shared_secret)).serialize()
+                }
+                None => {
+                    debug!("invalid ECDH public key for {:?}", alg);
+                    DeviceErrorKind::InvalidData.into()
+                }
+            }
+        } else {
+            debug!("not an ECC key: {:?}", obj.algorithm());
+            DeviceErrorKind::InvalidCommand.into()
+        }
+    } else {
+        debug!("no such object ID: {:?}", command.key_id);
+        DeviceErrorKind::ObjectNotFound.into()
+    }
+}
+
 /// Generate a mock device information report
 fn device_info() -> response::Message {
     let info = device::Info {
@@ -284,6 +514,46 @@ fn gen_hmac_key(state: &mut State, cmd_data: &[u8]) -> response::Message {
     .serialize()
 }
 
+/// Generate a new random OTP AEAD key
+fn gen_otp_aead_key(state: &mut State, cmd_data: &[u8]) -> response::Message {
+    let GenOtpAeadKeyCommand { params, nonce_id } = deserialize(cmd_data)
+        .unwrap_or_else(|e| panic!("error parsing Code::GenerateOtpAead: {:?}", e));
+
+    let algorithm = params
+        .algorithm
+        .otp()
+        .unwrap_or_else(|| panic!("not an OTP algorithm: {:?}", params.algorithm));
+
+    let mut key = vec![0u8; algorithm.key_len()];
+    OsRng::new().unwrap().fill_bytes(&mut key);
+
+    state
+        .objects
+        .put(
+            params.key_id,
+            object::Type::OtpAeadKey,
+            params.algorithm,
+            params.label,
+            params.capabilities,
+            Capability::default(),
+            params.domains,
+            &aead_key_payload(nonce_id, &key),
+        )
+        .unwrap();
+
+    state
+        .objects
+        .get_mut(params.key_id, object::Type::OtpAeadKey)
+        .unwrap()
+        .object_info
+        .origin = object::Origin::Generated;
+
+    GenOtpAeadKeyResponse {
+        key_id: params.key_id,
+    }
+    .serialize()
+}
+
 /// Generate a new random wrap (i.e. AES-CCM) key
 fn gen_wrap_key(state: &mut State, cmd_data: &[u8]) -> response::Message {
     let GenWrapKeyCommand {
@@ -308,15 +578,8 @@ fn gen_wrap_key(state: &mut State, cmd_data: &[u8]) -> response::Message {
 }
 
 /// Get mock log information
-fn get_log_entries() -> response::Message {
-    // TODO: mimic the YubiHSM's actual audit log
-    LogEntries {
-        unlogged_boot_events: 0,
-        unlogged_auth_events: 0,
-        num_entries: 0,
-        entries: vec![],
-    }
-    .serialize()
+fn get_log_entries(state: &State) -> response::Message {
+    state.audit_log.entries().serialize()
 }
 
 /// Get detailed info about a specific object
@@ -407,6 +670,30 @@ fn get_storage_info() -> response::Message {
     GetStorageInfoResponse(info).serialize()
 }
 
+/// Get an SSH template stored in the HSM
+fn get_template(state: &State, cmd_data: &[u8]) -> response::Message {
+    let command: GetTemplateCommand = deserialize(cmd_data)
+        .unwrap_or_else(|e| panic!("error parsing Code::GetTemplate: {:?}", e));
+
+    if let Some(obj) = state.objects.get(command.object_id, object::Type::Template) {
+        if let Payload::Template(algorithm, ref data) = obj.payload {
+            GetTemplateResponse {
+                algorithm,
+                data: data.clone(),
+            }
+            .serialize()
+        } else {
+            panic!(
+                "template object with non-template payload: {:?}",
+                obj.algorithm()
+            );
+        }
+    } else {
+        debug!("no such template ID: {:?}", command.object_id);
+        DeviceErrorKind::ObjectNotFound.into()
+    }
+}
+
 /// Import an object encrypted under a wrap key into the HSM
 fn import_wrapped(state: &mut State, cmd_data: &[u8]) -> response::Message {
     let ImportWrappedCommand {
@@ -472,7 +759,7 @@ fn put_asymmetric_key(state: &mut State, cmd_data: &[u8]) -> response::Message {
     let PutAsymmetricKeyCommand { params, data } = deserialize(cmd_data)
         .unwrap_or_else(|e| panic!("error parsing Code::PutAsymmetricKey: {:?}", e));
 
-    state.objects.put(
+    if let Err(e) = state.objects.put(
         params.id,
         object::Type::AsymmetricKey,
         params.algorithm,
@@ -481,7 +768,10 @@ fn put_asymmetric_key(state: &mut State, cmd_data: &[u8]) -> response::Message {
         Capability::default(),
         params.domains,
         &data,
-    );
+    ) {
+        debug!("error putting asymmetric key: {}", e);
+        return DeviceErrorKind::InvalidData.into();
+    }
 
     PutAsymmetricKeyResponse { key_id: params.id }.serialize()
 }
@@ -495,7 +785,7 @@ fn put_authentication_key(state: &mut State, cmd_data: &[u8]) -> response::Messa
     } = deserialize(cmd_data)
         .unwrap_or_else(|e| panic!("error parsing Code::PutAuthenticationKey: {:?}", e));
 
-    state.objects.put(
+    if let Err(e) = state.objects.put(
         params.id,
         object::Type::AuthenticationKey,
         params.algorithm,
@@ -504,7 +794,10 @@ fn put_authentication_key(state: &mut State, cmd_data: &[u8]) -> response::Messa
         delegated_capabilities,
         params.domains,
         &authentication_key.0,
-    );
+    ) {
+        debug!("error putting authentication key: {}", e);
+        return DeviceErrorKind::InvalidData.into();
+    }
 
     PutAuthenticationKeyResponse { key_id: params.id }.serialize()
 }
@@ -514,7 +807,7 @@ fn put_hmac_key(state: &mut State, cmd_data: &[u8]) -> response::Message {
     let PutHmacKeyCommand { params, hmac_key } =
         deserialize(cmd_data).unwrap_or_else(|e| panic!("error parsing Code::PutHMACKey: {:?}", e));
 
-    state.objects.put(
+    if let Err(e) = state.objects.put(
         params.id,
         object::Type::HmacKey,
         params.algorithm,
@@ -523,7 +816,10 @@ fn put_hmac_key(state: &mut State, cmd_data: &[u8]) -> response::Message {
         Capability::default(),
         params.domains,
         &hmac_key,
-    );
+    ) {
+        debug!("error putting HMAC key: {}", e);
+        return DeviceErrorKind::InvalidData.into();
+    }
 
     PutHmacKeyResponse { key_id: params.id }.serialize()
 }
@@ -533,7 +829,7 @@ fn put_opaque(state: &mut State, cmd_data: &[u8]) -> response::Message {
     let PutOpaqueCommand { params, data } = deserialize(cmd_data)
         .unwrap_or_else(|e| panic!("error parsing Code::PutOpaqueObject: {:?}", e));
 
-    state.objects.put(
+    if let Err(e) = state.objects.put(
         params.id,
         object::Type::Opaque,
         params.algorithm,
@@ -542,7 +838,10 @@ fn put_opaque(state: &mut State, cmd_data: &[u8]) -> response::Message {
         Capability::default(),
         params.domains,
         &data,
-    );
+    ) {
+        debug!("error putting opaque object: {}", e);
+        return DeviceErrorKind::InvalidData.into();
+    }
 
     PutOpaqueResponse {
         object_id: params.id,
@@ -550,6 +849,31 @@ fn put_opaque(state: &mut State, cmd_data: &[u8]) -> response::Message {
     .serialize()
 }
 
+/// Put an existing OTP AEAD key into the HSM
+fn put_otp_aead_key(state: &mut State, cmd_data: &[u8]) -> response::Message {
+    let PutOTPAEADKeyCommand {
+        params,
+        nonce_id,
+        data,
+    } = deserialize(cmd_data).unwrap_or_else(|e| panic!("error parsing Code::PutOtpAead: {:?}", e));
+
+    if let Err(e) = state.objects.put(
+        params.id,
+        object::Type::OtpAeadKey,
+        params.algorithm,
+        params.label,
+        params.capabilities,
+        Capability::default(),
+        params.domains,
+        &aead_key_payload(nonce_id, &data),
+    ) {
+        debug!("error putting OTP AEAD key: {}", e);
+        return DeviceErrorKind::InvalidData.into();
+    }
+
+    PutOTPAEADKeyResponse { key_id: params.id }.serialize()
+}
+
 /// Change an HSM auditing setting
 fn put_option(state: &mut State, cmd_data: &[u8]) -> response::Message {
     let SetOptionCommand { tag, length, value } =
@@ -574,6 +898,44 @@ fn put_option(state: &mut State, cmd_data: &[u8]) -> response::Message {
     PutOptionResponse {}.serialize()
 }
 
+/// Put an SSH template into the HSM
+fn put_template(state: &mut State, cmd_data: &[u8]) -> response::Message {
+    let PutTemplateCommand { params, data } = deserialize(cmd_data)
+        .unwrap_or_else(|e| panic!("error parsing Code::PutTemplate: {:?}", e));
+
+    match params.algorithm {
+        Algorithm::Template(template::Algorithm::SSH) => {
+            if let Err(e) = SshTemplate::from_bytes(&data) {
+                debug!("invalid SSH template: {}", e);
+                return DeviceErrorKind::InvalidData.into();
+            }
+        }
+        other => {
+            debug!("not a template algorithm: {:?}", other);
+            return DeviceErrorKind::InvalidData.into();
+        }
+    }
+
+    if let Err(e) = state.objects.put(
+        params.id,
+        object::Type::Template,
+        params.algorithm,
+        params.label,
+        params.capabilities,
+        Capability::default(),
+        params.domains,
+        &data,
+    ) {
+        debug!("error putting template: {}", e);
+        return DeviceErrorKind::InvalidData.into();
+    }
+
+    PutTemplateResponse {
+        object_id: params.id,
+    }
+    .serialize()
+}
+
 /// Put an existing wrap (i.e. AES-CCM) key into the HSM
 fn put_wrap_key(state: &mut State, cmd_data: &[u8]) -> response::Message {
     let PutWrapKeyCommand {
@@ -582,7 +944,7 @@ fn put_wrap_key(state: &mut State, cmd_data: &[u8]) -> response::Message {
         data,
     } = deserialize(cmd_data).unwrap_or_else(|e| panic!("error parsing Code::PutWrapKey: {:?}", e));
 
-    state.objects.put(
+    if let Err(e) = state.objects.put(
         params.id,
         object::Type::WrapKey,
         params.algorithm,
@@ -591,11 +953,29 @@ fn put_wrap_key(state: &mut State, cmd_data: &[u8]) -> response::Message {
         delegated_capabilities,
         params.domains,
         &data,
-    );
+    ) {
+        debug!("error putting wrap key: {}", e);
+        return DeviceErrorKind::InvalidData.into();
+    }
 
     PutWrapKeyResponse { key_id: params.id }.serialize()
 }
 
+/// Create a Yubico OTP AEAD from a random OTP key and private ID
+fn randomize_otp_aead(state: &State, cmd_data: &[u8]) -> response::Message {
+    let command: RandomizeOtpAeadCommand = deserialize(cmd_data)
+        .unwrap_or_else(|e| panic!("error parsing Code::RandomizeOtpAead: {:?}", e));
+
+    match get_otp_aead_key(state, command.key_id) {
+        Ok(key) => {
+            let mut plaintext = [0u8; otp::KEY_SIZE + otp::PRIVATE_ID_SIZE];
+            OsRng::new().unwrap().fill_bytes(&mut plaintext);
+            RandomizeOtpAeadResponse(key.seal(&plaintext)).serialize()
+        }
+        Err(response) => response,
+    }
+}
+
 /// Reset the MockHsm back to its default state
 fn reset_device(state: &mut State, session_id: session::Id) -> Vec<u8> {
     let response = state
@@ -608,6 +988,37 @@ fn reset_device(state: &mut State, session_id: session::Id) -> Vec<u8> {
     response
 }
 
+/// Decrypt a Yubico OTP AEAD and re-encrypt it under another OTP AEAD key
+fn rewrap_otp_aead(state: &State, cmd_data: &[u8]) -> response::Message {
+    let command: RewrapOtpAeadCommand = deserialize(cmd_data)
+        .unwrap_or_else(|e| panic!("error parsing Code::RewrapOtpAead: {:?}", e));
+
+    let (from_key, to_key) = match (
+        get_otp_aead_key(state, command.from_key_id),
+        get_otp_aead_key(state, command.to_key_id),
+    ) {
+        (Ok(from_key), Ok(to_key)) => (from_key, to_key),
+        (Err(response), _) | (_, Err(response)) => return response,
+    };
+
+    match from_key.open(&command.aead) {
+        Some(plaintext) => RewrapOtpAeadResponse(to_key.seal(&plaintext)).serialize(),
+        None => {
+            debug!("error decrypting OTP AEAD");
+            DeviceErrorKind::InvalidData.into()
+        }
+    }
+}
+
+/// Mark audit log entries as consumed
+fn set_log_index(state: &mut State, cmd_data: &[u8]) -> response::Message {
+    let command: SetLogIndexCommand = deserialize(cmd_data)
+        .unwrap_or_else(|e| panic!("error parsing Code::SetLogIndex: {:?}", e));
+
+    state.audit_log.set_index(command.log_index);
+    SetLogIndexResponse {}.serialize()
+}
+
 /// Sign a message using the Ed25519 signature algorithm
 fn sign_eddsa(state: &State, cmd_data: &[u8]) -> response::Message {
     let command: SignEddsaCommand =
@@ -655,6 +1066,180 @@ fn sign_hmac(state: &State, cmd_data: &[u8]) -> response::Message {
     }
 }
 
+/// Sign a digest using RSASSA-PKCS#1v1.5
+fn sign_pkcs1(state: &State, cmd_data: &[u8]) -> response::Message {
+    let command: SignPkcs1Command =
+        deserialize(cmd_data).unwrap_or_else(|e| panic!("error parsing Code::SignPkcs1: {:?}", e));
+
+    if let Some(obj) = state
+        .objects
+        .get(command.key_id, object::Type::AsymmetricKey)
+    {
+        if let Payload::RsaKeyPair(_, ref keypair) = obj.payload {
+            match keypair.sign_pkcs1v15(&command.digest) {
+                Some(signature) => SignPkcs1Response(rsa::pkcs1::Signature(signature)).serialize(),
+                None => {
+                    debug!("invalid digest length: {}", command.digest.len());
+                    DeviceErrorKind::InvalidData.into()
+                }
+            }
+        } else {
+            debug!("not an RSA key: {:?}", obj.algorithm());
+            DeviceErrorKind::InvalidCommand.into()
+        }
+    } else {
+        debug!("no such object ID: {:?}", command.key_id);
+        DeviceErrorKind::ObjectNotFound.into()
+    }
+}
+
+/// Sign a digest using RSASSA-PSS
+fn sign_pss(state: &State, cmd_data: &[u8]) -> response::Message {
+    let command: SignPssCommand =
+        deserialize(cmd_data).unwrap_or_else(|e| panic!("error parsing Code::SignPss: {:?}", e));
+
+    if let Some(obj) = state
+        .objects
+        .get(command.key_id, object::Type::AsymmetricKey)
+    {
+        if let Payload::RsaKeyPair(_, ref keypair) = obj.payload {
+            match keypair.sign_pss(
+                &command.digest,
+                command.mgf1_hash_alg,
+                command.salt_len as usize,
+            ) {
+                Some(signature) => SignPssResponse(rsa::pss::Signature(signature)).serialize(),
+                None => {
+                    debug!(
+                        "invalid digest or salt length: {}/{}",
+                        command.digest.len(),
+                        command.salt_len
+                    );
+                    DeviceErrorKind::InvalidData.into()
+                }
+            }
+        } else {
+            debug!("not an RSA key: {:?}", obj.algorithm());
+            DeviceErrorKind::InvalidCommand.into()
+        }
+    } else {
+        debug!("no such object ID: {:?}", command.key_id);
+        DeviceErrorKind::ObjectNotFound.into()
+    }
+}
+
+/// Sign an SSH certificate (Ed25519 CA keys only)
+fn sign_ssh_certificate(state: &State, cmd_data: &[u8]) -> response::Message {
+    let command: SignSshCertificateCommand = deserialize(cmd_data)
+        .unwrap_or_else(|e| panic!("error parsing Code::SignSshCertificate: {:?}", e));
+
+    let template = match state
+        .objects
+        .get(command.template_id, object::Type::Template)
+    {
+        Some(obj) => match SshTemplate::from_bytes(obj.payload.as_ref()) {
+            Ok(template) => template,
+            Err(e) => {
+                debug!("error parsing SSH template: {}", e);
+                return DeviceErrorKind::InvalidData.into();
+            }
+        },
+        None => {
+            debug!("no such template ID: {:?}", command.template_id);
+            return DeviceErrorKind::ObjectNotFound.into();
+        }
+    };
+
+    if !template.ca_key_ids().contains(&command.key_id) {
+        debug!("CA key not allowed by template: {:?}", command.key_id);
+        return DeviceErrorKind::SshCaConstraintViolation.into();
+    }
+
+    let request = match SshRequest::parse(&command.request) {
+        Ok(request) => request,
+        Err(e) => {
+            debug!("error parsing SSH certificate request: {}", e);
+            return DeviceErrorKind::InvalidData.into();
+        }
+    };
+
+    if !request.verify_timestamp(template.timestamp_key()) {
+        debug!("invalid SSH certificate request timestamp signature");
+        return DeviceErrorKind::SshCaConstraintViolation.into();
+    }
+
+    if !request.is_validity_allowed(&template) {
+        debug!("SSH certificate validity period not allowed by template");
+        return DeviceErrorKind::SshCaConstraintViolation.into();
+    }
+
+    if !request.are_principals_allowed(&template) {
+        debug!("SSH certificate principals not allowed by template");
+        return DeviceErrorKind::SshCaConstraintViolation.into();
+    }
+
+    let obj = match state
+        .objects
+        .get(command.key_id, object::Type::AsymmetricKey)
+    {
+        Some(obj) => obj,
+        None => {
+            debug!("no such object ID: {:?}", command.key_id);
+            return DeviceErrorKind::ObjectNotFound.into();
+        }
+    };
+
+    let body = request.body();
+
+    let signature = match (command.algorithm, &obj.payload) {
+        (Algorithm::Asymmetric(asymmetric::Algorithm::Ed25519), Payload::Ed25519KeyPair(seed)) => {
+            let keypair =
+                Ed25519KeyPair::from_seed_unchecked(untrusted::Input::from(seed)).unwrap();
+
+            Some(keypair.sign(body).as_ref().into())
+        }
+        (Algorithm::Ecdsa(alg), Payload::EccKeyPair(curve_alg, scalar)) => {
+            let digest: Vec<u8> = match alg {
+                ecdsa::Algorithm::SHA256 => Sha256::digest(body).as_slice().into(),
+                ecdsa::Algorithm::SHA384 => Sha384::digest(body).as_slice().into(),
+                ecdsa::Algorithm::SHA512 => Sha512::digest(body).as_slice().into(),
+                _ => {
+                    debug!("unsupported SSH signature algorithm: {:?}", alg);
+                    return DeviceErrorKind::InvalidCommand.into();
+                }
+            };
+
+            ecc::Curve::new(*curve_alg).and_then(|curve| curve.sign_ecdsa(scalar, &digest))
+        }
+        (Algorithm::Rsa(alg), Payload::RsaKeyPair(_, keypair))
+            if alg == rsa::Algorithm::PKCS1_SHA1
+                || alg == rsa::Algorithm::PKCS1_SHA256
+                || alg == rsa::Algorithm::PKCS1_SHA512 =>
+        {
+            keypair.sign_pkcs1v15(&alg.digest(body))
+        }
+        (algorithm, payload) => {
+            debug!(
+                "unsupported SSH signature algorithm for {:?} key: {:?}",
+                payload.algorithm(),
+                algorithm
+            );
+            return DeviceErrorKind::InvalidCommand.into();
+        }
+    };
+
+    match signature {
+        Some(signature) => SignSshCertificateResponse(signature).serialize(),
+        None => {
+            debug!(
+                "error signing SSH certificate with key: {:?}",
+                command.key_id
+            );
+            DeviceErrorKind::InvalidData.into()
+        }
+    }
+}
+
 /// Verify the HMAC tag for the given data
 fn verify_hmac(state: &State, cmd_data: &[u8]) -> response::Message {
     let command: VerifyHmacCommand =
@@ -682,3 +1267,14 @@ fn verify_hmac(state: &State, cmd_data: &[u8]) -> response::Message {
         DeviceErrorKind::ObjectNotFound.into()
     }
 }
+
+/// Load the OTP AEAD key with the given ID
+fn get_otp_aead_key(state: &State, key_id: object::Id) -> Result<AeadKey, response::Message> {
+    match state.objects.get(key_id, object::Type::OtpAeadKey) {
+        Some(obj) => Ok(AeadKey::new(obj.payload.as_ref()).expect("invalid OTP AEAD key payload")),
+        None => {
+            debug!("no such OTP AEAD key: {:?}", key_id);
+            Err(DeviceErrorKind::ObjectNotFound.into())
+        }
+    }
+}
@@ -0,0 +1,319 @@
+//! Software elliptic curve arithmetic used by the `MockHsm`.
+//!
+//! Like `mockhsm::rsa`, this is a textbook implementation which makes no
+//! attempt to run in constant time. It exists solely to exercise ECC
+//! commands in tests.
+
+use crate::{
+    asymmetric::{
+        self,
+        curve::{self, parse_hex, Params},
+    },
+    der,
+};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use rand_os::{rand_core::RngCore, OsRng};
+
+/// Tag byte identifying an uncompressed SEC1 point
+const UNCOMPRESSED_POINT_TAG: u8 = 0x04;
+
+/// Short Weierstrass curve: `y^2 = x^3 + ax + b (mod p)`
+#[derive(Clone, Debug)]
+pub(crate) struct Curve {
+    /// Point validation
+    curve: curve::Curve,
+
+    /// Field modulus
+    p: BigUint,
+
+    /// Curve coefficient `a`
+    a: BigUint,
+
+    /// Base point x-coordinate
+    gx: BigUint,
+
+    /// Base point y-coordinate
+    gy: BigUint,
+
+    /// Order of the base point
+    n: BigUint,
+
+    /// Size of the order of the base point in bits
+    order_bits: usize,
+
+    /// Size of a field element or scalar in bytes
+    size: usize,
+}
+
+impl Curve {
+    /// Get the curve for the given algorithm, if the MockHsm supports it
+    pub fn new(algorithm: asymmetric::Algorithm) -> Option<Self> {
+        let params = Params::for_algorithm(algorithm)?;
+
+        Some(Self {
+            curve: curve::Curve::new(algorithm)?,
+            p: parse_hex(params.p),
+            a: parse_hex(params.a),
+            gx: parse_hex(params.gx),
+            gy: parse_hex(params.gy),
+            n: parse_hex(params.n),
+            order_bits: parse_hex(params.n).bits(),
+            size: algorithm.key_len(),
+        })
+    }
+
+    /// Generate a random secret scalar
+    pub fn random_scalar(&self) -> Vec<u8> {
+        let mut rng = OsRng::new().unwrap();
+        let mut bytes = vec![0u8; self.size];
+        let excess_bits = self.size * 8 - self.order_bits;
+
+        loop {
+            rng.fill_bytes(&mut bytes);
+            bytes[0] &= 0xff >> excess_bits;
+
+            if self.curve.is_valid_scalar(&bytes) {
+                return bytes;
+            }
+        }
+    }
+
+    /// Compute the untagged public key (i.e. `X || Y`) for a secret scalar
+    pub fn public_key(&self, scalar: &[u8]) -> Option<Vec<u8>> {
+        if !self.curve.is_valid_scalar(scalar) {
+            return None;
+        }
+
+        let base = Point::from_affine(&self.gx, &self.gy);
+        let (x, y) = self.to_affine(&self.mul_point(&BigUint::from_bytes_be(scalar), &base))?;
+
+        let mut public_key = self.encode_field_element(&x);
+        public_key.extend_from_slice(&self.encode_field_element(&y));
+        Some(public_key)
+    }
+
+    /// Compute an ECDH shared secret (i.e. the x-coordinate of the product
+    /// of our secret scalar and the peer's point). The peer's public key
+    /// must be an uncompressed SEC1 point.
+    pub fn ecdh(&self, scalar: &[u8], peer_public_key: &[u8]) -> Option<Vec<u8>> {
+        if !self.curve.is_valid_scalar(scalar)
+            || peer_public_key.len() != 1 + self.size * 2
+            || peer_public_key[0] != UNCOMPRESSED_POINT_TAG
+        {
+            return None;
+        }
+
+        let (x_bytes, y_bytes) = peer_public_key[1..].split_at(self.size);
+        let x = BigUint::from_bytes_be(x_bytes);
+        let y = BigUint::from_bytes_be(y_bytes);
+
+        if !self.curve.is_on_curve(&x, &y) {
+            return None;
+        }
+
+        let peer = Point::from_affine(&x, &y);
+        let (shared_x, _) =
+            self.to_affine(&self.mul_point(&BigUint::from_bytes_be(scalar), &peer))?;
+
+        Some(self.encode_field_element(&shared_x))
+    }
+
+    /// Compute a DER-encoded ECDSA signature (i.e. `ECDSA-Sig-Value`) of the
+    /// given digest
+    pub fn sign_ecdsa(&self, scalar: &[u8], digest: &[u8]) -> Option<Vec<u8>> {
+        if !self.curve.is_valid_scalar(scalar) {
+            return None;
+        }
+
+        let d = BigUint::from_bytes_be(scalar);
+
+        // Use the leftmost bits of the digest if it's larger than the order
+        let mut e = BigUint::from_bytes_be(digest);
+        let digest_bits = digest.len() * 8;
+
+        if digest_bits > self.order_bits {
+            e >>= digest_bits - self.order_bits;
+        }
+
+        loop {
+            let k = BigUint::from_bytes_be(&self.random_scalar());
+            let base = Point::from_affine(&self.gx, &self.gy);
+            let (x, _) = self.to_affine(&self.mul_point(&k, &base))?;
+
+            let r = x % &self.n;
+
+            if r.is_zero() {
+                continue;
+            }
+
+            // Invert k using Fermat's little theorem (n is prime)
+            let k_inv = k.modpow(&(&self.n - BigUint::from(2u32)), &self.n);
+            let s = (k_inv * (&e + &r * &d)) % &self.n;
+
+            if s.is_zero() {
+                continue;
+            }
+
+            let mut ecdsa_sig_value = der::encode_integer(&r.to_bytes_be());
+            ecdsa_sig_value.extend_from_slice(&der::encode_integer(&s.to_bytes_be()));
+            return Some(der::encode(der::SEQUENCE, &ecdsa_sig_value));
+        }
+    }
+
+    /// Serialize a field element as a fixed-size big endian byte string
+    fn encode_field_element(&self, element: &BigUint) -> Vec<u8> {
+        let bytes = element.to_bytes_be();
+        let mut result = vec![0u8; self.size - bytes.len()];
+        result.extend_from_slice(&bytes);
+        result
+    }
+
+    /// Scalar multiplication (double-and-add)
+    fn mul_point(&self, scalar: &BigUint, point: &Point) -> Point {
+        let mut result = Point::infinity();
+
+        for byte in scalar.to_bytes_be() {
+            for i in (0..8).rev() {
+                result = self.double(&result);
+
+                if (byte >> i) & 1 == 1 {
+                    result = self.add(&result, point);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Point doubling
+    fn double(&self, point: &Point) -> Point {
+        if point.z.is_zero() || point.y.is_zero() {
+            return Point::infinity();
+        }
+
+        let xx = self.mul_mod(&point.x, &point.x);
+        let yy = self.mul_mod(&point.y, &point.y);
+        let yyyy = self.mul_mod(&yy, &yy);
+        let zz = self.mul_mod(&point.z, &point.z);
+
+        let s = self.mul_mod(&BigUint::from(4u32), &self.mul_mod(&point.x, &yy));
+        let m = self.add_mod(
+            &self.mul_mod(&BigUint::from(3u32), &xx),
+            &self.mul_mod(&self.a, &self.mul_mod(&zz, &zz)),
+        );
+
+        let x = self.sub_mod(&self.mul_mod(&m, &m), &self.add_mod(&s, &s));
+        let y = self.sub_mod(
+            &self.mul_mod(&m, &self.sub_mod(&s, &x)),
+            &self.mul_mod(&BigUint::from(8u32), &yyyy),
+        );
+        let z = self.mul_mod(&BigUint::from(2u32), &self.mul_mod(&point.y, &point.z));
+
+        Point { x, y, z }
+    }
+
+    /// Point addition
+    fn add(&self, p1: &Point, p2: &Point) -> Point {
+        if p1.z.is_zero() {
+            return p2.clone();
+        }
+
+        if p2.z.is_zero() {
+            return p1.clone();
+        }
+
+        let z1z1 = self.mul_mod(&p1.z, &p1.z);
+        let z2z2 = self.mul_mod(&p2.z, &p2.z);
+        let u1 = self.mul_mod(&p1.x, &z2z2);
+        let u2 = self.mul_mod(&p2.x, &z1z1);
+        let s1 = self.mul_mod(&p1.y, &self.mul_mod(&p2.z, &z2z2));
+        let s2 = self.mul_mod(&p2.y, &self.mul_mod(&p1.z, &z1z1));
+
+        if u1 == u2 {
+            return if s1 == s2 {
+                self.double(p1)
+            } else {
+                Point::infinity()
+            };
+        }
+
+        let h = self.sub_mod(&u2, &u1);
+        let r = self.sub_mod(&s2, &s1);
+        let hh = self.mul_mod(&h, &h);
+        let hhh = self.mul_mod(&h, &hh);
+        let v = self.mul_mod(&u1, &hh);
+
+        let x = self.sub_mod(
+            &self.sub_mod(&self.mul_mod(&r, &r), &hhh),
+            &self.add_mod(&v, &v),
+        );
+        let y = self.sub_mod(
+            &self.mul_mod(&r, &self.sub_mod(&v, &x)),
+            &self.mul_mod(&s1, &hhh),
+        );
+        let z = self.mul_mod(&self.mul_mod(&p1.z, &p2.z), &h);
+
+        Point { x, y, z }
+    }
+
+    /// Convert a point from Jacobian to affine coordinates
+    fn to_affine(&self, point: &Point) -> Option<(BigUint, BigUint)> {
+        if point.z.is_zero() {
+            return None;
+        }
+
+        // Invert z using Fermat's little theorem
+        let z_inv = point.z.modpow(&(&self.p - BigUint::from(2u32)), &self.p);
+        let z_inv2 = self.mul_mod(&z_inv, &z_inv);
+        let z_inv3 = self.mul_mod(&z_inv2, &z_inv);
+
+        Some((
+            self.mul_mod(&point.x, &z_inv2),
+            self.mul_mod(&point.y, &z_inv3),
+        ))
+    }
+
+    /// Modular addition
+    fn add_mod(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a + b) % &self.p
+    }
+
+    /// Modular subtraction (operands must be reduced)
+    fn sub_mod(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a + &self.p - b) % &self.p
+    }
+
+    /// Modular multiplication
+    fn mul_mod(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a * b) % &self.p
+    }
+}
+
+/// Curve point in Jacobian coordinates (`z == 0` is the point at infinity)
+#[derive(Clone, Debug)]
+struct Point {
+    x: BigUint,
+    y: BigUint,
+    z: BigUint,
+}
+
+impl Point {
+    /// Create a point from affine coordinates
+    fn from_affine(x: &BigUint, y: &BigUint) -> Self {
+        Point {
+            x: x.clone(),
+            y: y.clone(),
+            z: BigUint::one(),
+        }
+    }
+
+    /// The point at infinity (i.e. the identity element)
+    fn infinity() -> Self {
+        Point {
+            x: BigUint::one(),
+            y: BigUint::one(),
+            z: BigUint::zero(),
+        }
+    }
+}
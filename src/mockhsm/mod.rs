@@ -9,8 +9,12 @@ use std::sync::{Arc, Mutex};
 mod audit;
 mod command;
 mod connection;
+mod ecc;
 mod object;
+mod otp;
+mod rsa;
 mod session;
+mod ssh;
 mod state;
 
 pub use self::connection::MockConnection;
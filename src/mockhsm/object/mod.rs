@@ -8,10 +8,6 @@ mod payload;
 pub(crate) use self::{objects::Objects, payload::Payload};
 use crate::{object, Algorithm};
 
-/// Size of the wrap algorithm's MAC tag. The MockHsm uses AES-GCM instead of
-/// AES-CCM as there isn't a readily available Rust implementation
-const WRAPPED_DATA_MAC_SIZE: usize = 16;
-
 /// Label for the default auth key
 const DEFAULT_AUTHENTICATION_KEY_LABEL: &str = "DEFAULT AUTHKEY CHANGE THIS ASAP";
 
@@ -34,22 +30,6 @@ impl Object {
     }
 }
 
-/// A serialized object which can be exported/imported
-#[derive(Serialize, Deserialize, Debug)]
-pub(crate) struct WrappedObject {
-    pub object_info: object::Info,
-    pub data: Vec<u8>,
-}
-
-impl<'a> From<&'a Object> for WrappedObject {
-    fn from(obj: &'a Object) -> Self {
-        Self {
-            object_info: obj.object_info.clone(),
-            data: obj.payload.as_ref().into(),
-        }
-    }
-}
-
 impl<'a> From<&'a Object> for object::Entry {
     fn from(obj: &'a Object) -> Self {
         object::Entry {
@@ -1,14 +1,12 @@
 use failure::Error;
-use ring::aead::{self, AES_128_GCM, AES_256_GCM};
 use std::collections::{btree_map::Iter as BTreeMapIter, BTreeMap};
 
-use super::{
-    Object, Payload, WrappedObject, DEFAULT_AUTHENTICATION_KEY_LABEL, WRAPPED_DATA_MAC_SIZE,
-};
+use super::{Object, Payload, DEFAULT_AUTHENTICATION_KEY_LABEL};
 use crate::{
     authentication::{self, DEFAULT_AUTHENTICATION_KEY_ID},
+    ccm::Ccm,
     object::{Handle, Id, Info, Label, Origin, Type},
-    serialization::{deserialize, serialize},
+    serialization::serialize,
     wrap, Algorithm, Capability, Domain,
 };
 
@@ -87,6 +85,7 @@ impl Objects {
         };
 
         assert!(self.0.insert(handle, object).is_none());
+        Ok(())
     }
 
     /// Get an object
@@ -94,6 +93,11 @@ impl Objects {
         self.0.get(&Handle::new(object_id, object_type))
     }
 
+    /// Get a mutable reference to an object
+    pub fn get_mut(&mut self, object_id: Id, object_type: Type) -> Option<&mut Object> {
+        self.0.get_mut(&Handle::new(object_id, object_type))
+    }
+
     /// Put a new object in the MockHsm
     pub fn put(
         &mut self,
@@ -105,8 +109,9 @@ impl Objects {
         delegated_capabilities: Capability,
         domains: Domain,
         data: &[u8],
-    ) {
-        let payload = Payload::new(algorithm, data);
+    ) -> Result<(), Error> {
+        let payload = Payload::new(algorithm, data)
+            .ok_or_else(|| format_err!("invalid {:?} object data", algorithm))?;
         let length = payload.len();
 
         let object_info = Info {
@@ -130,6 +135,7 @@ impl Objects {
         };
 
         assert!(self.0.insert(handle, object).is_none());
+        Ok(())
     }
 
     /// Remove an object
@@ -150,17 +156,7 @@ impl Objects {
             None => bail!("no such wrap key: {:?}", wrap_key_id),
         };
 
-        let sealing_key = match wrap_key.algorithm().wrap().unwrap() {
-            // TODO: actually use AES-CCM
-            wrap::Algorithm::AES128_CCM => {
-                aead::SealingKey::new(&AES_128_GCM, wrap_key.payload.as_ref())
-            }
-            wrap::Algorithm::AES256_CCM => {
-                aead::SealingKey::new(&AES_256_GCM, wrap_key.payload.as_ref())
-            }
-            unsupported => bail!("unsupported wrap key algorithm: {:?}", unsupported),
-        }
-        .unwrap();
+        let cipher = Ccm::new(wrap_key.payload.as_ref(), wrap::MAC_SIZE).unwrap();
 
         let object_to_wrap = match self.get(object_id, object_type) {
             Some(o) => o,
@@ -187,28 +183,13 @@ impl Objects {
             Origin::WrappedGenerated | Origin::WrappedImported => (),
         }
 
-        let mut wrapped_object = serialize(&WrappedObject {
+        let wrapped_object = serialize(&wrap::Plaintext {
             object_info,
             data: object_to_wrap.payload.as_ref().into(),
         })
         .unwrap();
 
-        // Make room for the MAC
-        wrapped_object.extend_from_slice(&[0u8; WRAPPED_DATA_MAC_SIZE]);
-
-        let mut nonce = [0u8; 12];
-        nonce.copy_from_slice(&wrap_nonce.as_ref()[..12]);
-
-        aead::seal_in_place(
-            &sealing_key,
-            aead::Nonce::assume_unique_for_key(nonce),
-            aead::Aad::from(b""),
-            &mut wrapped_object,
-            WRAPPED_DATA_MAC_SIZE,
-        )
-        .unwrap();
-
-        Ok(wrapped_object)
+        Ok(cipher.seal(wrap_nonce.as_ref(), &[], &wrapped_object))
     }
 
     /// Deserialize an encrypted object and insert it into the HSM
@@ -218,48 +199,18 @@ impl Objects {
         wrap_nonce: &wrap::Nonce,
         ciphertext: V,
     ) -> Result<Handle, Error> {
-        let opening_key = match self.get(wrap_key_id, Type::WrapKey) {
-            Some(k) => match k.algorithm().wrap().unwrap() {
-                wrap::Algorithm::AES128_CCM => {
-                    aead::OpeningKey::new(&AES_128_GCM, k.payload.as_ref())
-                }
-                wrap::Algorithm::AES256_CCM => {
-                    aead::OpeningKey::new(&AES_256_GCM, k.payload.as_ref())
-                }
-                unsupported => bail!("unsupported wrap key algorithm: {:?}", unsupported),
-            }
-            .unwrap(),
+        let wrap_key = match self.get(wrap_key_id, Type::WrapKey) {
+            Some(k) => wrap::Key::from_bytes(wrap_key_id, k.payload.as_ref()).unwrap(),
             None => bail!("no such wrap key: {:?}", wrap_key_id),
         };
 
-        let mut wrapped_data: Vec<u8> = ciphertext.into();
-
-        let mut nonce = [0u8; 12];
-        nonce.copy_from_slice(&wrap_nonce.as_ref()[..12]);
+        let unwrapped_object = wrap::Message::new(wrap_nonce.clone(), ciphertext)
+            .decrypt(&wrap_key)
+            .map_err(|e| format_err!("error decrypting wrapped object: {}", e))?;
 
-        if aead::open_in_place(
-            &opening_key,
-            aead::Nonce::assume_unique_for_key(nonce),
-            aead::Aad::from(b""),
-            0,
-            &mut wrapped_data,
-        )
-        .is_err()
-        {
-            bail!("error decrypting wrapped object!");
-        }
-
-        let plaintext_len: usize = wrapped_data
-            .len()
-            .checked_sub(WRAPPED_DATA_MAC_SIZE)
-            .unwrap();
-
-        let unwrapped_object: WrappedObject = deserialize(&wrapped_data[..plaintext_len]).unwrap();
-
-        let payload = Payload::new(
-            unwrapped_object.object_info.algorithm,
-            &unwrapped_object.data,
-        );
+        let algorithm = unwrapped_object.object_info.algorithm;
+        let payload = Payload::new(algorithm, &unwrapped_object.data)
+            .ok_or_else(|| format_err!("invalid {:?} object data", algorithm))?;
 
         let object_key = Handle::new(
             unwrapped_object.object_info.object_id,
@@ -267,7 +218,7 @@ impl Objects {
         );
 
         let object = Object {
-            object_info: unwrapped_object.object_info,
+            object_info: unwrapped_object.object_info.clone(),
             payload,
         };
 
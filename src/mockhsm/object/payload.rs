@@ -7,7 +7,12 @@ use ring::{
 };
 use untrusted;
 
-use crate::{algorithm::Algorithm, asymmetric, authentication, hmac, opaque, wrap};
+use crate::{
+    algorithm::Algorithm,
+    asymmetric, authentication, hmac,
+    mockhsm::{ecc, rsa},
+    opaque, otp, template, wrap,
+};
 
 /// Size of an Ed25519 seed
 pub(crate) const ED25519_SEED_SIZE: usize = 32;
@@ -18,6 +23,9 @@ pub(crate) enum Payload {
     /// Authentication keys
     AuthenticationKey(authentication::Key),
 
+    /// Elliptic curve (i.e. short Weierstrass) private scalars
+    EccKeyPair(asymmetric::Algorithm, Vec<u8>),
+
     /// Ed25519 signing keys
     Ed25519KeyPair([u8; ED25519_SEED_SIZE]),
 
@@ -27,29 +35,66 @@ pub(crate) enum Payload {
     /// Opaque data
     Opaque(opaque::Algorithm, Vec<u8>),
 
+    /// Yubico OTP AEAD keys (serialized as `nonce_id || key`)
+    OtpAeadKey(otp::Algorithm, Vec<u8>),
+
+    /// RSA private keys
+    RsaKeyPair(asymmetric::Algorithm, rsa::KeyPair),
+
+    /// SSH templates
+    Template(template::Algorithm, Vec<u8>),
+
     /// Wrapping (i.e. symmetric encryption keys)
     // TODO: actually simulate AES-CCM. Instead we use GCM because *ring* has it
     WrapKey(wrap::Algorithm, Vec<u8>),
 }
 
 impl Payload {
-    /// Create a new payload from the given algorithm and data
-    pub fn new(algorithm: Algorithm, data: &[u8]) -> Self {
-        match algorithm {
+    /// Create a new payload from the given algorithm and data, returning
+    /// `None` if the data isn't a valid key for the algorithm
+    pub fn new(algorithm: Algorithm, data: &[u8]) -> Option<Self> {
+        Some(match algorithm {
             Algorithm::Wrap(alg) => Payload::WrapKey(alg, data.into()),
             Algorithm::Asymmetric(asymmetric::Algorithm::Ed25519) => {
-                assert_eq!(data.len(), ED25519_SEED_SIZE);
+                if data.len() != ED25519_SEED_SIZE {
+                    return None;
+                }
+
                 let mut bytes = [0u8; ED25519_SEED_SIZE];
                 bytes.copy_from_slice(data);
                 Payload::Ed25519KeyPair(bytes)
             }
+            Algorithm::Asymmetric(alg @ asymmetric::Algorithm::RSA_2048)
+            | Algorithm::Asymmetric(alg @ asymmetric::Algorithm::RSA_3072)
+            | Algorithm::Asymmetric(alg @ asymmetric::Algorithm::RSA_4096) => {
+                if data.len() != alg.key_len() {
+                    return None;
+                }
+
+                Payload::RsaKeyPair(alg, rsa::KeyPair::from_primes(data)?)
+            }
+            Algorithm::Asymmetric(alg @ asymmetric::Algorithm::EC_P256)
+            | Algorithm::Asymmetric(alg @ asymmetric::Algorithm::EC_P384)
+            | Algorithm::Asymmetric(alg @ asymmetric::Algorithm::EC_P521)
+            | Algorithm::Asymmetric(alg @ asymmetric::Algorithm::EC_K256) => {
+                ecc::Curve::new(alg)?.public_key(data)?;
+                Payload::EccKeyPair(alg, data.into())
+            }
             Algorithm::Hmac(alg) => Payload::HmacKey(alg, data.into()),
             Algorithm::Opaque(alg) => Payload::Opaque(alg, data.into()),
+            Algorithm::YubicoOtp(alg) => {
+                if data.len() != 4 + alg.key_len() {
+                    return None;
+                }
+
+                Payload::OtpAeadKey(alg, data.into())
+            }
+            Algorithm::Template(alg) => Payload::Template(alg, data.into()),
             Algorithm::Authentication(_) => {
-                Payload::AuthenticationKey(authentication::Key::from_slice(data).unwrap())
+                Payload::AuthenticationKey(authentication::Key::from_slice(data).ok()?)
             }
             _ => panic!("MockHsm does not support putting {:?} objects", algorithm),
-        }
+        })
     }
 
     /// Generate a new key with the given algorithm
@@ -68,6 +113,19 @@ impl Payload {
                     csprng.fill(&mut bytes).unwrap();
                     Payload::Ed25519KeyPair(bytes)
                 }
+                asymmetric::Algorithm::EC_P256
+                | asymmetric::Algorithm::EC_P384
+                | asymmetric::Algorithm::EC_P521
+                | asymmetric::Algorithm::EC_K256 => {
+                    let curve = ecc::Curve::new(asymmetric_alg).unwrap();
+                    Payload::EccKeyPair(asymmetric_alg, curve.random_scalar())
+                }
+                asymmetric::Algorithm::RSA_2048
+                | asymmetric::Algorithm::RSA_3072
+                | asymmetric::Algorithm::RSA_4096 => Payload::RsaKeyPair(
+                    asymmetric_alg,
+                    rsa::KeyPair::generate(asymmetric_alg.key_len()),
+                ),
                 _ => panic!(
                     "MockHsm doesn't support this asymmetric algorithm: {:?}",
                     asymmetric_alg
@@ -91,9 +149,13 @@ impl Payload {
             Payload::AuthenticationKey(_) => {
                 Algorithm::Authentication(authentication::Algorithm::YUBICO_AES)
             }
+            Payload::EccKeyPair(alg, _) => alg.into(),
             Payload::Ed25519KeyPair(_) => Algorithm::Asymmetric(asymmetric::Algorithm::Ed25519),
             Payload::HmacKey(alg, _) => alg.into(),
             Payload::Opaque(alg, _) => alg.into(),
+            Payload::OtpAeadKey(alg, _) => alg.into(),
+            Payload::RsaKeyPair(alg, _) => alg.into(),
+            Payload::Template(alg, _) => alg.into(),
             Payload::WrapKey(alg, _) => alg.into(),
         }
     }
@@ -102,9 +164,13 @@ impl Payload {
     pub fn len(&self) -> u16 {
         let l = match *self {
             Payload::AuthenticationKey(_) => authentication::key::SIZE,
+            Payload::EccKeyPair(_, ref k) => k.len(),
             Payload::Ed25519KeyPair(_) => ED25519_SEED_SIZE,
             Payload::HmacKey(_, ref data) => data.len(),
             Payload::Opaque(_, ref data) => data.len(),
+            Payload::OtpAeadKey(_, ref data) => data.len(),
+            Payload::RsaKeyPair(_, ref k) => k.as_ref().len(),
+            Payload::Template(_, ref data) => data.len(),
             Payload::WrapKey(_, ref data) => data.len(),
         };
         l as u16
@@ -113,6 +179,7 @@ impl Payload {
     /// If this object is a public key, return its byte serialization
     pub fn public_key_bytes(&self) -> Option<Vec<u8>> {
         match *self {
+            Payload::EccKeyPair(alg, ref k) => ecc::Curve::new(alg).unwrap().public_key(k),
             Payload::Ed25519KeyPair(ref k) => Some(
                 Ed25519KeyPair::from_seed_unchecked(untrusted::Input::from(k))
                     .unwrap()
@@ -120,6 +187,7 @@ impl Payload {
                     .as_ref()
                     .into(),
             ),
+            Payload::RsaKeyPair(_, ref k) => Some(k.modulus()),
             _ => None,
         }
     }
@@ -137,9 +205,13 @@ impl AsRef<[u8]> for Payload {
     fn as_ref(&self) -> &[u8] {
         match *self {
             Payload::AuthenticationKey(ref k) => k.0.as_ref(),
+            Payload::EccKeyPair(_, ref k) => k,
             Payload::Ed25519KeyPair(ref k) => k.as_ref(),
             Payload::HmacKey(_, ref data) => data,
             Payload::Opaque(_, ref data) => data,
+            Payload::OtpAeadKey(_, ref data) => data,
+            Payload::RsaKeyPair(_, ref k) => k.as_ref(),
+            Payload::Template(_, ref data) => data,
             Payload::WrapKey(_, ref data) => data,
         }
     }
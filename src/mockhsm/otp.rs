@@ -0,0 +1,130 @@
+//! Yubico OTP support for the `MockHsm`.
+//!
+//! AEADs are `nonce || AES-CCM(otp_key || private_id) || mac`, where the
+//! CCM nonce is the key's nonce ID followed by the per-AEAD nonce.
+
+use crate::{ccm::Ccm, otp};
+use aes::{
+    block_cipher_trait::{generic_array::GenericArray, BlockCipher},
+    Aes128,
+};
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use rand_os::{rand_core::RngCore, OsRng};
+use subtle::ConstantTimeEq;
+
+/// Size of the random per-AEAD nonce
+const AEAD_NONCE_SIZE: usize = 6;
+
+/// Size of a serialized nonce ID
+const NONCE_ID_SIZE: usize = 4;
+
+/// Size of the CCM message authentication code
+const AEAD_MAC_SIZE: usize = 8;
+
+/// Expected CRC-16 residual of a valid OTP
+const CRC_RESIDUAL: u16 = 0xf0b8;
+
+/// OTP AEAD key loaded from a serialized `nonce_id || key` payload
+pub(crate) struct AeadKey {
+    /// Nonce ID of the key
+    nonce_id: otp::NonceId,
+
+    /// AES-CCM cipher
+    cipher: Ccm,
+}
+
+impl AeadKey {
+    /// Load an OTP AEAD key from its serialized payload
+    pub fn new(payload: &[u8]) -> Option<Self> {
+        if payload.len() <= NONCE_ID_SIZE {
+            return None;
+        }
+
+        let (nonce_id, key) = payload.split_at(NONCE_ID_SIZE);
+
+        Some(Self {
+            nonce_id: BigEndian::read_u32(nonce_id),
+            cipher: Ccm::new(key, AEAD_MAC_SIZE)?,
+        })
+    }
+
+    /// Encrypt a YubiKey's OTP key and private ID as an AEAD
+    pub fn seal(&self, plaintext: &[u8]) -> otp::Aead {
+        let mut nonce = [0u8; AEAD_NONCE_SIZE];
+        OsRng::new().unwrap().fill_bytes(&mut nonce);
+
+        let mut aead = nonce.to_vec();
+        aead.extend_from_slice(&self.cipher.seal(&self.ccm_nonce(&nonce), &[], plaintext));
+        otp::Aead(aead)
+    }
+
+    /// Decrypt an AEAD, returning the OTP key and private ID
+    pub fn open(&self, aead: &otp::Aead) -> Option<Vec<u8>> {
+        if aead.len() != otp::AEAD_SIZE {
+            return None;
+        }
+
+        let (nonce, ciphertext) = aead.as_slice().split_at(AEAD_NONCE_SIZE);
+        self.cipher.open(&self.ccm_nonce(nonce), &[], ciphertext)
+    }
+
+    /// Compute the CCM nonce for the given AEAD nonce
+    fn ccm_nonce(&self, nonce: &[u8]) -> Vec<u8> {
+        let mut ccm_nonce = vec![0u8; NONCE_ID_SIZE];
+        BigEndian::write_u32(&mut ccm_nonce, self.nonce_id);
+        ccm_nonce.extend_from_slice(nonce);
+        ccm_nonce
+    }
+}
+
+/// Serialize an OTP AEAD key payload
+pub(crate) fn aead_key_payload(nonce_id: otp::NonceId, key: &[u8]) -> Vec<u8> {
+    let mut payload = vec![0u8; NONCE_ID_SIZE];
+    BigEndian::write_u32(&mut payload, nonce_id);
+    payload.extend_from_slice(key);
+    payload
+}
+
+/// Decrypt an OTP with the given decrypted AEAD (i.e. `otp_key || private_id`),
+/// returning `None` if the OTP is invalid
+pub(crate) fn decrypt_otp(key_and_id: &[u8], otp: &[u8]) -> Option<otp::DecryptedOtp> {
+    if key_and_id.len() != otp::KEY_SIZE + otp::PRIVATE_ID_SIZE || otp.len() != otp::OTP_SIZE {
+        return None;
+    }
+
+    let (key, private_id) = key_and_id.split_at(otp::KEY_SIZE);
+    let mut block = GenericArray::clone_from_slice(otp);
+    Aes128::new_varkey(key).unwrap().decrypt_block(&mut block);
+
+    if crc16(&block) != CRC_RESIDUAL
+        || block[..otp::PRIVATE_ID_SIZE].ct_eq(private_id).unwrap_u8() != 1
+    {
+        return None;
+    }
+
+    Some(otp::DecryptedOtp {
+        use_counter: LittleEndian::read_u16(&block[6..8]),
+        timestamp: LittleEndian::read_u24(&block[8..11]),
+        session_counter: block[11],
+    })
+}
+
+/// CRC-16 as used by Yubico OTP (ISO 13239)
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc = 0xffffu16;
+
+    for &byte in data {
+        crc ^= u16::from(byte);
+
+        for _ in 0..8 {
+            let lsb = crc & 1;
+            crc >>= 1;
+
+            if lsb != 0 {
+                crc ^= 0x8408;
+            }
+        }
+    }
+
+    crc
+}
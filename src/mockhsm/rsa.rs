@@ -0,0 +1,438 @@
+//! Software RSA implementation used by the `MockHsm`.
+//!
+//! This is a textbook implementation which makes no attempt to run in
+//! constant time. It exists solely to exercise RSA commands in tests.
+
+use crate::rsa::{mgf, PUBLIC_EXPONENT};
+use byteorder::{BigEndian, ByteOrder};
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive, Zero};
+use rand_os::{rand_core::RngCore, OsRng};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use subtle::ConstantTimeEq;
+
+/// Minimum amount of padding in an RSAES-PKCS#1v1.5 encrypted message
+const PKCS1_MIN_PADDING_SIZE: usize = 8;
+
+/// ASN.1 DER `DigestInfo` prefixes for RSASSA-PKCS#1v1.5 (RFC 8017 Section 9.2)
+const SHA1_DIGEST_INFO_PREFIX: &[u8] =
+    b"\x30\x21\x30\x09\x06\x05\x2B\x0E\x03\x02\x1A\x05\x00\x04\x14";
+const SHA256_DIGEST_INFO_PREFIX: &[u8] =
+    b"\x30\x31\x30\x0D\x06\x09\x60\x86\x48\x01\x65\x03\x04\x02\x01\x05\x00\x04\x20";
+const SHA384_DIGEST_INFO_PREFIX: &[u8] =
+    b"\x30\x41\x30\x0D\x06\x09\x60\x86\x48\x01\x65\x03\x04\x02\x02\x05\x00\x04\x30";
+const SHA512_DIGEST_INFO_PREFIX: &[u8] =
+    b"\x30\x51\x30\x0D\x06\x09\x60\x86\x48\x01\x65\x03\x04\x02\x03\x05\x00\x04\x40";
+
+/// Number of Miller-Rabin rounds to perform when generating primes
+const MILLER_RABIN_ROUNDS: usize = 16;
+
+/// Upper bound on the small primes used to sieve prime candidates
+const SIEVE_LIMIT: u32 = 1 << 14;
+
+/// RSA private key
+#[derive(Clone, Debug)]
+pub(crate) struct KeyPair {
+    /// Primes `p || q` (i.e. the format accepted by `PutAsymmetricKey`)
+    primes: Vec<u8>,
+
+    /// Public modulus
+    n: BigUint,
+
+    /// Private exponent
+    d: BigUint,
+
+    /// Size of the modulus in bytes
+    modulus_len: usize,
+}
+
+impl KeyPair {
+    /// Create a keypair from the concatenation of its primes `p || q`,
+    /// returning `None` if they aren't two distinct primes
+    pub fn from_primes(primes: &[u8]) -> Option<Self> {
+        if primes.is_empty() || primes.len() % 2 != 0 {
+            return None;
+        }
+
+        let (p_bytes, q_bytes) = primes.split_at(primes.len() / 2);
+        let p = BigUint::from_bytes_be(p_bytes);
+        let q = BigUint::from_bytes_be(q_bytes);
+
+        let mut rng = OsRng::new().ok()?;
+        let min_prime = BigUint::from(5u32);
+
+        if p == q
+            || p < min_prime
+            || q < min_prime
+            || !is_probable_prime(&p, &mut rng)
+            || !is_probable_prime(&q, &mut rng)
+        {
+            return None;
+        }
+
+        let n = &p * &q;
+
+        let phi = (&p - BigUint::one()) * (&q - BigUint::one());
+        let d = mod_inverse(&BigUint::from(PUBLIC_EXPONENT), &phi)?;
+
+        Some(Self {
+            primes: primes.into(),
+            n,
+            d,
+            modulus_len: primes.len(),
+        })
+    }
+
+    /// Generate a random keypair with a modulus of the given size in bytes
+    pub fn generate(modulus_len: usize) -> Self {
+        let small_primes = small_primes();
+
+        loop {
+            let p = random_prime(modulus_len / 2, &small_primes);
+            let q = random_prime(modulus_len / 2, &small_primes);
+
+            if p == q {
+                continue;
+            }
+
+            let mut primes = to_fixed_size(&p, modulus_len / 2);
+            primes.extend_from_slice(&to_fixed_size(&q, modulus_len / 2));
+
+            // Fails if the public exponent isn't invertible modulo phi(n)
+            if let Some(keypair) = Self::from_primes(&primes) {
+                return keypair;
+            }
+        }
+    }
+
+    /// Size of the public modulus in bytes
+    pub fn modulus_len(&self) -> usize {
+        self.modulus_len
+    }
+
+    /// Serialize the public modulus as big endian bytes
+    pub fn modulus(&self) -> Vec<u8> {
+        to_fixed_size(&self.n, self.modulus_len)
+    }
+
+    /// Compute an RSASSA-PKCS#1v1.5 signature of the given digest. The
+    /// digest function is selected by the length of the digest.
+    pub fn sign_pkcs1v15(&self, digest: &[u8]) -> Option<Vec<u8>> {
+        let prefix = match digest_alg(digest.len())? {
+            mgf::Algorithm::SHA1 => SHA1_DIGEST_INFO_PREFIX,
+            mgf::Algorithm::SHA256 => SHA256_DIGEST_INFO_PREFIX,
+            mgf::Algorithm::SHA384 => SHA384_DIGEST_INFO_PREFIX,
+            mgf::Algorithm::SHA512 => SHA512_DIGEST_INFO_PREFIX,
+        };
+
+        let digest_info_len = prefix.len() + digest.len();
+
+        if self.modulus_len < digest_info_len + PKCS1_MIN_PADDING_SIZE + 3 {
+            return None;
+        }
+
+        let mut encoded_message = vec![0x00, 0x01];
+        encoded_message.resize(self.modulus_len - digest_info_len - 1, 0xff);
+        encoded_message.push(0x00);
+        encoded_message.extend_from_slice(prefix);
+        encoded_message.extend_from_slice(digest);
+
+        self.sign_raw(&encoded_message)
+    }
+
+    /// Compute an RSASSA-PSS signature of the given digest with a random salt
+    /// of the given length. The digest function is selected by the length of
+    /// the digest.
+    pub fn sign_pss(
+        &self,
+        digest: &[u8],
+        mgf1_hash_alg: mgf::Algorithm,
+        salt_len: usize,
+    ) -> Option<Vec<u8>> {
+        let hash_alg = digest_alg(digest.len())?;
+        let hash_len = digest.len();
+
+        // Moduli are always a multiple of 8 bits, so `emLen` is `modulus_len`
+        // and only the top bit of the encoded message is cleared
+        if self.modulus_len < hash_len + salt_len + 2 {
+            return None;
+        }
+
+        let mut salt = vec![0u8; salt_len];
+        OsRng::new().unwrap().fill_bytes(&mut salt);
+
+        let mut m_prime = vec![0u8; 8];
+        m_prime.extend_from_slice(digest);
+        m_prime.extend_from_slice(&salt);
+        let h = self::digest(hash_alg, &m_prime);
+
+        let db_len = self.modulus_len - hash_len - 1;
+        let mut db = vec![0u8; db_len - salt_len - 1];
+        db.push(0x01);
+        db.extend_from_slice(&salt);
+
+        let db_mask = mgf1(mgf1_hash_alg, &h, db_len);
+        xor_in_place(&mut db, &db_mask);
+        db[0] &= 0x7f;
+
+        let mut encoded_message = db;
+        encoded_message.extend_from_slice(&h);
+        encoded_message.push(0xbc);
+
+        self.sign_raw(&encoded_message)
+    }
+
+    /// Raw RSA signature primitive (RSASP1)
+    fn sign_raw(&self, encoded_message: &[u8]) -> Option<Vec<u8>> {
+        let m = BigUint::from_bytes_be(encoded_message);
+
+        if m >= self.n {
+            return None;
+        }
+
+        Some(to_fixed_size(&m.modpow(&self.d, &self.n), self.modulus_len))
+    }
+
+    /// Raw RSA decryption primitive (RSADP)
+    pub fn decrypt_raw(&self, ciphertext: &[u8]) -> Option<Vec<u8>> {
+        if ciphertext.len() != self.modulus_len {
+            return None;
+        }
+
+        let c = BigUint::from_bytes_be(ciphertext);
+
+        if c >= self.n {
+            return None;
+        }
+
+        Some(to_fixed_size(&c.modpow(&self.d, &self.n), self.modulus_len))
+    }
+
+    /// Decrypt a message encrypted with RSAES-PKCS#1v1.5
+    pub fn decrypt_pkcs1v15(&self, ciphertext: &[u8]) -> Option<Vec<u8>> {
+        let encoded_message = self.decrypt_raw(ciphertext)?;
+
+        if encoded_message[0] != 0x00 || encoded_message[1] != 0x02 {
+            return None;
+        }
+
+        let padding_len = encoded_message[2..].iter().position(|&byte| byte == 0)?;
+
+        if padding_len < PKCS1_MIN_PADDING_SIZE {
+            return None;
+        }
+
+        Some(encoded_message[(padding_len + 3)..].into())
+    }
+
+    /// Decrypt a message encrypted with RSAES-OAEP. The digest function
+    /// is selected by the length of the given label hash.
+    pub fn decrypt_oaep(
+        &self,
+        ciphertext: &[u8],
+        label_hash: &[u8],
+        mgf1_hash_alg: mgf::Algorithm,
+    ) -> Option<Vec<u8>> {
+        let hash_len = label_hash.len();
+
+        if self.modulus_len < 2 * hash_len + 2 {
+            return None;
+        }
+
+        let encoded_message = self.decrypt_raw(ciphertext)?;
+        let (masked_seed, masked_db) = encoded_message[1..].split_at(hash_len);
+
+        let mut seed = mgf1(mgf1_hash_alg, masked_db, hash_len);
+        xor_in_place(&mut seed, masked_seed);
+
+        let mut db = mgf1(mgf1_hash_alg, &seed, masked_db.len());
+        xor_in_place(&mut db, masked_db);
+
+        if encoded_message[0] != 0x00 || db[..hash_len].ct_eq(label_hash).unwrap_u8() != 1 {
+            return None;
+        }
+
+        let separator_pos = db[hash_len..].iter().position(|&byte| byte != 0)? + hash_len;
+
+        if db[separator_pos] != 0x01 {
+            return None;
+        }
+
+        Some(db[(separator_pos + 1)..].into())
+    }
+}
+
+impl AsRef<[u8]> for KeyPair {
+    fn as_ref(&self) -> &[u8] {
+        &self.primes
+    }
+}
+
+/// Find the hash function with the given digest size
+fn digest_alg(digest_len: usize) -> Option<mgf::Algorithm> {
+    match digest_len {
+        20 => Some(mgf::Algorithm::SHA1),
+        32 => Some(mgf::Algorithm::SHA256),
+        48 => Some(mgf::Algorithm::SHA384),
+        64 => Some(mgf::Algorithm::SHA512),
+        _ => None,
+    }
+}
+
+/// Compute the odd primes less than `SIEVE_LIMIT`
+fn small_primes() -> Vec<u32> {
+    let mut primes: Vec<u32> = vec![];
+
+    for candidate in (3..SIEVE_LIMIT).step_by(2) {
+        if primes.iter().all(|p| candidate % p != 0) {
+            primes.push(candidate);
+        }
+    }
+
+    primes
+}
+
+/// Generate a random prime of the given size in bytes with its two highest
+/// bits set (so the product of two such primes is twice as many bytes)
+fn random_prime(len: usize, small_primes: &[u32]) -> BigUint {
+    let mut rng = OsRng::new().unwrap();
+    let mut bytes = vec![0u8; len];
+
+    loop {
+        rng.fill_bytes(&mut bytes);
+        bytes[0] |= 0xc0;
+        bytes[len - 1] |= 0x01;
+
+        let candidate = BigUint::from_bytes_be(&bytes);
+
+        // Sieve odd candidates following the random starting point using the
+        // residues of the starting point modulo each small prime
+        let residues: Vec<u32> = small_primes
+            .iter()
+            .map(|&p| (&candidate % BigUint::from(p)).to_u32().unwrap())
+            .collect();
+
+        for delta in (0..(1u32 << 16)).step_by(2) {
+            let sieved = small_primes
+                .iter()
+                .zip(&residues)
+                .any(|(&p, &r)| (r + delta) % p == 0);
+
+            if sieved {
+                continue;
+            }
+
+            let prime = &candidate + BigUint::from(delta);
+
+            if prime.bits() == len * 8 && is_probable_prime(&prime, &mut rng) {
+                return prime;
+            }
+        }
+    }
+}
+
+/// Miller-Rabin probabilistic primality test
+fn is_probable_prime(n: &BigUint, rng: &mut OsRng) -> bool {
+    let one = BigUint::one();
+    let two = BigUint::from(2u32);
+    let n_minus_one = n - &one;
+
+    let mut d = n_minus_one.clone();
+    let mut s = 0;
+
+    while d.is_even() {
+        d >>= 1;
+        s += 1;
+    }
+
+    let mut base_bytes = vec![0u8; (n.bits() + 7) / 8];
+
+    'witness: for _ in 0..MILLER_RABIN_ROUNDS {
+        rng.fill_bytes(&mut base_bytes);
+        let base = BigUint::from_bytes_be(&base_bytes) % (n - 3u32) + &two;
+        let mut x = base.modpow(&d, n);
+
+        if x == one || x == n_minus_one {
+            continue;
+        }
+
+        for _ in 1..s {
+            x = x.modpow(&two, n);
+
+            if x == n_minus_one {
+                continue 'witness;
+            }
+        }
+
+        return false;
+    }
+
+    true
+}
+
+/// Compute the multiplicative inverse of `a` modulo `m`
+fn mod_inverse(a: &BigUint, m: &BigUint) -> Option<BigUint> {
+    let modulus = BigInt::from_biguint(Sign::Plus, m.clone());
+    let mut r = (BigInt::from_biguint(Sign::Plus, a.clone()), modulus.clone());
+    let mut s = (BigInt::one(), BigInt::zero());
+
+    while !r.1.is_zero() {
+        let quotient = &r.0 / &r.1;
+        r = (r.1.clone(), &r.0 - &quotient * &r.1);
+        s = (s.1.clone(), &s.0 - &quotient * &s.1);
+    }
+
+    if r.0 != BigInt::one() {
+        return None;
+    }
+
+    s.0.mod_floor(&modulus).to_biguint()
+}
+
+/// Compute the digest of the given data using the given MGF1 hash function
+fn digest(algorithm: mgf::Algorithm, data: &[u8]) -> Vec<u8> {
+    match algorithm {
+        mgf::Algorithm::SHA1 => Sha1::digest(data).as_slice().into(),
+        mgf::Algorithm::SHA256 => Sha256::digest(data).as_slice().into(),
+        mgf::Algorithm::SHA384 => Sha384::digest(data).as_slice().into(),
+        mgf::Algorithm::SHA512 => Sha512::digest(data).as_slice().into(),
+    }
+}
+
+/// MGF1 mask generating function (RFC 8017 Appendix B.2.1)
+fn mgf1(algorithm: mgf::Algorithm, seed: &[u8], mask_len: usize) -> Vec<u8> {
+    let mut mask = Vec::with_capacity(mask_len);
+    let mut counter = 0u32;
+
+    while mask.len() < mask_len {
+        let mut counter_bytes = [0u8; 4];
+        BigEndian::write_u32(&mut counter_bytes, counter);
+
+        let mut input = seed.to_vec();
+        input.extend_from_slice(&counter_bytes);
+        mask.extend_from_slice(&digest(algorithm, &input));
+        counter += 1;
+    }
+
+    mask.truncate(mask_len);
+    mask
+}
+
+/// XOR the given mask into a buffer
+fn xor_in_place(buffer: &mut [u8], mask: &[u8]) {
+    for (byte, mask_byte) in buffer.iter_mut().zip(mask) {
+        *byte ^= mask_byte;
+    }
+}
+
+/// Serialize an integer as a big endian byte string of the given size
+fn to_fixed_size(n: &BigUint, size: usize) -> Vec<u8> {
+    let bytes = n.to_bytes_be();
+    assert!(bytes.len() <= size, "integer too large for {} bytes", size);
+
+    let mut result = vec![0u8; size - bytes.len()];
+    result.extend_from_slice(&bytes);
+    result
+}
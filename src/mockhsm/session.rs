@@ -3,7 +3,7 @@
 use std::fmt::{self, Debug};
 
 use crate::{
-    command, response,
+    command, object, response,
     session::{
         securechannel::{Challenge, Cryptogram, SecureChannel},
         Id,
@@ -15,6 +15,9 @@ pub(crate) struct HsmSession {
     /// ID of the session
     pub id: Id,
 
+    /// ID of the authentication key used to establish this session
+    pub authentication_key_id: object::Id,
+
     /// Card challenge for this session
     pub card_challenge: Challenge,
 
@@ -24,9 +27,15 @@ pub(crate) struct HsmSession {
 
 impl HsmSession {
     /// Create a new session
-    pub fn new(id: Id, card_challenge: Challenge, channel: SecureChannel) -> Self {
+    pub fn new(
+        id: Id,
+        authentication_key_id: object::Id,
+        card_challenge: Challenge,
+        channel: SecureChannel,
+    ) -> Self {
         Self {
             id,
+            authentication_key_id,
             card_challenge,
             channel,
         }
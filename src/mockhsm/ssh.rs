@@ -0,0 +1,161 @@
+//! SSH certificate requests as seen by the `MockHsm`, which must enforce the
+//! constraints of an SSH template before signing them.
+
+use crate::{
+    asymmetric::PublicKey,
+    ssh::{
+        encoding::Decoder,
+        SshError,
+        SshErrorKind::{AlgorithmUnsupported, FormatInvalid},
+        TIMESTAMP_SIGNATURE_SIZE, TIMESTAMP_SIZE,
+    },
+    template::SshTemplate,
+};
+use byteorder::{BigEndian, ByteOrder};
+use ring::signature::RSA_PKCS1_2048_8192_SHA256;
+use sha2::{Digest, Sha256};
+
+/// Suffix of the key types of OpenSSH certificates
+const CERT_KEY_TYPE_SUFFIX: &[u8] = b"-cert-v01@openssh.com";
+
+/// Parsed certificate request: `timestamp || timestamp signature || body`
+#[derive(Debug)]
+pub(crate) struct Request<'a> {
+    /// Time of the request (seconds since the UNIX epoch)
+    timestamp: u32,
+
+    /// Signature of the timestamp message by the template's timestamp key
+    timestamp_signature: &'a [u8],
+
+    /// Certificate body to be signed by the CA key
+    body: &'a [u8],
+
+    /// Principals the certificate is valid for
+    principals: Vec<&'a [u8]>,
+
+    /// Start of the validity period (seconds since the UNIX epoch)
+    valid_after: u64,
+
+    /// End of the validity period (seconds since the UNIX epoch)
+    valid_before: u64,
+
+    /// Public key of the CA (SSH wire format)
+    ca_public_key: &'a [u8],
+}
+
+impl<'a> Request<'a> {
+    /// Parse a certificate request sent with `SignSshCertificate`
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, SshError> {
+        let body_offset = TIMESTAMP_SIZE + TIMESTAMP_SIGNATURE_SIZE;
+        ensure!(
+            bytes.len() > body_offset,
+            FormatInvalid,
+            "SSH certificate request too short: {}",
+            bytes.len()
+        );
+
+        let (timestamp_signature, body) =
+            bytes[TIMESTAMP_SIZE..].split_at(TIMESTAMP_SIGNATURE_SIZE);
+        let mut decoder = Decoder::new(body);
+
+        let key_type = decoder.string()?;
+        ensure!(
+            key_type.ends_with(CERT_KEY_TYPE_SUFFIX),
+            FormatInvalid,
+            "not an OpenSSH certificate"
+        );
+
+        let _nonce = decoder.string()?;
+
+        // Number of public key fields following the nonce
+        let key_fields = match &key_type[..key_type.len() - CERT_KEY_TYPE_SUFFIX.len()] {
+            b"ssh-ed25519" => 1,
+            b"ssh-rsa" => 2,
+            key_type if key_type.starts_with(b"ecdsa-sha2-") => 2,
+            _ => fail!(AlgorithmUnsupported, "unsupported certificate key type"),
+        };
+
+        for _ in 0..key_fields {
+            decoder.string()?;
+        }
+
+        let _serial = decoder.u64()?;
+        let _cert_type = decoder.u32()?;
+        let _key_id = decoder.string()?;
+
+        let mut principals = vec![];
+        let mut principals_decoder = Decoder::new(decoder.string()?);
+
+        while !principals_decoder.remaining().is_empty() {
+            principals.push(principals_decoder.string()?);
+        }
+
+        let valid_after = decoder.u64()?;
+        let valid_before = decoder.u64()?;
+        let _critical_options = decoder.string()?;
+        let _extensions = decoder.string()?;
+        let _reserved = decoder.string()?;
+        let ca_public_key = decoder.string()?;
+
+        ensure!(
+            decoder.remaining().is_empty(),
+            FormatInvalid,
+            "trailing data in SSH certificate request"
+        );
+
+        Ok(Self {
+            timestamp: BigEndian::read_u32(bytes),
+            timestamp_signature,
+            body,
+            principals,
+            valid_after,
+            valid_before,
+            ca_public_key,
+        })
+    }
+
+    /// Get the certificate body (i.e. the data the CA signs)
+    pub fn body(&self) -> &'a [u8] {
+        self.body
+    }
+
+    /// Verify the timestamp signature under the given (RSA) timestamp key
+    pub fn verify_timestamp(&self, timestamp_key: &PublicKey) -> bool {
+        let mut message = vec![0u8; TIMESTAMP_SIZE];
+        BigEndian::write_u32(&mut message, self.timestamp);
+        message.extend_from_slice(Sha256::digest(self.ca_public_key).as_slice());
+        message.extend_from_slice(Sha256::digest(self.body).as_slice());
+
+        timestamp_key
+            .rsa()
+            .map(|key| {
+                key.verify(
+                    &RSA_PKCS1_2048_8192_SHA256,
+                    &message,
+                    self.timestamp_signature,
+                )
+            })
+            .unwrap_or(false)
+    }
+
+    /// Is the validity period within the bounds the template allows around
+    /// the request timestamp?
+    pub fn is_validity_allowed(&self, template: &SshTemplate) -> bool {
+        let timestamp = u64::from(self.timestamp);
+        let not_before = template.not_before_duration().as_secs();
+        let not_after = template.not_after_duration().as_secs();
+
+        self.valid_after <= self.valid_before
+            && self.valid_after >= timestamp.saturating_sub(not_before)
+            && self.valid_before <= timestamp + not_after
+    }
+
+    /// Are all of the certificate's principals permitted by the template,
+    /// i.e. are none of them denied?
+    pub fn are_principals_allowed(&self, template: &SshTemplate) -> bool {
+        template
+            .denied_principals()
+            .iter()
+            .all(|denied| !self.principals.contains(&denied.as_bytes()))
+    }
+}
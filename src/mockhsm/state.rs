@@ -12,7 +12,11 @@ use crate::{
 };
 use std::collections::BTreeMap;
 
-use super::{audit::CommandAuditOptions, object::Objects, session::HsmSession};
+use super::{
+    audit::{AuditLog, CommandAuditOptions},
+    object::Objects,
+    session::HsmSession,
+};
 
 /// Mutable interior state of the `MockHsm`
 #[derive(Debug)]
@@ -24,6 +28,9 @@ pub(crate) struct State {
     /// via the `SetLogIndex` command.
     pub(super) force_audit: AuditOption,
 
+    /// Audit log entries
+    pub(super) audit_log: AuditLog,
+
     /// Active sessions with the MockHsm
     sessions: BTreeMap<session::Id, HsmSession>,
 
@@ -37,6 +44,7 @@ impl State {
         Self {
             command_audit_options: CommandAuditOptions::default(),
             force_audit: AuditOption::Off,
+            audit_log: AuditLog::default(),
             sessions: BTreeMap::new(),
             objects: Objects::default(),
         }
@@ -80,7 +88,7 @@ impl State {
             )
         };
 
-        let session = HsmSession::new(session_id, card_challenge, channel);
+        let session = HsmSession::new(session_id, authentication_key_id, card_challenge, channel);
         assert!(self.sessions.insert(session_id, session).is_none());
 
         self.get_session(session_id).unwrap()
@@ -104,6 +112,7 @@ impl State {
     /// Reset the internal HSM state, closing all connections
     pub fn reset(&mut self) {
         self.command_audit_options = CommandAuditOptions::default();
+        self.audit_log = AuditLog::default();
         self.sessions = BTreeMap::new();
         self.objects = Objects::default();
     }
@@ -0,0 +1,55 @@
+/// Size of a Yubico OTP AEAD: nonce (6 bytes), encrypted OTP key and
+/// private ID (22 bytes), and MAC (8 bytes)
+pub const AEAD_SIZE: usize = 36;
+
+/// Yubico OTP AEADs: a YubiKey's OTP key and private ID, encrypted under
+/// an OTP AEAD key stored in the HSM
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Aead(pub Vec<u8>);
+
+#[allow(clippy::len_without_is_empty)]
+impl Aead {
+    /// Create a new OTP AEAD
+    pub fn new<V: Into<Vec<u8>>>(vec: V) -> Aead {
+        Aead(vec.into())
+    }
+
+    /// Unwrap inner byte vector
+    pub fn into_vec(self) -> Vec<u8> {
+        self.into()
+    }
+
+    /// Get length of the AEAD
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Get slice of the inner byte vector
+    pub fn as_slice(&self) -> &[u8] {
+        self.as_ref()
+    }
+}
+
+impl AsRef<[u8]> for Aead {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl From<Vec<u8>> for Aead {
+    fn from(vec: Vec<u8>) -> Aead {
+        Aead::new(vec)
+    }
+}
+
+impl<'a> From<&'a [u8]> for Aead {
+    fn from(slice: &'a [u8]) -> Aead {
+        Aead::from(slice.to_vec())
+    }
+}
+
+impl Into<Vec<u8>> for Aead {
+    fn into(self) -> Vec<u8> {
+        self.0
+    }
+}
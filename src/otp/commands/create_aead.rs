@@ -0,0 +1,31 @@
+//! Create a Yubico OTP AEAD from an existing OTP key and private ID
+//!
+//! <https://developers.yubico.com/YubiHSM2/Commands/Create_Otp_Aead.html>
+
+use crate::{
+    command::{self, Command},
+    object, otp,
+    response::Response,
+};
+
+/// Request parameters for `command::create_otp_aead`
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct CreateOtpAeadCommand {
+    /// ID of the OTP AEAD key to encrypt the AEAD with
+    pub key_id: object::Id,
+
+    /// OTP key (16 bytes) followed by private ID (6 bytes)
+    pub data: Vec<u8>,
+}
+
+impl Command for CreateOtpAeadCommand {
+    type ResponseType = CreateOtpAeadResponse;
+}
+
+/// Response from `command::create_otp_aead`
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct CreateOtpAeadResponse(pub(crate) otp::Aead);
+
+impl Response for CreateOtpAeadResponse {
+    const COMMAND_CODE: command::Code = command::Code::CreateOtpAead;
+}
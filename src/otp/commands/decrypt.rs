@@ -0,0 +1,54 @@
+//! Decrypt a Yubico OTP using an OTP AEAD
+//!
+//! <https://developers.yubico.com/YubiHSM2/Commands/Decrypt_Otp.html>
+
+use crate::{
+    command::{self, Command},
+    object, otp,
+    response::Response,
+};
+
+/// Request parameters for `command::decrypt_otp`
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct DecryptOtpCommand {
+    /// ID of the OTP AEAD key the AEAD is encrypted under
+    pub key_id: object::Id,
+
+    /// AEAD (36 bytes) followed by the binary OTP (16 bytes)
+    pub data: Vec<u8>,
+}
+
+impl Command for DecryptOtpCommand {
+    type ResponseType = DecryptOtpResponse;
+}
+
+/// Response from `command::decrypt_otp`
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct DecryptOtpResponse {
+    /// Use counter
+    pub use_counter: u16,
+
+    /// Session counter
+    pub session_counter: u8,
+
+    /// Timestamp (high 8 bits)
+    pub timestamp_high: u8,
+
+    /// Timestamp (low 16 bits)
+    pub timestamp_low: u16,
+}
+
+impl Response for DecryptOtpResponse {
+    const COMMAND_CODE: command::Code = command::Code::DecryptOtp;
+}
+
+impl From<DecryptOtpResponse> for otp::DecryptedOtp {
+    fn from(response: DecryptOtpResponse) -> otp::DecryptedOtp {
+        otp::DecryptedOtp {
+            use_counter: response.use_counter,
+            session_counter: response.session_counter,
+            timestamp: (u32::from(response.timestamp_high) << 16)
+                | u32::from(response.timestamp_low),
+        }
+    }
+}
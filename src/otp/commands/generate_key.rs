@@ -0,0 +1,35 @@
+//! Generate a new OTP AEAD key within the `YubiHSM 2`
+//!
+//! <https://developers.yubico.com/YubiHSM2/Commands/Generate_Otp_Aead_Key.html>
+
+use crate::{
+    command::{self, Command},
+    object::{self, generate},
+    otp,
+    response::Response,
+};
+
+/// Request parameters for `command::generate_otp_aead_key`
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct GenOtpAeadKeyCommand {
+    /// Common parameters to all key generation commands
+    pub params: generate::Params,
+
+    /// Nonce ID
+    pub nonce_id: otp::NonceId,
+}
+
+impl Command for GenOtpAeadKeyCommand {
+    type ResponseType = GenOtpAeadKeyResponse;
+}
+
+/// Response from `command::generate_otp_aead_key`
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct GenOtpAeadKeyResponse {
+    /// ID of the key
+    pub key_id: object::Id,
+}
+
+impl Response for GenOtpAeadKeyResponse {
+    const COMMAND_CODE: command::Code = command::Code::GenerateOtpAead;
+}
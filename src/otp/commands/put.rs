@@ -4,7 +4,7 @@
 
 use crate::{
     command::{self, Command},
-    object,
+    object, otp,
     response::Response,
 };
 
@@ -14,6 +14,9 @@ pub(crate) struct PutOTPAEADKeyCommand {
     /// Common parameters to all put object commands
     pub params: object::import::Params,
 
+    /// Nonce ID
+    pub nonce_id: otp::NonceId,
+
     /// Serialized object
     pub data: Vec<u8>,
 }
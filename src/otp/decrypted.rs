@@ -0,0 +1,14 @@
+/// Counters and timestamp from a Yubico OTP which was successfully
+/// decrypted (and authenticated) by the HSM
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct DecryptedOtp {
+    /// Non-volatile counter incremented when the YubiKey is powered up
+    pub use_counter: u16,
+
+    /// Volatile counter incremented each time an OTP is generated in a session
+    pub session_counter: u8,
+
+    /// 24-bit timestamp from the YubiKey's 8Hz internal clock (starts at a
+    /// random value each time the YubiKey is powered up)
+    pub timestamp: u32,
+}
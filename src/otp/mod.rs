@@ -1,6 +1,28 @@
 //! Yubico One Time Password (OTP) functionality
+//!
+//! <https://developers.yubico.com/OTP/OTPs_Explained.html>
 
+mod aead;
 mod algorithm;
 pub(crate) mod commands;
+mod decrypted;
+pub mod validation;
 
-pub use self::algorithm::Algorithm;
+pub use self::{
+    aead::{Aead, AEAD_SIZE},
+    algorithm::Algorithm,
+    decrypted::DecryptedOtp,
+};
+
+/// Size of a YubiKey's OTP key (AES-128)
+pub const KEY_SIZE: usize = 16;
+
+/// Size of a YubiKey's private ID
+pub const PRIVATE_ID_SIZE: usize = 6;
+
+/// Size of a (binary, i.e. non-ModHex-encoded) OTP
+pub const OTP_SIZE: usize = 16;
+
+/// Nonce IDs are part of the nonce of every AEAD created under an OTP AEAD
+/// key, and must be unique for every HSM sharing that key
+pub type NonceId = u32;
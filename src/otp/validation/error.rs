@@ -0,0 +1,34 @@
+//! OTP validation errors
+
+use crate::error::Error;
+
+/// OTP validation errors
+pub type ValidationError = Error<ValidationErrorKind>;
+
+/// OTP validation error kinds
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Fail)]
+pub enum ValidationErrorKind {
+    /// OTP is not well-formed (e.g. bad ModHex or length)
+    #[fail(display = "invalid OTP format")]
+    FormatInvalid,
+
+    /// No credential is registered for the OTP's public ID
+    #[fail(display = "unknown public ID")]
+    PublicIdUnknown,
+
+    /// HSM could not authenticate the OTP under the public ID's AEAD
+    #[fail(display = "invalid OTP")]
+    OtpInvalid,
+
+    /// OTP counters are not greater than the last accepted OTP's
+    #[fail(display = "replayed OTP")]
+    Replayed,
+
+    /// Error communicating with the HSM
+    #[fail(display = "HSM error")]
+    HsmError,
+
+    /// Error in the credential store
+    #[fail(display = "store error")]
+    StoreError,
+}
@@ -0,0 +1,163 @@
+//! Yubico OTP validation: decrypts ModHex-encoded OTPs typed by YubiKeys
+//! using AEADs created by the HSM, rejecting replayed OTPs.
+//!
+//! <https://developers.yubico.com/OTP/OTPs_Explained.html>
+
+mod error;
+pub mod modhex;
+mod store;
+
+use self::ValidationErrorKind::{FormatInvalid, HsmError, OtpInvalid, PublicIdUnknown, Replayed};
+pub use self::{
+    error::{ValidationError, ValidationErrorKind},
+    store::{Credential, MemoryStore, Store},
+};
+use crate::{client::Client, device::DeviceErrorKind, otp};
+use std::str::FromStr;
+
+/// Maximum size of a YubiKey's public ID
+pub const PUBLIC_ID_MAX_SIZE: usize = 16;
+
+/// Yubico OTP as typed by a YubiKey: a public ID followed by the encrypted
+/// OTP, both ModHex-encoded
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Otp {
+    /// Public ID of the YubiKey
+    public_id: Vec<u8>,
+
+    /// OTP ciphertext
+    ciphertext: [u8; otp::OTP_SIZE],
+}
+
+impl Otp {
+    /// Get the public ID of the YubiKey which generated this OTP
+    pub fn public_id(&self) -> &[u8] {
+        &self.public_id
+    }
+
+    /// Get the encrypted (binary) OTP
+    pub fn ciphertext(&self) -> &[u8] {
+        &self.ciphertext
+    }
+}
+
+impl FromStr for Otp {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, ValidationError> {
+        let bytes = modhex::decode(s)?;
+
+        ensure!(
+            bytes.len() > otp::OTP_SIZE && bytes.len() <= otp::OTP_SIZE + PUBLIC_ID_MAX_SIZE,
+            FormatInvalid,
+            "bad OTP length: {} (expected {}-{} ModHex characters)",
+            s.len(),
+            (otp::OTP_SIZE + 1) * 2,
+            (otp::OTP_SIZE + PUBLIC_ID_MAX_SIZE) * 2
+        );
+
+        let (public_id, otp_bytes) = bytes.split_at(bytes.len() - otp::OTP_SIZE);
+        let mut ciphertext = [0u8; otp::OTP_SIZE];
+        ciphertext.copy_from_slice(otp_bytes);
+
+        Ok(Self {
+            public_id: public_id.into(),
+            ciphertext,
+        })
+    }
+}
+
+/// Use and session counters of an OTP. OTPs are ordered by use counter, then
+/// session counter, and each accepted OTP must be greater than the last.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Counters {
+    /// Non-volatile counter incremented when the YubiKey is powered up
+    pub use_counter: u16,
+
+    /// Volatile counter incremented each time an OTP is generated in a session
+    pub session_counter: u8,
+}
+
+impl From<otp::DecryptedOtp> for Counters {
+    fn from(decrypted_otp: otp::DecryptedOtp) -> Counters {
+        Counters {
+            use_counter: decrypted_otp.use_counter,
+            session_counter: decrypted_otp.session_counter,
+        }
+    }
+}
+
+/// Validates OTPs using the credentials in a `Store`
+#[derive(Debug)]
+pub struct Validator<S: Store> {
+    /// Credential store
+    store: S,
+}
+
+impl<S: Store> Validator<S> {
+    /// Create a new validator backed by the given credential store
+    pub fn new(store: S) -> Self {
+        Self { store }
+    }
+
+    /// Borrow the credential store
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Mutably borrow the credential store
+    pub fn store_mut(&mut self) -> &mut S {
+        &mut self.store
+    }
+
+    /// Unwrap the credential store
+    pub fn into_store(self) -> S {
+        self.store
+    }
+
+    /// Validate a ModHex-encoded OTP, decrypting it with the given HSM client
+    pub fn validate(
+        &mut self,
+        client: &Client,
+        otp: &str,
+    ) -> Result<otp::DecryptedOtp, ValidationError> {
+        self.validate_otp(client, &otp.parse()?)
+    }
+
+    /// Validate a parsed OTP, decrypting it with the given HSM client
+    pub fn validate_otp(
+        &mut self,
+        client: &Client,
+        otp: &Otp,
+    ) -> Result<otp::DecryptedOtp, ValidationError> {
+        let credential = self.store.get(otp.public_id())?.ok_or_else(|| {
+            err!(
+                PublicIdUnknown,
+                "no credential for public ID: {}",
+                modhex::encode(otp.public_id())
+            )
+        })?;
+
+        let decrypted_otp = client
+            .decrypt_otp(credential.key_id, &credential.aead, otp.ciphertext())
+            .map_err(|e| match e.kind().device_error() {
+                Some(DeviceErrorKind::InvalidOtp) => err!(OtpInvalid, e),
+                _ => err!(HsmError, e),
+            })?;
+
+        let counters = Counters::from(decrypted_otp);
+
+        if let Some(last_counters) = credential.counters {
+            ensure!(
+                counters > last_counters,
+                Replayed,
+                "counters {:?} not greater than last accepted {:?}",
+                counters,
+                last_counters
+            );
+        }
+
+        self.store.update_counters(otp.public_id(), counters)?;
+        Ok(decrypted_otp)
+    }
+}
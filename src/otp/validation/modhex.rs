@@ -0,0 +1,68 @@
+//! ModHex: the keyboard layout-independent hexadecimal encoding used by
+//! YubiKeys when typing OTPs
+//!
+//! <https://developers.yubico.com/yubico-c/Manuals/modhex.1.html>
+
+use super::{ValidationError, ValidationErrorKind::FormatInvalid};
+
+/// ModHex alphabet (replaces `0123456789abcdef`)
+const ALPHABET: &[u8; 16] = b"cbdefghijklnrtuv";
+
+/// Encode bytes as a ModHex string
+pub fn encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len() * 2);
+
+    for byte in bytes {
+        encoded.push(ALPHABET[(byte >> 4) as usize] as char);
+        encoded.push(ALPHABET[(byte & 0xf) as usize] as char);
+    }
+
+    encoded
+}
+
+/// Decode a ModHex string (case-insensitive)
+pub fn decode(encoded: &str) -> Result<Vec<u8>, ValidationError> {
+    ensure!(
+        encoded.len() % 2 == 0,
+        FormatInvalid,
+        "ModHex string has odd length: {}",
+        encoded.len()
+    );
+
+    encoded
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| Ok((decode_char(pair[0])? << 4) | decode_char(pair[1])?))
+        .collect()
+}
+
+/// Decode a single ModHex character
+fn decode_char(c: u8) -> Result<u8, ValidationError> {
+    let c = c.to_ascii_lowercase();
+
+    ALPHABET
+        .iter()
+        .position(|&m| m == c)
+        .map(|pos| pos as u8)
+        .ok_or_else(|| err!(FormatInvalid, "invalid ModHex character: {:?}", c as char))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let bytes = b"\x00\x01\x23\x45\x67\x89\xAB\xCD\xEF\xFF";
+        let encoded = encode(bytes);
+        assert_eq!(encoded, "cccbdefghijklnrtuvvv");
+        assert_eq!(decode(&encoded).unwrap(), bytes);
+        assert_eq!(decode(&encoded.to_uppercase()).unwrap(), bytes);
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(decode("cbd").unwrap_err().kind(), FormatInvalid);
+        assert_eq!(decode("ca").unwrap_err().kind(), FormatInvalid);
+    }
+}
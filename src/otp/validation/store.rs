@@ -0,0 +1,93 @@
+//! Storage for the credentials (i.e. AEADs and counters) of YubiKeys
+//! registered with a `Validator`
+
+use super::{modhex, Counters, ValidationError, ValidationErrorKind::PublicIdUnknown};
+use crate::{object, otp};
+use std::collections::BTreeMap;
+
+/// Credential for a YubiKey, identified by its public ID
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Credential {
+    /// ID of the OTP AEAD key the AEAD was created under
+    pub key_id: object::Id,
+
+    /// AEAD containing the YubiKey's OTP key and private ID
+    pub aead: otp::Aead,
+
+    /// Counters of the last accepted OTP (if any)
+    pub counters: Option<Counters>,
+}
+
+impl Credential {
+    /// Create a new credential for a YubiKey which hasn't been used yet
+    pub fn new(key_id: object::Id, aead: otp::Aead) -> Self {
+        Self {
+            key_id,
+            aead,
+            counters: None,
+        }
+    }
+}
+
+/// Pluggable credential storage (e.g. a database) used by a `Validator`.
+///
+/// If several validators share a store, `update_counters` must be atomic and
+/// fail with `ValidationErrorKind::Replayed` unless the new counters are
+/// greater than the stored ones, or concurrently submitted OTPs may be
+/// accepted more than once.
+pub trait Store {
+    /// Look up the credential for the given public ID
+    fn get(&self, public_id: &[u8]) -> Result<Option<Credential>, ValidationError>;
+
+    /// Record the counters of an accepted OTP
+    fn update_counters(
+        &mut self,
+        public_id: &[u8],
+        counters: Counters,
+    ) -> Result<(), ValidationError>;
+}
+
+/// In-memory credential store
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStore(BTreeMap<Vec<u8>, Credential>);
+
+impl MemoryStore {
+    /// Create a new, empty in-memory store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a credential for the given public ID, replacing any existing one
+    pub fn insert<P: Into<Vec<u8>>>(&mut self, public_id: P, credential: Credential) {
+        self.0.insert(public_id.into(), credential);
+    }
+
+    /// Remove the credential for the given public ID
+    pub fn remove(&mut self, public_id: &[u8]) -> Option<Credential> {
+        self.0.remove(public_id)
+    }
+}
+
+impl Store for MemoryStore {
+    fn get(&self, public_id: &[u8]) -> Result<Option<Credential>, ValidationError> {
+        Ok(self.0.get(public_id).cloned())
+    }
+
+    fn update_counters(
+        &mut self,
+        public_id: &[u8],
+        counters: Counters,
+    ) -> Result<(), ValidationError> {
+        match self.0.get_mut(public_id) {
+            Some(credential) => {
+                credential.counters = Some(counters);
+                Ok(())
+            }
+            None => fail!(
+                PublicIdUnknown,
+                "no credential for public ID: {}",
+                modhex::encode(public_id)
+            ),
+        }
+    }
+}
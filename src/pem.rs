@@ -0,0 +1,67 @@
+//! Minimal RFC 7468 PEM encoder and decoder
+
+use subtle_encoding::base64;
+
+/// Maximum length of a line of base64 in a PEM document
+const LINE_WIDTH: usize = 64;
+
+/// Encode DER data as a PEM document with the given label
+pub(crate) fn encode(label: &str, der: &[u8]) -> String {
+    let encoded = base64::encode(der);
+    let mut pem = format!("-----BEGIN {}-----\n", label);
+
+    for line in encoded.chunks(LINE_WIDTH) {
+        pem.push_str(&String::from_utf8(line.to_vec()).unwrap());
+        pem.push('\n');
+    }
+
+    pem.push_str(&format!("-----END {}-----\n", label));
+    pem
+}
+
+/// Decode a series of PEM documents, returning the label and data of each.
+///
+/// Returns `None` if the input is malformed or contains anything other than
+/// PEM documents (and whitespace).
+pub(crate) fn decode(pem: &str) -> Option<Vec<(String, Vec<u8>)>> {
+    let mut documents = vec![];
+    let mut lines = pem.lines().map(str::trim).filter(|line| !line.is_empty());
+
+    while let Some(line) = lines.next() {
+        let label = strip_delimiters(line, "-----BEGIN ")?;
+        let mut encoded = String::new();
+
+        loop {
+            let line = lines.next()?;
+
+            if let Some(end_label) = strip_delimiters(line, "-----END ") {
+                if end_label != label {
+                    return None;
+                }
+
+                break;
+            }
+
+            encoded.push_str(line);
+        }
+
+        let der = base64::decode(encoded.as_bytes()).ok()?;
+        documents.push((label.to_owned(), der));
+    }
+
+    if documents.is_empty() {
+        None
+    } else {
+        Some(documents)
+    }
+}
+
+/// Extract the label from a `-----BEGIN <label>-----` or
+/// `-----END <label>-----` line
+fn strip_delimiters<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+    if line.starts_with(prefix) && line.ends_with("-----") && line.len() >= prefix.len() + 5 {
+        Some(&line[prefix.len()..line.len() - 5])
+    } else {
+        None
+    }
+}
@@ -6,7 +6,7 @@ use serde::{
 };
 
 /// Codes associated with HSM responses
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub enum Code {
     /// Successful response for the given command type
     Success(command::Code),
@@ -1,6 +1,9 @@
 //! RSA-related algorithms
 
+use super::mgf;
 use crate::algorithm::{AlgorithmError, AlgorithmErrorKind::TagInvalid};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha384, Sha512};
 
 /// RSA algorithms (signing and encryption)
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -45,7 +48,7 @@ pub enum Algorithm {
 }
 
 impl Algorithm {
-    /// Convert an unsigned byte tag into an `Algorithmorithm` (if valid)
+    /// Convert an unsigned byte tag into an `Algorithm` (if valid)
     pub fn from_u8(tag: u8) -> Result<Self, AlgorithmError> {
         Ok(match tag {
             0x01 => Algorithm::PKCS1_SHA1,
@@ -68,6 +71,77 @@ impl Algorithm {
     pub fn to_u8(self) -> u8 {
         self as u8
     }
+
+    /// Is this an RSASSA-PKCS#1v1.5 algorithm?
+    pub fn is_pkcs1(self) -> bool {
+        match self {
+            Algorithm::PKCS1_SHA1
+            | Algorithm::PKCS1_SHA256
+            | Algorithm::PKCS1_SHA384
+            | Algorithm::PKCS1_SHA512 => true,
+            _ => false,
+        }
+    }
+
+    /// Is this an RSASSA-PSS algorithm?
+    pub fn is_pss(self) -> bool {
+        match self {
+            Algorithm::PSS_SHA1
+            | Algorithm::PSS_SHA256
+            | Algorithm::PSS_SHA384
+            | Algorithm::PSS_SHA512 => true,
+            _ => false,
+        }
+    }
+
+    /// Is this an RSA-OAEP algorithm?
+    pub fn is_oaep(self) -> bool {
+        match self {
+            Algorithm::OAEP_SHA1
+            | Algorithm::OAEP_SHA256
+            | Algorithm::OAEP_SHA384
+            | Algorithm::OAEP_SHA512 => true,
+            _ => false,
+        }
+    }
+
+    /// Get the MGF1 algorithm which uses the same hash function as this algorithm
+    pub fn mgf1_hash_alg(self) -> mgf::Algorithm {
+        match self {
+            Algorithm::PKCS1_SHA1 | Algorithm::PSS_SHA1 | Algorithm::OAEP_SHA1 => {
+                mgf::Algorithm::SHA1
+            }
+            Algorithm::PKCS1_SHA256 | Algorithm::PSS_SHA256 | Algorithm::OAEP_SHA256 => {
+                mgf::Algorithm::SHA256
+            }
+            Algorithm::PKCS1_SHA384 | Algorithm::PSS_SHA384 | Algorithm::OAEP_SHA384 => {
+                mgf::Algorithm::SHA384
+            }
+            Algorithm::PKCS1_SHA512 | Algorithm::PSS_SHA512 | Algorithm::OAEP_SHA512 => {
+                mgf::Algorithm::SHA512
+            }
+        }
+    }
+
+    /// Get the size of this algorithm's digest in bytes
+    pub fn digest_len(self) -> usize {
+        match self.mgf1_hash_alg() {
+            mgf::Algorithm::SHA1 => 20,
+            mgf::Algorithm::SHA256 => 32,
+            mgf::Algorithm::SHA384 => 48,
+            mgf::Algorithm::SHA512 => 64,
+        }
+    }
+
+    /// Compute the digest of the given message using this algorithm's hash function
+    pub fn digest(self, msg: &[u8]) -> Vec<u8> {
+        match self.mgf1_hash_alg() {
+            mgf::Algorithm::SHA1 => Sha1::digest(msg).as_slice().into(),
+            mgf::Algorithm::SHA256 => Sha256::digest(msg).as_slice().into(),
+            mgf::Algorithm::SHA384 => Sha384::digest(msg).as_slice().into(),
+            mgf::Algorithm::SHA512 => Sha512::digest(msg).as_slice().into(),
+        }
+    }
 }
 
 impl_algorithm_serializers!(Algorithm);
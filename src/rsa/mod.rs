@@ -1,18 +1,14 @@
 //! RSA (Rivest–Shamir–Adleman) asymmetric cryptosystem support
 //! (signing/encryption).
-//!
-//! NOTE: This functionality has not been properly tested and is therefore
-//! not enabled by default! Enable the `rsa-preview` cargo feature if you would
-//! like to use it (please report success or bugs!)
-
-// TODO(tarcieri): finalize and test RSA support
 
 mod algorithm;
 pub mod mgf;
-
-#[cfg(feature = "rsa-preview")]
+pub mod oaep;
 pub mod pkcs1;
-#[cfg(feature = "rsa-preview")]
 pub mod pss;
+mod public_key;
 
-pub use self::algorithm::*;
+pub use self::{
+    algorithm::*,
+    public_key::{PublicKey, PUBLIC_EXPONENT},
+};
@@ -0,0 +1,33 @@
+//! RSAES-OAEP commands
+
+use crate::{
+    command::{self, Command},
+    object,
+    response::Response,
+    rsa,
+};
+
+/// Request parameters for `command::decrypt_rsa_oaep`
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct DecryptOaepCommand {
+    /// ID of the key to perform the decryption with
+    pub key_id: object::Id,
+
+    /// Hash algorithm to use for MGF1
+    pub mgf1_hash_alg: rsa::mgf::Algorithm,
+
+    /// Ciphertext followed by the digest of the OAEP label
+    pub data: Vec<u8>,
+}
+
+impl Command for DecryptOaepCommand {
+    type ResponseType = DecryptOaepResponse;
+}
+
+/// Plaintext recovered from an RSAES-OAEP ciphertext
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct DecryptOaepResponse(pub(crate) Vec<u8>);
+
+impl Response for DecryptOaepResponse {
+    const COMMAND_CODE: command::Code = command::Code::DecryptOaep;
+}
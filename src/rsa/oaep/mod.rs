@@ -0,0 +1,26 @@
+//! RSA Optimal Asymmetric Encryption Padding (OAEP) decryption
+//!
+//! <https://en.wikipedia.org/wiki/Optimal_asymmetric_encryption_padding>
+
+pub(crate) mod commands;
+
+use crate::{
+    algorithm::{AlgorithmError, AlgorithmErrorKind::TagInvalid},
+    rsa,
+};
+
+/// Compute the digest of an OAEP label using the hash function of the given
+/// RSA-OAEP algorithm (i.e. `rsa::Algorithm::OAEP_*`).
+///
+/// The resulting digest is passed to `Client::decrypt_rsa_oaep`. Note that
+/// messages encrypted without a label use the digest of the empty string.
+pub fn hash_label(algorithm: rsa::Algorithm, label: &[u8]) -> Result<Vec<u8>, AlgorithmError> {
+    ensure!(
+        algorithm.is_oaep(),
+        TagInvalid,
+        "not an RSA-OAEP algorithm: {:?}",
+        algorithm
+    );
+
+    Ok(algorithm.digest(label))
+}
@@ -1,4 +1,4 @@
-//! RSASSA-PKCS#1v1.5 commands
+//! RSA PKCS#1v1.5 commands (RSASSA-PKCS#1v1.5 and RSAES-PKCS#1v1.5)
 
 use crate::{
     command::{self, Command},
@@ -23,7 +23,7 @@ impl Command for SignPkcs1Command {
 
 /// RSASSA-PKCS#1v1.5 signatures (ASN.1 DER encoded)
 #[derive(Serialize, Deserialize, Debug)]
-pub struct SignPkcs1Response(rsa::pkcs1::Signature);
+pub(crate) struct SignPkcs1Response(pub(crate) rsa::pkcs1::Signature);
 
 impl Response for SignPkcs1Response {
     const COMMAND_CODE: command::Code = command::Code::SignPkcs1;
@@ -34,3 +34,25 @@ impl From<SignPkcs1Response> for rsa::pkcs1::Signature {
         response.0
     }
 }
+
+/// Request parameters for `command::decrypt_rsa_pkcs1v15`
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct DecryptPkcs1Command {
+    /// ID of the key to perform the decryption with
+    pub key_id: object::Id,
+
+    /// Data to be decrypted
+    pub data: Vec<u8>,
+}
+
+impl Command for DecryptPkcs1Command {
+    type ResponseType = DecryptPkcs1Response;
+}
+
+/// Plaintext recovered from an RSAES-PKCS#1v1.5 ciphertext
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct DecryptPkcs1Response(pub(crate) Vec<u8>);
+
+impl Response for DecryptPkcs1Response {
+    const COMMAND_CODE: command::Code = command::Code::DecryptPkcs1;
+}
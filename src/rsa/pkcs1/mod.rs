@@ -1,9 +1,10 @@
-//! RSASSA-PKCS#1v1.5 commands
+//! RSA PKCS#1v1.5: RSASSA-PKCS#1v1.5 signatures and RSAES-PKCS#1v1.5 decryption
 //!
 //! Note: This is a legacy algorithm. Greenfield projects should consider
 //! non-RSA algorithms like Ed25519 or ECDSA, or RSA-PSS if RSA is required.
 
 pub(crate) mod commands;
 mod signature;
+mod signer;
 
-pub use self::signature::Signature;
+pub use self::{signature::Signature, signer::Signer};
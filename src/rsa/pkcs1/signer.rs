@@ -0,0 +1,84 @@
+//! RSASSA-PKCS#1v1.5 provider for `YubiHSM 2` devices
+
+use super::Signature;
+use crate::{object, rsa, Client};
+use sha2::{Sha256, Sha384, Sha512};
+use signatory::{
+    error::{Error, ErrorKind::*},
+    Digest, DigestSigner, PublicKeyed,
+};
+
+/// RSASSA-PKCS#1v1.5 signature provider for yubihsm-client
+pub struct Signer {
+    /// YubiHSM client
+    client: Client,
+
+    /// ID of an RSA key to perform signatures with
+    signing_key_id: object::Id,
+}
+
+impl Signer {
+    /// Create a new YubiHSM-backed RSASSA-PKCS#1v1.5 signer
+    pub fn create(client: Client, signing_key_id: object::Id) -> Result<Self, Error> {
+        let signer = Self {
+            client,
+            signing_key_id,
+        };
+
+        // Ensure the signing_key_id slot contains a valid RSA public key
+        signer.public_key()?;
+
+        Ok(signer)
+    }
+
+    /// Compute an RSASSA-PKCS#1v1.5 signature of the given digest
+    fn sign_digest<D: Digest>(
+        &self,
+        algorithm: rsa::Algorithm,
+        digest: D,
+    ) -> Result<Signature, Error> {
+        Ok(self.client.sign_rsa_pkcs1v15_prehashed(
+            self.signing_key_id,
+            algorithm,
+            digest.result().as_slice(),
+        )?)
+    }
+}
+
+impl PublicKeyed<rsa::PublicKey> for Signer {
+    /// Obtain the public key which identifies this signer
+    fn public_key(&self) -> Result<rsa::PublicKey, Error> {
+        let public_key = self.client.get_public_key(self.signing_key_id)?;
+
+        public_key.rsa().ok_or_else(|| {
+            Error::new(
+                KeyInvalid,
+                Some(&format!(
+                    "expected an RSA key, got: {:?}",
+                    public_key.algorithm
+                )),
+            )
+        })
+    }
+}
+
+impl DigestSigner<Sha256, Signature> for Signer {
+    /// Compute an RSASSA-PKCS#1v1.5 signature of the given SHA-256 digest
+    fn sign(&self, digest: Sha256) -> Result<Signature, Error> {
+        self.sign_digest(rsa::Algorithm::PKCS1_SHA256, digest)
+    }
+}
+
+impl DigestSigner<Sha384, Signature> for Signer {
+    /// Compute an RSASSA-PKCS#1v1.5 signature of the given SHA-384 digest
+    fn sign(&self, digest: Sha384) -> Result<Signature, Error> {
+        self.sign_digest(rsa::Algorithm::PKCS1_SHA384, digest)
+    }
+}
+
+impl DigestSigner<Sha512, Signature> for Signer {
+    /// Compute an RSASSA-PKCS#1v1.5 signature of the given SHA-512 digest
+    fn sign(&self, digest: Sha512) -> Result<Signature, Error> {
+        self.sign_digest(rsa::Algorithm::PKCS1_SHA512, digest)
+    }
+}
@@ -3,8 +3,6 @@
 
 pub(crate) mod commands;
 mod signature;
+mod signer;
 
-/// Maximum message size supported for RSASSA-PSS
-pub const MAX_MESSAGE_SIZE: usize = 0xFFFF;
-
-pub use self::signature::Signature;
+pub use self::{signature::Signature, signer::Signer};
@@ -28,6 +28,12 @@ impl AsRef<[u8]> for Signature {
     }
 }
 
+impl signatory::Signature for Signature {
+    fn from_bytes<B: AsRef<[u8]>>(bytes: B) -> Result<Self, signatory::Error> {
+        Ok(Signature(bytes.as_ref().into()))
+    }
+}
+
 impl Into<Vec<u8>> for Signature {
     fn into(self) -> Vec<u8> {
         self.0
@@ -0,0 +1,94 @@
+//! RSA public keys
+
+use crate::asymmetric;
+use ring::signature::{primitive::verify_rsa, RsaParameters};
+
+/// Public exponent used by all RSA keys generated or imported by the YubiHSM 2
+pub const PUBLIC_EXPONENT: u32 = 65_537;
+
+/// RSA public key: a public modulus (as returned by `Client::get_public_key`)
+/// and the YubiHSM 2's fixed public exponent
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct PublicKey {
+    /// RSA key algorithm (i.e. key size)
+    algorithm: asymmetric::Algorithm,
+
+    /// Public modulus (big endian)
+    modulus: Vec<u8>,
+}
+
+impl PublicKey {
+    /// Create an RSA public key from its modulus, returning `None` if the
+    /// modulus isn't 2048, 3072, or 4096 bits
+    pub fn from_modulus(modulus: &[u8]) -> Option<Self> {
+        let algorithm = match modulus.len() {
+            256 => asymmetric::Algorithm::RSA_2048,
+            384 => asymmetric::Algorithm::RSA_3072,
+            512 => asymmetric::Algorithm::RSA_4096,
+            _ => return None,
+        };
+
+        // Moduli must be full-size (i.e. have their high bit set)
+        if modulus[0] & 0x80 == 0 {
+            return None;
+        }
+
+        Some(Self {
+            algorithm,
+            modulus: modulus.into(),
+        })
+    }
+
+    /// Get the algorithm (i.e. key size) of this key
+    pub fn algorithm(&self) -> asymmetric::Algorithm {
+        self.algorithm
+    }
+
+    /// Get the public modulus as big endian bytes
+    pub fn modulus(&self) -> &[u8] {
+        &self.modulus
+    }
+
+    /// Get the public exponent
+    pub fn exponent(&self) -> u32 {
+        PUBLIC_EXPONENT
+    }
+
+    /// Get the public exponent as (minimal) big endian bytes
+    pub fn exponent_bytes(&self) -> &'static [u8] {
+        &[0x01, 0x00, 0x01]
+    }
+
+    /// Get the size of the modulus in bits
+    pub fn bits(&self) -> usize {
+        self.modulus.len() * 8
+    }
+
+    /// Verify a signature of the given message under this key using *ring*
+    /// (e.g. `RSA_PKCS1_2048_8192_SHA256` for RSASSA-PKCS#1v1.5 with SHA-256)
+    pub(crate) fn verify(
+        &self,
+        algorithm: &RsaParameters,
+        message: &[u8],
+        signature: &[u8],
+    ) -> bool {
+        verify_rsa(
+            algorithm,
+            (
+                untrusted::Input::from(&self.modulus),
+                untrusted::Input::from(self.exponent_bytes()),
+            ),
+            untrusted::Input::from(message),
+            untrusted::Input::from(signature),
+        )
+        .is_ok()
+    }
+}
+
+impl AsRef<[u8]> for PublicKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.modulus
+    }
+}
+
+impl signatory::PublicKey for PublicKey {}
@@ -0,0 +1,84 @@
+//! Asymmetric keys generated within the YubiHSM 2 during provisioning
+
+use super::config::AsymmetricKeyConfig;
+use crate::{asymmetric, object, Capability, Client, Domain};
+use failure::Error;
+use serde::de::{self, Deserialize, Deserializer};
+
+/// Asymmetric key to generate within the device
+#[derive(Clone, Debug)]
+pub struct AsymmetricKey {
+    /// Object ID of the key
+    pub(super) key_id: object::Id,
+
+    /// Label to place on the key
+    pub(super) label: object::Label,
+
+    /// Algorithm of the key
+    pub(super) algorithm: asymmetric::Algorithm,
+
+    /// Capabilities of the key
+    pub(super) capabilities: Capability,
+
+    /// Domains the key is accessible from
+    pub(super) domains: Domain,
+}
+
+impl AsymmetricKey {
+    /// Create a new asymmetric key with the given object ID and algorithm
+    pub fn new(key_id: object::Id, algorithm: asymmetric::Algorithm) -> Self {
+        Self {
+            key_id,
+            label: Default::default(),
+            algorithm,
+            capabilities: Capability::empty(),
+            domains: Domain::empty(),
+        }
+    }
+
+    /// Set the label for this key
+    pub fn label<L>(mut self, label: L) -> Self
+    where
+        L: Into<object::Label>,
+    {
+        self.label = label.into();
+        self
+    }
+
+    /// Set the capabilities of this key
+    pub fn capabilities(mut self, capabilities: Capability) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Set the domains this key is accessible from
+    pub fn domains(mut self, domains: Domain) -> Self {
+        self.domains = domains;
+        self
+    }
+
+    /// Generate this key within the YubiHSM 2 device, returning its public key
+    pub fn create(&self, client: &mut Client) -> Result<asymmetric::PublicKey, Error> {
+        client
+            .generate_asymmetric_key(
+                self.key_id,
+                self.label.clone(),
+                self.domains,
+                self.capabilities,
+                self.algorithm,
+            )
+            .map_err(|e| format_err!("error generating asymmetric key: {}", e))?;
+
+        client
+            .get_public_key(self.key_id)
+            .map_err(|e| format_err!("error getting public key: {}", e))
+    }
+}
+
+impl<'de> Deserialize<'de> for AsymmetricKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        AsymmetricKeyConfig::deserialize(deserializer)?
+            .into_asymmetric_key()
+            .map_err(de::Error::custom)
+    }
+}
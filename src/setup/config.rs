@@ -0,0 +1,618 @@
+//! Declarative configuration for provisioning profiles, e.g. loaded from a
+//! TOML or JSON file reviewed by an operations team.
+//!
+//! Configuration files never contain secrets inline: role passwords are read
+//! from a file or environment variable, and wrap keys are read from a
+//! (hex-encoded) key file. Wrap keys are never generated from a configuration,
+//! as the same key must be available to every HSM backups are restored into.
+//!
+//! Capabilities are given by name (e.g. `sign-eddsa`, or `all`), and domains
+//! by number (e.g. `1` or `dom1`, or `all`). For example, in TOML:
+//!
+//! ```toml
+//! audit_option = "on"
+//! report_object_id = 65534
+//!
+//! [[roles]]
+//! authentication_key_id = 2
+//! authentication_key_label = "signer"
+//! password_env = "YUBIHSM_SIGNER_PASSWORD"
+//! capabilities = ["sign-eddsa"]
+//! domains = ["1"]
+//!
+//! [[wrap_keys]]
+//! id = 3
+//! label = "backup key"
+//! key_file = "/etc/yubihsm/backup-wrap-key.hex"
+//! capabilities = ["export-wrapped", "import-wrapped"]
+//! delegated_capabilities = ["all"]
+//! domains = ["all"]
+//!
+//! [[asymmetric_keys]]
+//! key_id = 100
+//! label = "validator key"
+//! algorithm = "ed25519"
+//! capabilities = ["sign-eddsa", "exportable-under-wrap"]
+//! domains = ["1"]
+//!
+//! [[hmac_keys]]
+//! key_id = 101
+//! algorithm = "hmac-sha256"
+//! key_file = "/etc/yubihsm/hmac-key.hex" # omit to generate the key
+//! capabilities = ["sign-hmac", "verify-hmac"]
+//! domains = ["1"]
+//!
+//! [[opaque_objects]]
+//! object_id = 100
+//! algorithm = "x509-certificate"
+//! file = "/etc/yubihsm/validator-cert.pem"
+//! capabilities = ["get-opaque"]
+//! domains = ["1"]
+//!
+//! [command_audit_options]
+//! sign-eddsa = "fix"
+//! ```
+
+use super::{
+    profile::{DEFAULT_REPORT_OBJECT_ID, DEFAULT_SETUP_KEY_ID},
+    AsymmetricKey, HmacKey, Opaque, Profile, Role,
+};
+use crate::{
+    asymmetric, command, hmac, object, opaque, pem, wrap, AuditOption, Capability, Credentials,
+    Domain,
+};
+use failure::Error;
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    str,
+    time::Duration,
+};
+use subtle_encoding::hex;
+use zeroize::Zeroize;
+
+/// Default number of seconds to wait for a device reset
+const DEFAULT_RESET_DEVICE_TIMEOUT_SECS: u64 = 10;
+
+/// Configuration for a `Profile`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct ProfileConfig {
+    /// Key ID to use for provisioning the device
+    #[serde(default = "default_setup_auth_key_id")]
+    setup_auth_key_id: Option<object::Id>,
+
+    /// Should the setup auth key be deleted when provisioning is complete?
+    #[serde(default = "default_delete_setup_auth_key")]
+    delete_setup_auth_key: bool,
+
+    /// Auditing mode to configure the device with (`off`, `on`, or `fix`)
+    #[serde(default)]
+    audit_option: Option<String>,
+
+    /// Roles to create on the new device
+    #[serde(default)]
+    roles: Vec<Role>,
+
+    /// Wrap keys to provision the device with
+    #[serde(default)]
+    wrap_keys: Vec<WrapKeyConfig>,
+
+    /// Asymmetric keys to generate within the device
+    #[serde(default)]
+    asymmetric_keys: Vec<AsymmetricKey>,
+
+    /// HMAC keys to generate within or import into the device
+    #[serde(default)]
+    hmac_keys: Vec<HmacKey>,
+
+    /// Opaque objects (e.g. certificates) to store in the device
+    #[serde(default)]
+    opaque_objects: Vec<Opaque>,
+
+    /// Per-command auditing modes, keyed by command name (e.g. `sign-eddsa`)
+    #[serde(default)]
+    command_audit_options: BTreeMap<String, String>,
+
+    /// Opaque object slot to store the provisioning report in
+    #[serde(default = "default_report_object_id")]
+    report_object_id: Option<object::Id>,
+
+    /// Current credentials of roles whose authentication keys should be
+    /// changed to the configured ones when reconciling a device
+    #[serde(default)]
+    rotate_credentials: Vec<CredentialsConfig>,
+
+    /// Delete and recreate objects which differ from the profile when
+    /// reconciling a device
+    #[serde(default)]
+    replace_conflicting: bool,
+
+    /// How long to wait for the device to reset before giving up (in seconds)
+    #[serde(default = "default_reset_device_timeout")]
+    reset_device_timeout: u64,
+}
+
+impl ProfileConfig {
+    /// Resolve this configuration into a `Profile`, reading or generating
+    /// any wrap keys it references
+    pub(super) fn into_profile(self) -> Result<Profile, Error> {
+        let audit_option = match self.audit_option {
+            Some(ref option) => parse_audit_option(option)?,
+            None => AuditOption::Off,
+        };
+
+        let mut wrap_keys = vec![];
+
+        for config in self.wrap_keys {
+            wrap_keys.push(config.into_wrap_key()?);
+        }
+
+        let mut command_audit_options = vec![];
+
+        for (name, option) in &self.command_audit_options {
+            command_audit_options
+                .push((name.parse::<command::Code>()?, parse_audit_option(option)?));
+        }
+
+        let mut rotate_credentials = vec![];
+
+        for config in self.rotate_credentials {
+            rotate_credentials.push(config.into_credentials()?);
+        }
+
+        Ok(Profile {
+            setup_auth_key_id: self.setup_auth_key_id,
+            delete_setup_auth_key: self.delete_setup_auth_key,
+            audit_option,
+            roles: self.roles,
+            wrap_keys,
+            asymmetric_keys: self.asymmetric_keys,
+            hmac_keys: self.hmac_keys,
+            opaque_objects: self.opaque_objects,
+            command_audit_options,
+            report_object_id: self.report_object_id,
+            rotate_credentials,
+            replace_conflicting: self.replace_conflicting,
+            reset_device_timeout: Duration::from_secs(self.reset_device_timeout),
+        })
+    }
+}
+
+/// Configuration for a `Role`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct RoleConfig {
+    /// Object ID of the role's authentication key
+    authentication_key_id: object::Id,
+
+    /// Label to place on the role's authentication key
+    #[serde(default)]
+    authentication_key_label: String,
+
+    /// File containing the role's password
+    #[serde(default)]
+    password_file: Option<PathBuf>,
+
+    /// Environment variable containing the role's password
+    #[serde(default)]
+    password_env: Option<String>,
+
+    /// Names of the role's capabilities
+    #[serde(default)]
+    capabilities: Vec<String>,
+
+    /// Names of the capabilities the role can delegate
+    #[serde(default)]
+    delegated_capabilities: Vec<String>,
+
+    /// Domains the role has access to
+    #[serde(default)]
+    domains: Vec<String>,
+}
+
+impl RoleConfig {
+    /// Resolve this configuration into a `Role`, reading its password
+    pub(super) fn into_role(self) -> Result<Role, Error> {
+        let credentials = read_credentials(
+            self.authentication_key_id,
+            self.password_file,
+            self.password_env,
+        )?;
+
+        Ok(Role::new(credentials)
+            .authentication_key_label(self.authentication_key_label.as_str())
+            .capabilities(parse_capabilities(&self.capabilities)?)
+            .delegated_capabilities(parse_capabilities(&self.delegated_capabilities)?)
+            .domains(parse_domains(&self.domains)?))
+    }
+}
+
+/// Configuration for the current `Credentials` of a role being rotated
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CredentialsConfig {
+    /// Object ID of the role's authentication key
+    authentication_key_id: object::Id,
+
+    /// File containing the role's current password
+    #[serde(default)]
+    password_file: Option<PathBuf>,
+
+    /// Environment variable containing the role's current password
+    #[serde(default)]
+    password_env: Option<String>,
+}
+
+impl CredentialsConfig {
+    /// Resolve this configuration into `Credentials`, reading the password
+    fn into_credentials(self) -> Result<Credentials, Error> {
+        read_credentials(
+            self.authentication_key_id,
+            self.password_file,
+            self.password_env,
+        )
+    }
+}
+
+/// Configuration for a `wrap::Key`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WrapKeyConfig {
+    /// Object ID of the wrap key
+    id: object::Id,
+
+    /// Label to place on the wrap key
+    #[serde(default)]
+    label: String,
+
+    /// File containing the hex-encoded wrap key
+    key_file: PathBuf,
+
+    /// Names of the wrap key's capabilities
+    #[serde(default)]
+    capabilities: Vec<String>,
+
+    /// Names of the capabilities of objects imported under the wrap key
+    #[serde(default)]
+    delegated_capabilities: Vec<String>,
+
+    /// Domains the wrap key is accessible from
+    #[serde(default)]
+    domains: Vec<String>,
+}
+
+impl WrapKeyConfig {
+    /// Resolve this configuration into a `wrap::Key`
+    fn into_wrap_key(self) -> Result<wrap::Key, Error> {
+        let mut bytes = read_key_file(&self.key_file)?;
+        let key = wrap::Key::from_bytes(self.id, &bytes);
+        bytes.zeroize();
+        let key = key?;
+
+        Ok(key
+            .label(self.label.as_str().into())
+            .capabilities(parse_capabilities(&self.capabilities)?)
+            .delegated_capabilities(parse_capabilities(&self.delegated_capabilities)?)
+            .domains(parse_domains(&self.domains)?))
+    }
+}
+
+/// Configuration for an `AsymmetricKey`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct AsymmetricKeyConfig {
+    /// Object ID of the key
+    key_id: object::Id,
+
+    /// Label to place on the key
+    #[serde(default)]
+    label: String,
+
+    /// Algorithm of the key (e.g. `ed25519` or `ecp256`)
+    algorithm: String,
+
+    /// Names of the key's capabilities
+    #[serde(default)]
+    capabilities: Vec<String>,
+
+    /// Domains the key is accessible from
+    #[serde(default)]
+    domains: Vec<String>,
+}
+
+impl AsymmetricKeyConfig {
+    /// Resolve this configuration into an `AsymmetricKey`
+    pub(super) fn into_asymmetric_key(self) -> Result<AsymmetricKey, Error> {
+        Ok(
+            AsymmetricKey::new(self.key_id, parse_asymmetric_algorithm(&self.algorithm)?)
+                .label(self.label.as_str())
+                .capabilities(parse_capabilities(&self.capabilities)?)
+                .domains(parse_domains(&self.domains)?),
+        )
+    }
+}
+
+/// Configuration for an `HmacKey`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct HmacKeyConfig {
+    /// Object ID of the key
+    key_id: object::Id,
+
+    /// Label to place on the key
+    #[serde(default)]
+    label: String,
+
+    /// Algorithm of the key (e.g. `hmac-sha256`)
+    algorithm: String,
+
+    /// File containing the hex-encoded key to import. If omitted, the key is
+    /// generated within the device.
+    #[serde(default)]
+    key_file: Option<PathBuf>,
+
+    /// Names of the key's capabilities
+    #[serde(default)]
+    capabilities: Vec<String>,
+
+    /// Domains the key is accessible from
+    #[serde(default)]
+    domains: Vec<String>,
+}
+
+impl HmacKeyConfig {
+    /// Resolve this configuration into an `HmacKey`, reading its key file
+    pub(super) fn into_hmac_key(self) -> Result<HmacKey, Error> {
+        let algorithm = parse_hmac_algorithm(&self.algorithm)?;
+
+        let key = match self.key_file {
+            Some(ref path) => HmacKey::import(self.key_id, algorithm, read_key_file(path)?),
+            None => HmacKey::generate(self.key_id, algorithm),
+        };
+
+        Ok(key
+            .label(self.label.as_str())
+            .capabilities(parse_capabilities(&self.capabilities)?)
+            .domains(parse_domains(&self.domains)?))
+    }
+}
+
+/// Configuration for an `Opaque` object
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct OpaqueConfig {
+    /// Object ID of the opaque object
+    object_id: object::Id,
+
+    /// Label to place on the object
+    #[serde(default)]
+    label: String,
+
+    /// Type of data in the object (`data` or `x509-certificate`)
+    #[serde(default)]
+    algorithm: Option<String>,
+
+    /// File containing the object's data. Certificates may be DER or PEM
+    /// encoded.
+    file: PathBuf,
+
+    /// Names of the object's capabilities
+    #[serde(default)]
+    capabilities: Vec<String>,
+
+    /// Domains the object is accessible from
+    #[serde(default)]
+    domains: Vec<String>,
+}
+
+impl OpaqueConfig {
+    /// Resolve this configuration into an `Opaque` object, reading its file
+    pub(super) fn into_opaque(self) -> Result<Opaque, Error> {
+        let algorithm = match self.algorithm {
+            Some(ref name) => parse_opaque_algorithm(name)?,
+            None => opaque::Algorithm::DATA,
+        };
+
+        let mut data = fs::read(&self.file)
+            .map_err(|e| format_err!("error reading {}: {}", self.file.display(), e))?;
+
+        if algorithm == opaque::Algorithm::X509_CERTIFICATE {
+            data = decode_certificate(data)
+                .map_err(|e| format_err!("error reading {}: {}", self.file.display(), e))?;
+        }
+
+        Ok(Opaque::new(self.object_id, algorithm, data)
+            .label(self.label.as_str())
+            .capabilities(parse_capabilities(&self.capabilities)?)
+            .domains(parse_domains(&self.domains)?))
+    }
+}
+
+/// Derive credentials from a password read from exactly one of a file or an
+/// environment variable
+fn read_credentials(
+    authentication_key_id: object::Id,
+    password_file: Option<PathBuf>,
+    password_env: Option<String>,
+) -> Result<Credentials, Error> {
+    let mut password = match (password_file, password_env) {
+        (Some(path), None) => {
+            let mut password = fs::read(&path).map_err(|e| {
+                format_err!("error reading password file {}: {}", path.display(), e)
+            })?;
+
+            // Ignore a trailing newline
+            while password.last() == Some(&b'\n') || password.last() == Some(&b'\r') {
+                password.pop();
+            }
+
+            password
+        }
+        (None, Some(var)) => env::var(&var)
+            .map_err(|e| format_err!("error reading password from ${}: {}", var, e))?
+            .into_bytes(),
+        _ => bail!(
+            "role {} must have exactly one of password_file or password_env",
+            authentication_key_id
+        ),
+    };
+
+    let credentials = Credentials::from_password(authentication_key_id, &password);
+    password.zeroize();
+
+    Ok(credentials)
+}
+
+/// Read a hex-encoded key from a file
+fn read_key_file(path: &Path) -> Result<Vec<u8>, Error> {
+    let mut encoded = fs::read(path)
+        .map_err(|e| format_err!("error reading key file {}: {}", path.display(), e))?;
+
+    let decoded = hex::decode(trim_whitespace(&encoded));
+    encoded.zeroize();
+
+    decoded.map_err(|e| format_err!("error decoding key file {}: {}", path.display(), e))
+}
+
+/// Decode a certificate which is either DER encoded or a single PEM encoded
+/// `CERTIFICATE`
+fn decode_certificate(data: Vec<u8>) -> Result<Vec<u8>, Error> {
+    let documents = match str::from_utf8(&data).ok().and_then(pem::decode) {
+        Some(documents) => documents,
+        None => return Ok(data),
+    };
+
+    match documents.as_slice() {
+        [(label, der)] if label == "CERTIFICATE" => Ok(der.clone()),
+        _ => bail!("expected a single PEM encoded CERTIFICATE"),
+    }
+}
+
+/// Parse capability names, where `all` means all capabilities
+fn parse_capabilities(names: &[String]) -> Result<Capability, Error> {
+    let mut capabilities = Capability::empty();
+
+    for name in names {
+        capabilities |= match name.as_str() {
+            "all" => Capability::all(),
+            other => other
+                .parse()
+                .map_err(|_| format_err!("unknown capability: {}", other))?,
+        };
+    }
+
+    Ok(capabilities)
+}
+
+/// Parse domain names (e.g. `1` or `dom1`), where `all` means all domains
+fn parse_domains(names: &[String]) -> Result<Domain, Error> {
+    let mut domains = Domain::empty();
+
+    for name in names {
+        let name = name.to_lowercase();
+
+        domains |= if name == "all" {
+            Domain::all()
+        } else {
+            let index = if name.starts_with("dom") {
+                &name["dom".len()..]
+            } else {
+                name.as_str()
+            };
+
+            index
+                .parse()
+                .map_err(|_| format_err!("invalid domain: {}", name))
+                .and_then(Domain::at)?
+        };
+    }
+
+    Ok(domains)
+}
+
+/// Parse an audit option name
+fn parse_audit_option(name: &str) -> Result<AuditOption, Error> {
+    Ok(match name {
+        "off" => AuditOption::Off,
+        "on" => AuditOption::On,
+        "fix" => AuditOption::Fix,
+        other => bail!("invalid audit option: {} (expected off, on, or fix)", other),
+    })
+}
+
+/// Parse an asymmetric algorithm name
+fn parse_asymmetric_algorithm(name: &str) -> Result<asymmetric::Algorithm, Error> {
+    Ok(match name {
+        "rsa2048" => asymmetric::Algorithm::RSA_2048,
+        "rsa3072" => asymmetric::Algorithm::RSA_3072,
+        "rsa4096" => asymmetric::Algorithm::RSA_4096,
+        "ed25519" => asymmetric::Algorithm::Ed25519,
+        "ecp224" => asymmetric::Algorithm::EC_P224,
+        "ecp256" => asymmetric::Algorithm::EC_P256,
+        "ecp384" => asymmetric::Algorithm::EC_P384,
+        "ecp521" => asymmetric::Algorithm::EC_P521,
+        "eck256" => asymmetric::Algorithm::EC_K256,
+        "ecbp256" => asymmetric::Algorithm::EC_BP256,
+        "ecbp384" => asymmetric::Algorithm::EC_BP384,
+        "ecbp512" => asymmetric::Algorithm::EC_BP512,
+        other => bail!("invalid asymmetric algorithm: {}", other),
+    })
+}
+
+/// Parse an HMAC algorithm name
+fn parse_hmac_algorithm(name: &str) -> Result<hmac::Algorithm, Error> {
+    Ok(match name {
+        "hmac-sha1" => hmac::Algorithm::SHA1,
+        "hmac-sha256" => hmac::Algorithm::SHA256,
+        "hmac-sha384" => hmac::Algorithm::SHA384,
+        "hmac-sha512" => hmac::Algorithm::SHA512,
+        other => bail!("invalid HMAC algorithm: {}", other),
+    })
+}
+
+/// Parse an opaque object algorithm name
+fn parse_opaque_algorithm(name: &str) -> Result<opaque::Algorithm, Error> {
+    Ok(match name {
+        "data" => opaque::Algorithm::DATA,
+        "x509-certificate" => opaque::Algorithm::X509_CERTIFICATE,
+        other => bail!("invalid opaque algorithm: {}", other),
+    })
+}
+
+/// Trim leading and trailing ASCII whitespace from a byte slice
+fn trim_whitespace(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or_else(|| bytes.len());
+
+    let end = bytes
+        .iter()
+        .rposition(|b| !b.is_ascii_whitespace())
+        .map(|i| i + 1)
+        .unwrap_or(start);
+
+    &bytes[start..end]
+}
+
+/// Default setup authentication key ID
+fn default_setup_auth_key_id() -> Option<object::Id> {
+    Some(DEFAULT_SETUP_KEY_ID)
+}
+
+/// Delete the setup authentication key by default
+fn default_delete_setup_auth_key() -> bool {
+    true
+}
+
+/// Default report object ID
+fn default_report_object_id() -> Option<object::Id> {
+    Some(DEFAULT_REPORT_OBJECT_ID)
+}
+
+/// Default device reset timeout
+fn default_reset_device_timeout() -> u64 {
+    DEFAULT_RESET_DEVICE_TIMEOUT_SECS
+}
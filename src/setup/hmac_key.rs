@@ -0,0 +1,133 @@
+//! HMAC keys generated within or imported into the YubiHSM 2 during
+//! provisioning
+
+use super::config::HmacKeyConfig;
+use crate::{hmac, object, Capability, Client, Domain};
+use failure::Error;
+use serde::de::{self, Deserialize, Deserializer};
+use std::fmt::{self, Debug};
+use zeroize::Zeroize;
+
+/// HMAC key to generate within (or import into) the device
+#[derive(Clone)]
+pub struct HmacKey {
+    /// Object ID of the key
+    pub(super) key_id: object::Id,
+
+    /// Label to place on the key
+    pub(super) label: object::Label,
+
+    /// Algorithm of the key
+    pub(super) algorithm: hmac::Algorithm,
+
+    /// Capabilities of the key
+    pub(super) capabilities: Capability,
+
+    /// Domains the key is accessible from
+    pub(super) domains: Domain,
+
+    /// Key bytes to import, or `None` to generate a key within the device
+    pub(super) key_bytes: Option<Vec<u8>>,
+}
+
+impl HmacKey {
+    /// Create a new HMAC key to be generated within the device
+    pub fn generate(key_id: object::Id, algorithm: hmac::Algorithm) -> Self {
+        Self {
+            key_id,
+            label: Default::default(),
+            algorithm,
+            capabilities: Capability::empty(),
+            domains: Domain::empty(),
+            key_bytes: None,
+        }
+    }
+
+    /// Create a new HMAC key to be imported into the device
+    pub fn import<K>(key_id: object::Id, algorithm: hmac::Algorithm, key_bytes: K) -> Self
+    where
+        K: Into<Vec<u8>>,
+    {
+        let mut key = Self::generate(key_id, algorithm);
+        key.key_bytes = Some(key_bytes.into());
+        key
+    }
+
+    /// Set the label for this key
+    pub fn label<L>(mut self, label: L) -> Self
+    where
+        L: Into<object::Label>,
+    {
+        self.label = label.into();
+        self
+    }
+
+    /// Set the capabilities of this key
+    pub fn capabilities(mut self, capabilities: Capability) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Set the domains this key is accessible from
+    pub fn domains(mut self, domains: Domain) -> Self {
+        self.domains = domains;
+        self
+    }
+
+    /// Create this key within the YubiHSM 2 device
+    pub fn create(&self, client: &mut Client) -> Result<(), Error> {
+        match self.key_bytes {
+            Some(ref key_bytes) => client.put_hmac_key(
+                self.key_id,
+                self.label.clone(),
+                self.domains,
+                self.capabilities,
+                self.algorithm,
+                key_bytes.clone(),
+            ),
+            None => client.generate_hmac_key(
+                self.key_id,
+                self.label.clone(),
+                self.domains,
+                self.capabilities,
+                self.algorithm,
+            ),
+        }
+        .map_err(|e| format_err!("error creating HMAC key: {}", e))?;
+
+        Ok(())
+    }
+}
+
+impl Debug for HmacKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Avoid leaking secrets in debug messages
+        write!(
+            f,
+            "yubihsm::setup::HmacKey {{ key_id: {}, label: {:?}, algorithm: {:?}, \
+             capabilities: {:?}, domains: {:?}, imported: {} }}",
+            self.key_id,
+            self.label,
+            self.algorithm,
+            self.capabilities,
+            self.domains,
+            self.key_bytes.is_some()
+        )
+    }
+}
+
+impl Drop for HmacKey {
+    fn drop(&mut self) {
+        if let Some(ref mut key_bytes) = self.key_bytes {
+            key_bytes.zeroize();
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HmacKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        HmacKeyConfig::deserialize(deserializer)?
+            .into_hmac_key()
+            .map_err(de::Error::custom)
+    }
+}
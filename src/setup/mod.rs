@@ -1,10 +1,18 @@
 //! Initial YubiHSM 2 setup functionality using declarative device profiles.
 
+mod asymmetric_key;
+pub mod config;
+mod hmac_key;
+mod opaque;
+pub mod plan;
 mod profile;
 pub mod report;
 mod role;
 
-pub use self::{profile::Profile, report::Report, role::Role};
+pub use self::{
+    asymmetric_key::AsymmetricKey, hmac_key::HmacKey, opaque::Opaque, plan::Plan, profile::Profile,
+    report::Report, role::Role,
+};
 
 use crate::{
     authentication::{self, Credentials, DEFAULT_AUTHENTICATION_KEY_ID},
@@ -0,0 +1,100 @@
+//! Opaque objects (e.g. certificates) stored in the YubiHSM 2 during
+//! provisioning
+
+use super::config::OpaqueConfig;
+use crate::{object, opaque, Capability, Client, Domain};
+use failure::Error;
+use serde::de::{self, Deserialize, Deserializer};
+
+/// Opaque object (arbitrary data or an X.509 certificate) to store in the
+/// device
+#[derive(Clone, Debug)]
+pub struct Opaque {
+    /// Object ID of the opaque object
+    pub(super) object_id: object::Id,
+
+    /// Label to place on the object
+    pub(super) label: object::Label,
+
+    /// Algorithm of the object (i.e. the type of data it contains)
+    pub(super) algorithm: opaque::Algorithm,
+
+    /// Capabilities of the object
+    pub(super) capabilities: Capability,
+
+    /// Domains the object is accessible from
+    pub(super) domains: Domain,
+
+    /// Data to store
+    pub(super) data: Vec<u8>,
+}
+
+impl Opaque {
+    /// Create a new opaque object containing the given data
+    pub fn new<D>(object_id: object::Id, algorithm: opaque::Algorithm, data: D) -> Self
+    where
+        D: Into<Vec<u8>>,
+    {
+        Self {
+            object_id,
+            label: Default::default(),
+            algorithm,
+            capabilities: Capability::empty(),
+            domains: Domain::empty(),
+            data: data.into(),
+        }
+    }
+
+    /// Create a new opaque object containing a DER-encoded X.509 certificate
+    pub fn certificate<D>(object_id: object::Id, der: D) -> Self
+    where
+        D: Into<Vec<u8>>,
+    {
+        Self::new(object_id, opaque::Algorithm::X509_CERTIFICATE, der)
+    }
+
+    /// Set the label for this object
+    pub fn label<L>(mut self, label: L) -> Self
+    where
+        L: Into<object::Label>,
+    {
+        self.label = label.into();
+        self
+    }
+
+    /// Set the capabilities of this object
+    pub fn capabilities(mut self, capabilities: Capability) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Set the domains this object is accessible from
+    pub fn domains(mut self, domains: Domain) -> Self {
+        self.domains = domains;
+        self
+    }
+
+    /// Store this object within the YubiHSM 2 device
+    pub fn create(&self, client: &mut Client) -> Result<(), Error> {
+        client
+            .put_opaque(
+                self.object_id,
+                self.label.clone(),
+                self.domains,
+                self.capabilities,
+                self.algorithm,
+                self.data.clone(),
+            )
+            .map_err(|e| format_err!("error storing opaque object: {}", e))?;
+
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for Opaque {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        OpaqueConfig::deserialize(deserializer)?
+            .into_opaque()
+            .map_err(de::Error::custom)
+    }
+}
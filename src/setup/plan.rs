@@ -0,0 +1,382 @@
+//! Provisioning plans: the differences between a profile and the current
+//! state of a device, computed without modifying the device
+
+use super::{report::REPORT_OBJECT_LABEL, Profile, SETUP_KEY_LABEL};
+use crate::{
+    authentication, command, object, opaque, Algorithm, AuditOption, Capability, Client, Domain,
+};
+use failure::Error;
+use std::collections::BTreeMap;
+
+/// Attributes of an object described by a profile
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spec {
+    /// Object identifier
+    pub object_id: object::Id,
+
+    /// Object type
+    pub object_type: object::Type,
+
+    /// Label of the object
+    pub label: object::Label,
+
+    /// Algorithm of the object
+    pub algorithm: Algorithm,
+
+    /// Capabilities of the object
+    pub capabilities: Capability,
+
+    /// Delegated capabilities of the object
+    pub delegated_capabilities: Capability,
+
+    /// Domains the object is accessible from
+    pub domains: Domain,
+}
+
+impl Spec {
+    /// Get the handle of the object
+    pub fn handle(&self) -> object::Handle {
+        object::Handle::new(self.object_id, self.object_type)
+    }
+
+    /// Find the attributes in which an existing object differs from this spec
+    fn differences(&self, info: &object::Info) -> Vec<Difference> {
+        let mut differences = vec![];
+
+        if info.label != self.label {
+            differences.push(Difference::Label {
+                expected: self.label.clone(),
+                actual: info.label.clone(),
+            });
+        }
+
+        if info.algorithm != self.algorithm {
+            differences.push(Difference::Algorithm {
+                expected: self.algorithm,
+                actual: info.algorithm,
+            });
+        }
+
+        if info.capabilities != self.capabilities {
+            differences.push(Difference::Capabilities {
+                expected: self.capabilities,
+                actual: info.capabilities,
+            });
+        }
+
+        if info.delegated_capabilities != self.delegated_capabilities {
+            differences.push(Difference::DelegatedCapabilities {
+                expected: self.delegated_capabilities,
+                actual: info.delegated_capabilities,
+            });
+        }
+
+        if info.domains != self.domains {
+            differences.push(Difference::Domains {
+                expected: self.domains,
+                actual: info.domains,
+            });
+        }
+
+        differences
+    }
+}
+
+/// Attribute in which an existing object differs from the profile
+#[derive(Clone, Debug, PartialEq)]
+pub enum Difference {
+    /// Object has a different label
+    Label {
+        /// Label in the profile
+        expected: object::Label,
+
+        /// Label on the device
+        actual: object::Label,
+    },
+
+    /// Object has a different algorithm
+    Algorithm {
+        /// Algorithm in the profile
+        expected: Algorithm,
+
+        /// Algorithm on the device
+        actual: Algorithm,
+    },
+
+    /// Object has different capabilities
+    Capabilities {
+        /// Capabilities in the profile
+        expected: Capability,
+
+        /// Capabilities on the device
+        actual: Capability,
+    },
+
+    /// Object has different delegated capabilities
+    DelegatedCapabilities {
+        /// Delegated capabilities in the profile
+        expected: Capability,
+
+        /// Delegated capabilities on the device
+        actual: Capability,
+    },
+
+    /// Object is accessible from different domains
+    Domains {
+        /// Domains in the profile
+        expected: Domain,
+
+        /// Domains on the device
+        actual: Domain,
+    },
+}
+
+/// Object which exists on the device but differs from the profile
+#[derive(Clone, Debug)]
+pub struct Conflict {
+    /// Object as described by the profile
+    pub spec: Spec,
+
+    /// Object as it exists on the device
+    pub info: object::Info,
+
+    /// Attributes in which the object differs
+    pub differences: Vec<Difference>,
+}
+
+/// Change to an auditing setting
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AuditChange {
+    /// Change to the force audit option
+    Force {
+        /// Option currently set on the device
+        current: AuditOption,
+
+        /// Option in the profile
+        desired: AuditOption,
+    },
+
+    /// Change to the audit option for a particular command
+    Command {
+        /// Command the option applies to
+        command: command::Code,
+
+        /// Option currently set on the device
+        current: AuditOption,
+
+        /// Option in the profile
+        desired: AuditOption,
+    },
+}
+
+/// Differences between a profile and the current state of a device
+#[derive(Clone, Debug, Default)]
+pub struct Plan {
+    /// Objects in the profile which don't exist on the device
+    pub create: Vec<Spec>,
+
+    /// Objects on the device which differ from the profile
+    pub conflicts: Vec<Conflict>,
+
+    /// Objects on the device which match the profile
+    pub unchanged: Vec<Spec>,
+
+    /// Objects on the device which aren't in the profile, and would be
+    /// removed by provisioning it from scratch
+    pub remove: Vec<object::Info>,
+
+    /// Auditing settings which differ from the profile
+    pub audit: Vec<AuditChange>,
+
+    /// Auditing settings which differ from the profile but can't be changed,
+    /// because they're set to `Fix` on the device (which is permanent until
+    /// the device is reset)
+    pub audit_conflicts: Vec<AuditChange>,
+}
+
+impl Plan {
+    /// Compare the given profile to the device's current objects and audit
+    /// settings. The device is not modified.
+    pub(super) fn new(profile: &Profile, client: &Client) -> Result<Self, Error> {
+        let mut plan = Plan::default();
+        let mut existing = BTreeMap::new();
+
+        for entry in client.list_objects(&[])? {
+            let info = client.get_object_info(entry.object_id, entry.object_type)?;
+            existing.insert(
+                object::Handle::new(entry.object_id, entry.object_type),
+                info,
+            );
+        }
+
+        for spec in specs(profile) {
+            match existing.remove(&spec.handle()) {
+                Some(info) => {
+                    let differences = spec.differences(&info);
+
+                    if differences.is_empty() {
+                        plan.unchanged.push(spec);
+                    } else {
+                        plan.conflicts.push(Conflict {
+                            spec,
+                            info,
+                            differences,
+                        });
+                    }
+                }
+                None => plan.create.push(spec),
+            }
+        }
+
+        plan.remove = existing.into_iter().map(|(_, info)| info).collect();
+
+        let current = client.get_force_audit_option()?;
+
+        if current != profile.audit_option {
+            plan.push_audit_change(
+                current,
+                AuditChange::Force {
+                    current,
+                    desired: profile.audit_option,
+                },
+            );
+        }
+
+        let command_audit_options = client.get_commands_audit_options()?;
+
+        for &(command, desired) in &profile.command_audit_options {
+            let current = command_audit_options
+                .iter()
+                .find(|option| option.command_type() == command)
+                .map(|option| option.audit_option())
+                .unwrap_or(AuditOption::Off);
+
+            if current != desired {
+                plan.push_audit_change(
+                    current,
+                    AuditChange::Command {
+                        command,
+                        current,
+                        desired,
+                    },
+                );
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Does the device already match the profile?
+    pub fn is_empty(&self) -> bool {
+        self.create.is_empty()
+            && self.conflicts.is_empty()
+            && self.remove.is_empty()
+            && self.audit.is_empty()
+            && self.audit_conflicts.is_empty()
+    }
+
+    /// Add a change to an auditing setting, which is a conflict if the
+    /// setting is currently fixed
+    fn push_audit_change(&mut self, current: AuditOption, change: AuditChange) {
+        if current == AuditOption::Fix {
+            self.audit_conflicts.push(change);
+        } else {
+            self.audit.push(change);
+        }
+    }
+}
+
+/// Attributes of all of the objects described by a profile
+fn specs(profile: &Profile) -> Vec<Spec> {
+    let mut specs = vec![];
+
+    for role in &profile.roles {
+        specs.push(Spec {
+            object_id: role.credentials.authentication_key_id,
+            object_type: object::Type::AuthenticationKey,
+            label: role.authentication_key_label.clone(),
+            algorithm: authentication::Algorithm::default().into(),
+            capabilities: role.capabilities,
+            delegated_capabilities: role.delegated_capabilities,
+            domains: role.domains,
+        });
+    }
+
+    for wrap_key in &profile.wrap_keys {
+        let params = &wrap_key.import_params;
+
+        specs.push(Spec {
+            object_id: params.id,
+            object_type: object::Type::WrapKey,
+            label: params.label.clone(),
+            algorithm: params.algorithm,
+            capabilities: params.capabilities,
+            delegated_capabilities: wrap_key.delegated_capabilities,
+            domains: params.domains,
+        });
+    }
+
+    for key in &profile.asymmetric_keys {
+        specs.push(Spec {
+            object_id: key.key_id,
+            object_type: object::Type::AsymmetricKey,
+            label: key.label.clone(),
+            algorithm: key.algorithm.into(),
+            capabilities: key.capabilities,
+            delegated_capabilities: Capability::empty(),
+            domains: key.domains,
+        });
+    }
+
+    for key in &profile.hmac_keys {
+        specs.push(Spec {
+            object_id: key.key_id,
+            object_type: object::Type::HmacKey,
+            label: key.label.clone(),
+            algorithm: key.algorithm.into(),
+            capabilities: key.capabilities,
+            delegated_capabilities: Capability::empty(),
+            domains: key.domains,
+        });
+    }
+
+    for opaque_object in &profile.opaque_objects {
+        specs.push(Spec {
+            object_id: opaque_object.object_id,
+            object_type: object::Type::Opaque,
+            label: opaque_object.label.clone(),
+            algorithm: opaque_object.algorithm.into(),
+            capabilities: opaque_object.capabilities,
+            delegated_capabilities: Capability::empty(),
+            domains: opaque_object.domains,
+        });
+    }
+
+    if let Some(report_object_id) = profile.report_object_id {
+        specs.push(Spec {
+            object_id: report_object_id,
+            object_type: object::Type::Opaque,
+            label: REPORT_OBJECT_LABEL.into(),
+            algorithm: opaque::Algorithm::DATA.into(),
+            capabilities: Capability::GET_OPAQUE,
+            delegated_capabilities: Capability::empty(),
+            domains: Domain::all(),
+        });
+    }
+
+    if !profile.delete_setup_auth_key {
+        if let Some(setup_auth_key_id) = profile.setup_auth_key_id {
+            specs.push(Spec {
+                object_id: setup_auth_key_id,
+                object_type: object::Type::AuthenticationKey,
+                label: SETUP_KEY_LABEL.into(),
+                algorithm: authentication::Algorithm::default().into(),
+                capabilities: Capability::all(),
+                delegated_capabilities: Capability::all(),
+                domains: Domain::all(),
+            });
+        }
+    }
+
+    specs
+}
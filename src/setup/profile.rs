@@ -1,10 +1,14 @@
 //! Device provisioning profiles: all attributes required to initialize a device
 
+use super::config::ProfileConfig;
+use super::plan::{AuditChange, Spec};
+use super::report::GeneratedKey;
 use super::role::Role;
-use super::Report;
-use crate::{object, wrap, AuditOption, Client};
+use super::{AsymmetricKey, HmacKey, Opaque, Plan, Report};
+use crate::{command, object, wrap, AuditOption, Capability, Client, Credentials};
 use failure::Error;
-use std::time::Duration;
+use serde::de::{self, Deserialize, Deserializer};
+use std::{collections::BTreeSet, str::FromStr, time::Duration};
 
 /// Temporary account key to use for device provisioning.
 /// Uses key ID #65534 as 65535 is reserved for internal use.
@@ -16,6 +20,9 @@ pub const DEFAULT_REPORT_OBJECT_ID: object::Id = 0xFFFE;
 
 /// YubiHSM 2 provisioning profile: a declarative profile specifying how a
 /// device should be (re)provisioned.
+///
+/// Profiles can be built in code, or deserialized from a configuration file
+/// (e.g. TOML or JSON). See the `setup::config` module for the format.
 #[derive(Clone, Debug)]
 pub struct Profile {
     /// Key ID to use for provisioning the device
@@ -36,10 +43,32 @@ pub struct Profile {
     /// imported into other devices.
     pub(super) wrap_keys: Vec<wrap::Key>,
 
+    /// Set of asymmetric keys to generate within the device. Their public
+    /// keys are recorded in the provisioning report.
+    pub(super) asymmetric_keys: Vec<AsymmetricKey>,
+
+    /// Set of HMAC keys to generate within or import into the device
+    pub(super) hmac_keys: Vec<HmacKey>,
+
+    /// Set of opaque objects (e.g. certificates) to store in the device
+    pub(super) opaque_objects: Vec<Opaque>,
+
+    /// Per-command auditing modes to configure the device with
+    pub(super) command_audit_options: Vec<(command::Code, AuditOption)>,
+
     /// Store a JSON copy of the provisioning report in the given opaque
     /// object slot
     pub(super) report_object_id: Option<object::Id>,
 
+    /// Current credentials of roles whose authentication keys should be
+    /// changed to the ones in the profile when reconciling a device which
+    /// already contains them
+    pub(super) rotate_credentials: Vec<Credentials>,
+
+    /// Should objects which differ from the profile be deleted and recreated
+    /// when reconciling a device, rather than aborting?
+    pub(super) replace_conflicting: bool,
+
     /// How long to wait for the device to reset before giving up
     pub(super) reset_device_timeout: Duration,
 }
@@ -52,7 +81,13 @@ impl Default for Profile {
             audit_option: AuditOption::Off,
             roles: Vec::new(),
             wrap_keys: Vec::new(),
+            asymmetric_keys: Vec::new(),
+            hmac_keys: Vec::new(),
+            opaque_objects: Vec::new(),
+            command_audit_options: Vec::new(),
             report_object_id: Some(DEFAULT_REPORT_OBJECT_ID),
+            rotate_credentials: Vec::new(),
+            replace_conflicting: false,
             reset_device_timeout: Duration::from_secs(10),
         }
     }
@@ -64,6 +99,13 @@ impl Profile {
         Self::default()
     }
 
+    /// Parse a profile from a TOML configuration (see the `setup::config`
+    /// module for the format). JSON configurations can be parsed with `FromStr`.
+    pub fn from_toml_str(s: &str) -> Result<Self, Error> {
+        toml::from_str(s)
+            .map_err(|e| format_err!("error parsing yubihsm::setup::Profile TOML: {}", e))
+    }
+
     /// Configure the auth key ID to use when performing device setup
     pub fn setup_auth_key_id(mut self, key_id: Option<object::Id>) -> Self {
         self.setup_auth_key_id = key_id;
@@ -96,6 +138,74 @@ impl Profile {
         self
     }
 
+    /// Set the asymmetric keys to generate
+    pub fn asymmetric_keys<I>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = AsymmetricKey>,
+    {
+        self.asymmetric_keys = keys.into_iter().collect();
+        self
+    }
+
+    /// Set the HMAC keys to generate or import
+    pub fn hmac_keys<I>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = HmacKey>,
+    {
+        self.hmac_keys = keys.into_iter().collect();
+        self
+    }
+
+    /// Set the opaque objects (e.g. certificates) to store
+    pub fn opaque_objects<I>(mut self, objects: I) -> Self
+    where
+        I: IntoIterator<Item = Opaque>,
+    {
+        self.opaque_objects = objects.into_iter().collect();
+        self
+    }
+
+    /// Set the auditing mode for a particular command. See:
+    ///
+    /// <https://docs.rs/yubihsm/latest/yubihsm/client/struct.Client.html#method.set_command_audit_option>
+    pub fn command_audit_option(mut self, command: command::Code, value: AuditOption) -> Self {
+        self.command_audit_options.push((command, value));
+        self
+    }
+
+    /// Change the authentication keys of the roles with the given (current)
+    /// credentials to the ones in the profile when reconciling a device which
+    /// already contains them.
+    ///
+    /// Each role needs the `CHANGE_AUTHENTICATION_KEY` capability, as its key
+    /// is changed in place by a session authenticated as the role itself.
+    pub fn rotate_credentials<I>(mut self, current_credentials: I) -> Self
+    where
+        I: IntoIterator<Item = Credentials>,
+    {
+        self.rotate_credentials = current_credentials.into_iter().collect();
+        self
+    }
+
+    /// Delete and recreate objects which differ from the profile when
+    /// reconciling a device, rather than aborting.
+    ///
+    /// WARNING: replacing an asymmetric or HMAC key destroys the existing key!
+    pub fn replace_conflicting(mut self, value: bool) -> Self {
+        self.replace_conflicting = value;
+        self
+    }
+
+    /// Compare this profile to the current state of the YubiHSM 2 without
+    /// modifying it, e.g. to review what `erase_device_and_init_with_profile`
+    /// will change before running it.
+    ///
+    /// The client must be able to see every object on the device (i.e. have
+    /// access to all domains) for the plan to be complete.
+    pub fn plan(&self, client: &Client) -> Result<Plan, Error> {
+        Plan::new(self, client)
+    }
+
     /// Use this profile to provision the YubiHSM 2 with the given client
     pub fn provision(&self, client: &mut Client) -> Result<Report, Error> {
         for role in &self.roles {
@@ -108,12 +218,240 @@ impl Profile {
             wrap_key.create(client)?;
         }
 
+        let mut generated_keys = vec![];
+
+        for asymmetric_key in &self.asymmetric_keys {
+            info!("generating asymmetric key: {}", &asymmetric_key.label);
+            let public_key = asymmetric_key.create(client)?;
+            generated_keys.push(GeneratedKey::new(asymmetric_key, &public_key));
+        }
+
+        for hmac_key in &self.hmac_keys {
+            info!("installing HMAC key: {}", &hmac_key.label);
+            hmac_key.create(client)?;
+        }
+
+        for opaque_object in &self.opaque_objects {
+            info!("storing opaque object: {}", &opaque_object.label);
+            opaque_object.create(client)?;
+        }
+
+        for &(command, audit_option) in &self.command_audit_options {
+            info!("setting {:?} command audit to: {:?}", command, audit_option);
+            client.set_command_audit_option(command, audit_option)?;
+        }
+
         if self.audit_option != AuditOption::Off {
             info!("setting force audit to: {:?}", self.audit_option);
             client.set_force_audit_option(self.audit_option)?;
         }
 
-        let report = Report::new(client.device_info()?.serial_number);
+        self.store_report(client, generated_keys)
+    }
+
+    /// Apply this profile to an already-provisioned YubiHSM 2 in place,
+    /// without resetting it (and so without destroying existing keys).
+    ///
+    /// Objects in the profile which are missing from the device are created,
+    /// and roles listed in `rotate_credentials` have their authentication keys
+    /// changed in place to the profile's credentials. If any existing objects differ
+    /// from the profile, nothing is changed and an error is returned unless
+    /// `replace_conflicting` is set, in which case they're deleted and
+    /// recreated. Objects on the device which aren't in the profile are left
+    /// alone. Audit options set to `Fix` on the device can't be changed, so
+    /// an error is returned if they differ from the profile. Finally, the
+    /// provisioning report is rewritten.
+    ///
+    /// The client must be able to see every object on the device, and needs
+    /// the capabilities to delete any objects being replaced.
+    pub fn reconcile(&self, client: &mut Client) -> Result<Report, Error> {
+        for current_credentials in &self.rotate_credentials {
+            let key_id = current_credentials.authentication_key_id;
+
+            let role = match self
+                .roles
+                .iter()
+                .find(|role| role.credentials.authentication_key_id == key_id)
+            {
+                Some(role) => role,
+                None => bail!(
+                    "can't rotate credentials for authentication key {}: no such role in profile",
+                    key_id
+                ),
+            };
+
+            if !role
+                .capabilities
+                .contains(Capability::CHANGE_AUTHENTICATION_KEY)
+            {
+                bail!(
+                    "can't rotate credentials for authentication key {}: role lacks the CHANGE_AUTHENTICATION_KEY capability",
+                    key_id
+                );
+            }
+        }
+
+        let plan = self.plan(client)?;
+
+        if !plan.audit_conflicts.is_empty() {
+            bail!(
+                "audit options fixed on the device differ from the profile: {:?}",
+                plan.audit_conflicts
+            );
+        }
+
+        // The temporary setup key is only meaningful when provisioning from scratch
+        let setup_key = self
+            .setup_auth_key_id
+            .map(|key_id| object::Handle::new(key_id, object::Type::AuthenticationKey));
+
+        let conflicts: Vec<_> = plan
+            .conflicts
+            .iter()
+            .filter(|conflict| Some(conflict.spec.handle()) != setup_key)
+            .collect();
+
+        if !conflicts.is_empty() && !self.replace_conflicting {
+            let descriptions: Vec<_> = conflicts
+                .iter()
+                .map(|conflict| {
+                    format!(
+                        "{:?} object {} ({:?})",
+                        conflict.spec.object_type, conflict.spec.object_id, conflict.differences
+                    )
+                })
+                .collect();
+
+            bail!(
+                "objects on the device differ from the profile: {}",
+                descriptions.join(", ")
+            );
+        }
+
+        for conflict in &conflicts {
+            warn!(
+                "replacing {:?} object {} which differs from the profile",
+                conflict.spec.object_type, conflict.spec.object_id
+            );
+
+            client
+                .delete_object(conflict.spec.object_id, conflict.spec.object_type)
+                .map_err(|e| format_err!("error deleting conflicting object: {}", e))?;
+        }
+
+        let existing: BTreeSet<_> = plan.unchanged.iter().map(Spec::handle).collect();
+        let exists = |object_id, object_type| {
+            existing.contains(&object::Handle::new(object_id, object_type))
+        };
+
+        for role in &self.roles {
+            let key_id = role.credentials.authentication_key_id;
+
+            if !exists(key_id, object::Type::AuthenticationKey) {
+                info!("installing role: {}", role.authentication_key_label);
+                role.create(client)?;
+                continue;
+            }
+
+            let current_credentials = match self
+                .rotate_credentials
+                .iter()
+                .find(|credentials| credentials.authentication_key_id == key_id)
+            {
+                Some(credentials) => credentials,
+                None => continue,
+            };
+
+            info!(
+                "rotating credentials for role: {}",
+                role.authentication_key_label
+            );
+
+            // Only a session authenticated as the role can change its key, and
+            // changing it in place leaves the role usable if this fails
+            let role_client = Client::open(
+                client.connector().clone(),
+                current_credentials.clone(),
+                false,
+            )
+            .map_err(|e| format_err!("error authenticating as role to rotate: {}", e))?;
+
+            role_client
+                .change_authentication_key(key_id, role.credentials.authentication_key.clone())
+                .map_err(|e| format_err!("error rotating role credentials: {}", e))?;
+        }
+
+        for wrap_key in &self.wrap_keys {
+            if !exists(wrap_key.import_params.id, object::Type::WrapKey) {
+                info!("installing wrap key: {}", &wrap_key.import_params.label);
+                wrap_key.create(client)?;
+            }
+        }
+
+        let mut generated_keys = vec![];
+
+        for asymmetric_key in &self.asymmetric_keys {
+            let public_key = if exists(asymmetric_key.key_id, object::Type::AsymmetricKey) {
+                client
+                    .get_public_key(asymmetric_key.key_id)
+                    .map_err(|e| format_err!("error getting public key: {}", e))?
+            } else {
+                info!("generating asymmetric key: {}", &asymmetric_key.label);
+                asymmetric_key.create(client)?
+            };
+
+            generated_keys.push(GeneratedKey::new(asymmetric_key, &public_key));
+        }
+
+        for hmac_key in &self.hmac_keys {
+            if !exists(hmac_key.key_id, object::Type::HmacKey) {
+                info!("installing HMAC key: {}", &hmac_key.label);
+                hmac_key.create(client)?;
+            }
+        }
+
+        for opaque_object in &self.opaque_objects {
+            if !exists(opaque_object.object_id, object::Type::Opaque) {
+                info!("storing opaque object: {}", &opaque_object.label);
+                opaque_object.create(client)?;
+            }
+        }
+
+        for change in &plan.audit {
+            match *change {
+                AuditChange::Command {
+                    command, desired, ..
+                } => {
+                    info!("setting {:?} command audit to: {:?}", command, desired);
+                    client.set_command_audit_option(command, desired)?;
+                }
+                AuditChange::Force { desired, .. } => {
+                    info!("setting force audit to: {:?}", desired);
+                    client.set_force_audit_option(desired)?;
+                }
+            }
+        }
+
+        if let Some(report_object_id) = self.report_object_id {
+            // Remove the previous report so it can be replaced
+            if exists(report_object_id, object::Type::Opaque) {
+                client
+                    .delete_object(report_object_id, object::Type::Opaque)
+                    .map_err(|e| format_err!("error deleting previous report: {}", e))?;
+            }
+        }
+
+        self.store_report(client, generated_keys)
+    }
+
+    /// Create a provisioning report, storing it in the device if configured
+    fn store_report(
+        &self,
+        client: &mut Client,
+        public_keys: Vec<GeneratedKey>,
+    ) -> Result<Report, Error> {
+        let mut report = Report::new(client.device_info()?.serial_number);
+        report.public_keys = public_keys;
 
         if let Some(report_object_id) = self.report_object_id {
             info!(
@@ -126,3 +464,21 @@ impl Profile {
         Ok(report)
     }
 }
+
+impl<'de> Deserialize<'de> for Profile {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ProfileConfig::deserialize(deserializer)?
+            .into_profile()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl FromStr for Profile {
+    type Err = Error;
+
+    /// Parse a `yubihsm::setup::Profile` from a JSON configuration
+    fn from_str(s: &str) -> Result<Self, Error> {
+        serde_json::from_str(s)
+            .map_err(|e| format_err!("error parsing yubihsm::setup::Profile JSON: {}", e))
+    }
+}
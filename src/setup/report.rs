@@ -1,10 +1,13 @@
 //! YubiHSM 2 provisioning reports which record the server where the HSM was
 //! provisioned, the username which performed the provisioning operation,
-//! and the date provisioning occurred.
+//! the date provisioning occurred, and the public keys of any asymmetric
+//! keys generated during provisioning.
 
 #![allow(clippy::new_without_default)]
 
+use super::AsymmetricKey;
 use crate::{
+    asymmetric,
     device::SerialNumber,
     object, opaque,
     uuid::{self, Uuid},
@@ -13,6 +16,7 @@ use crate::{
 use chrono::{DateTime, Utc};
 use failure::Error;
 use std::{env, str::FromStr};
+use subtle_encoding::hex;
 
 /// Label string for the provisioning report object
 pub const REPORT_OBJECT_LABEL: &str = "yubihsm.rs setup report";
@@ -50,6 +54,10 @@ pub struct Report {
 
     /// Software that performed the provisioning
     pub software: String,
+
+    /// Asymmetric keys generated during provisioning (added in version 2)
+    #[serde(default)]
+    pub public_keys: Vec<GeneratedKey>,
 }
 
 impl Report {
@@ -57,13 +65,14 @@ impl Report {
     pub fn new(serial_number: SerialNumber) -> Self {
         // TODO: handle these better on operating systems other than *IX
         Report {
-            version: Version(1),
+            version: Version(2),
             uuid: uuid::new_v4(),
             device_serial_number: serial_number.to_string(),
             username: env::var("LOGNAME").map(|u| u.to_owned()).ok(),
             hostname: env::var("HOSTNAME").map(|h| h.to_owned()).ok(),
             date: Utc::now(),
             software: format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+            public_keys: vec![],
         }
     }
 
@@ -98,3 +107,40 @@ impl FromStr for Report {
             .map_err(|e| format_err!("error parsing yubihsm::setup::Report JSON: {}", e))
     }
 }
+
+/// Asymmetric key generated during provisioning
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GeneratedKey {
+    /// Object ID of the key
+    pub key_id: object::Id,
+
+    /// Label of the key
+    pub label: String,
+
+    /// Algorithm of the key
+    pub algorithm: asymmetric::Algorithm,
+
+    /// Hex-encoded public key bytes (see `asymmetric::PublicKey::bytes`)
+    pub public_key: String,
+}
+
+impl GeneratedKey {
+    /// Record the public key of a key generated during provisioning
+    pub(super) fn new(key: &AsymmetricKey, public_key: &asymmetric::PublicKey) -> Self {
+        GeneratedKey {
+            key_id: key.key_id,
+            label: key.label.to_string(),
+            algorithm: public_key.algorithm,
+            public_key: String::from_utf8(hex::encode(&public_key.bytes)).unwrap(),
+        }
+    }
+
+    /// Decode the public key
+    pub fn public_key(&self) -> Result<asymmetric::PublicKey, Error> {
+        let bytes = hex::decode(&self.public_key)
+            .map_err(|e| format_err!("error decoding public key {}: {}", self.key_id, e))?;
+
+        asymmetric::PublicKey::new(self.algorithm, bytes)
+            .map_err(|e| format_err!("invalid public key {}: {}", self.key_id, e))
+    }
+}
@@ -2,10 +2,16 @@
 
 pub use crate::{object, Capability, Credentials, Domain};
 
-use crate::Client;
+use super::config::RoleConfig;
+use crate::{authentication, Client};
 use failure::Error;
+use serde::de::{self, Deserialize, Deserializer};
 
-/// Roles represent accounts on the device with specific permissions
+/// Roles represent accounts on the device with specific permissions.
+///
+/// Roles can be deserialized from configuration files, in which case their
+/// password is read from the file given as `password_file` or the
+/// environment variable given as `password_env`.
 #[derive(Clone, Debug)]
 pub struct Role {
     /// Label to place on the authentication key for this role
@@ -63,6 +69,11 @@ impl Role {
         self
     }
 
+    /// Get the credentials used to authenticate with this role
+    pub fn credentials(&self) -> &Credentials {
+        &self.credentials
+    }
+
     /// Create this role within the YubiHSM 2 device
     pub fn create(&self, client: &mut Client) -> Result<(), Error> {
         client
@@ -79,4 +90,33 @@ impl Role {
 
         Ok(())
     }
+
+    /// Rotate this role's authentication key in place within the YubiHSM 2,
+    /// preserving its object ID, and update this role's credentials.
+    ///
+    /// The client must be authenticated as this role, which needs the
+    /// `CHANGE_AUTHENTICATION_KEY` capability.
+    pub fn rotate_credentials(
+        &mut self,
+        client: &mut Client,
+        new_authentication_key: authentication::Key,
+    ) -> Result<(), Error> {
+        client
+            .change_authentication_key(
+                self.credentials.authentication_key_id,
+                new_authentication_key.clone(),
+            )
+            .map_err(|e| format_err!("error rotating role credentials: {}", e))?;
+
+        self.credentials.authentication_key = new_authentication_key;
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for Role {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        RoleConfig::deserialize(deserializer)?
+            .into_role()
+            .map_err(de::Error::custom)
+    }
 }
@@ -0,0 +1,178 @@
+//! OpenSSH certificates
+
+use super::{
+    encoding::{Decoder, Encoder},
+    SignedRequest, SshError,
+    SshErrorKind::{AlgorithmUnsupported, FormatInvalid},
+};
+use crate::{algorithm::Algorithm, asymmetric, der, ecdsa, rsa};
+use subtle_encoding::base64;
+
+/// Type of an OpenSSH certificate
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CertificateType {
+    /// User certificate (authenticates a user to a host)
+    User = 1,
+
+    /// Host certificate (authenticates a host to a user)
+    Host = 2,
+}
+
+/// OpenSSH certificate (`*-cert-v01@openssh.com`) in SSH wire format
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Certificate(Vec<u8>);
+
+#[allow(clippy::len_without_is_empty)]
+impl Certificate {
+    /// Assemble a certificate from a signed request and the CA's raw signature
+    /// over its body, as returned by the HSM
+    pub(crate) fn new(
+        request: &SignedRequest,
+        algorithm: Algorithm,
+        signature: &[u8],
+    ) -> Result<Self, SshError> {
+        let ca_key_type = request.ca_key_type();
+        let mut signature_blob = Encoder::new();
+
+        match algorithm {
+            Algorithm::Asymmetric(asymmetric::Algorithm::Ed25519) => {
+                ensure_ca_key_type(ca_key_type, "ssh-ed25519")?;
+                signature_blob.string(b"ssh-ed25519");
+                signature_blob.string(signature);
+            }
+            Algorithm::Ecdsa(alg) => {
+                let expected_key_type = match alg {
+                    ecdsa::Algorithm::SHA256 => "ecdsa-sha2-nistp256",
+                    ecdsa::Algorithm::SHA384 => "ecdsa-sha2-nistp384",
+                    ecdsa::Algorithm::SHA512 => "ecdsa-sha2-nistp521",
+                    _ => fail!(
+                        AlgorithmUnsupported,
+                        "unsupported ECDSA algorithm: {:?}",
+                        alg
+                    ),
+                };
+
+                ensure_ca_key_type(ca_key_type, expected_key_type)?;
+                let (r, s) = parse_der_signature(signature)?;
+
+                let mut rs = Encoder::new();
+                rs.mpint(r);
+                rs.mpint(s);
+
+                signature_blob.string(expected_key_type.as_bytes());
+                signature_blob.string(&rs.into_vec());
+            }
+            Algorithm::Rsa(alg) => {
+                let signature_type = match alg {
+                    rsa::Algorithm::PKCS1_SHA1 => "ssh-rsa",
+                    rsa::Algorithm::PKCS1_SHA256 => "rsa-sha2-256",
+                    rsa::Algorithm::PKCS1_SHA512 => "rsa-sha2-512",
+                    _ => fail!(AlgorithmUnsupported, "unsupported RSA algorithm: {:?}", alg),
+                };
+
+                ensure_ca_key_type(ca_key_type, "ssh-rsa")?;
+                signature_blob.string(signature_type.as_bytes());
+                signature_blob.string(signature);
+            }
+            _ => fail!(
+                AlgorithmUnsupported,
+                "unsupported SSH signature algorithm: {:?}",
+                algorithm
+            ),
+        }
+
+        let mut certificate = request.body().to_vec();
+        let mut encoder = Encoder::new();
+        encoder.string(&signature_blob.into_vec());
+        certificate.extend_from_slice(&encoder.into_vec());
+
+        Ok(Certificate(certificate))
+    }
+
+    /// Unwrap inner byte vector (SSH wire format)
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Get length of the certificate
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Get slice of the inner byte vector (SSH wire format)
+    pub fn as_slice(&self) -> &[u8] {
+        self.as_ref()
+    }
+
+    /// Serialize this certificate in the format of OpenSSH `*-cert.pub`
+    /// files, i.e. `<certificate type> <base64 data>`
+    pub fn to_openssh(&self) -> String {
+        // Certificates always begin with their (ASCII) type string
+        let cert_type = Decoder::new(&self.0).string().unwrap_or(&[]);
+
+        format!(
+            "{} {}",
+            String::from_utf8_lossy(cert_type),
+            String::from_utf8(base64::encode(&self.0)).unwrap()
+        )
+    }
+}
+
+impl AsRef<[u8]> for Certificate {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Into<Vec<u8>> for Certificate {
+    fn into(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+/// Ensure the CA key type is compatible with the signature algorithm
+fn ensure_ca_key_type(ca_key_type: &str, expected: &str) -> Result<(), SshError> {
+    ensure!(
+        ca_key_type == expected,
+        AlgorithmUnsupported,
+        "signature algorithm incompatible with CA key type: {}",
+        ca_key_type
+    );
+
+    Ok(())
+}
+
+/// Parse an ASN.1 DER-encoded ECDSA signature into its `r` and `s` components
+fn parse_der_signature(signature: &[u8]) -> Result<(&[u8], &[u8]), SshError> {
+    let mut outer = der::Decoder::new(signature);
+    let ecdsa_sig_value = outer.read(der::SEQUENCE).ok_or_else(malformed)?;
+    ensure!(
+        outer.is_empty(),
+        FormatInvalid,
+        "trailing data after ECDSA signature"
+    );
+
+    let mut ecdsa_sig_value = der::Decoder::new(ecdsa_sig_value);
+    let r = ecdsa_sig_value
+        .read(der::INTEGER)
+        .and_then(der::decode_integer)
+        .ok_or_else(malformed)?;
+    let s = ecdsa_sig_value
+        .read(der::INTEGER)
+        .and_then(der::decode_integer)
+        .ok_or_else(malformed)?;
+
+    ensure!(
+        ecdsa_sig_value.is_empty(),
+        FormatInvalid,
+        "trailing data in ECDSA signature"
+    );
+
+    Ok((r, s))
+}
+
+/// Error for malformed DER
+fn malformed() -> SshError {
+    err!(FormatInvalid, "malformed ECDSA signature")
+}
@@ -0,0 +1,38 @@
+//! Sign an SSH certificate using the given template
+//!
+//! <https://developers.yubico.com/YubiHSM2/Commands/Sign_Ssh_Certificate.html>
+
+use crate::{
+    algorithm::Algorithm,
+    command::{self, Command},
+    object,
+    response::Response,
+};
+
+/// Request parameters for `command::sign_ssh_certificate`
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct SignSshCertificateCommand {
+    /// ID of the CA key used to sign the certificate
+    pub key_id: object::Id,
+
+    /// ID of the SSH template used to constrain the certificate
+    pub template_id: object::Id,
+
+    /// Signature algorithm
+    pub algorithm: Algorithm,
+
+    /// Certificate request (timestamp, timestamp signature, and certificate body)
+    pub request: Vec<u8>,
+}
+
+impl Command for SignSshCertificateCommand {
+    type ResponseType = SignSshCertificateResponse;
+}
+
+/// Raw signature over the certificate body
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct SignSshCertificateResponse(pub(crate) Vec<u8>);
+
+impl Response for SignSshCertificateResponse {
+    const COMMAND_CODE: command::Code = command::Code::SignSshCertificate;
+}
@@ -0,0 +1,116 @@
+//! SSH wire format encoding (RFC 4251 Section 5)
+
+use super::{SshError, SshErrorKind::FormatInvalid};
+use byteorder::{BigEndian, ByteOrder};
+
+/// Encoder for SSH wire format messages
+#[derive(Debug, Default)]
+pub(crate) struct Encoder(Vec<u8>);
+
+impl Encoder {
+    /// Create a new encoder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append raw bytes
+    pub fn raw(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    /// Append a `uint32`
+    pub fn u32(&mut self, value: u32) {
+        let mut bytes = [0u8; 4];
+        BigEndian::write_u32(&mut bytes, value);
+        self.raw(&bytes);
+    }
+
+    /// Append a `uint64`
+    pub fn u64(&mut self, value: u64) {
+        let mut bytes = [0u8; 8];
+        BigEndian::write_u64(&mut bytes, value);
+        self.raw(&bytes);
+    }
+
+    /// Append a length-prefixed `string`
+    pub fn string(&mut self, bytes: &[u8]) {
+        self.u32(bytes.len() as u32);
+        self.raw(bytes);
+    }
+
+    /// Append an `mpint` from the big endian bytes of an unsigned integer
+    pub fn mpint(&mut self, bytes: &[u8]) {
+        let leading_zeroes = bytes.iter().take_while(|&&byte| byte == 0).count();
+        let magnitude = &bytes[leading_zeroes..];
+
+        // Positive integers with the high bit set need a leading zero byte
+        if magnitude.first().map(|&byte| byte & 0x80 != 0) == Some(true) {
+            self.u32(magnitude.len() as u32 + 1);
+            self.raw(&[0]);
+            self.raw(magnitude);
+        } else {
+            self.string(magnitude);
+        }
+    }
+
+    /// Get the encoded message
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+/// Decoder for SSH wire format messages
+#[derive(Debug)]
+pub(crate) struct Decoder<'a>(&'a [u8]);
+
+impl<'a> Decoder<'a> {
+    /// Create a new decoder for the given message
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Decoder(bytes)
+    }
+
+    /// Read a `uint32`
+    pub fn u32(&mut self) -> Result<u32, SshError> {
+        ensure!(self.0.len() >= 4, FormatInvalid, "truncated uint32");
+        let value = BigEndian::read_u32(self.0);
+        self.0 = &self.0[4..];
+        Ok(value)
+    }
+
+    /// Read a `uint64`
+    #[cfg(feature = "mockhsm")]
+    pub fn u64(&mut self) -> Result<u64, SshError> {
+        ensure!(self.0.len() >= 8, FormatInvalid, "truncated uint64");
+        let value = BigEndian::read_u64(self.0);
+        self.0 = &self.0[8..];
+        Ok(value)
+    }
+
+    /// Read a length-prefixed `string`
+    pub fn string(&mut self) -> Result<&'a [u8], SshError> {
+        let len = self.u32()? as usize;
+        ensure!(self.0.len() >= len, FormatInvalid, "truncated string");
+        let (string, remaining) = self.0.split_at(len);
+        self.0 = remaining;
+        Ok(string)
+    }
+
+    /// Read an `mpint`, returning the big endian bytes of an unsigned integer
+    /// with any leading zeroes stripped
+    pub fn mpint(&mut self) -> Result<&'a [u8], SshError> {
+        let bytes = self.string()?;
+        ensure!(
+            bytes.first().map(|&byte| byte & 0x80 == 0) != Some(false),
+            FormatInvalid,
+            "negative mpint"
+        );
+
+        let leading_zeroes = bytes.iter().take_while(|&&byte| byte == 0).count();
+        Ok(&bytes[leading_zeroes..])
+    }
+
+    /// Get the data which hasn't been decoded yet
+    pub fn remaining(&self) -> &'a [u8] {
+        self.0
+    }
+}
@@ -0,0 +1,18 @@
+//! SSH certificate error types
+
+use crate::error::Error;
+
+/// SSH certificate errors
+pub type SshError = Error<SshErrorKind>;
+
+/// SSH certificate error kinds
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Fail)]
+pub enum SshErrorKind {
+    /// Malformed SSH wire format data (keys, signatures, etc)
+    #[fail(display = "invalid format")]
+    FormatInvalid,
+
+    /// Key or signature algorithm is not supported
+    #[fail(display = "unsupported algorithm")]
+    AlgorithmUnsupported,
+}
@@ -0,0 +1,19 @@
+//! SSH certificate authority: sign OpenSSH certificates with keys held by
+//! the YubiHSM 2, as constrained by an SSH template.
+//!
+//! <https://developers.yubico.com/YubiHSM2/Usage_Guides/YubiHSM2_for_SSH-CA.html>
+
+mod certificate;
+pub(crate) mod commands;
+pub(crate) mod encoding;
+mod error;
+mod request;
+
+pub use self::{
+    certificate::{Certificate, CertificateType},
+    error::{SshError, SshErrorKind},
+    request::{CertificateRequest, SignedRequest},
+};
+
+#[cfg(feature = "mockhsm")]
+pub(crate) use self::request::{TIMESTAMP_SIGNATURE_SIZE, TIMESTAMP_SIZE};
@@ -0,0 +1,340 @@
+//! OpenSSH certificate requests
+
+use super::{
+    encoding::{Decoder, Encoder},
+    CertificateType, SshError,
+    SshErrorKind::AlgorithmUnsupported,
+};
+use byteorder::{BigEndian, ByteOrder};
+use rand_os::{rand_core::RngCore, OsRng};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, str};
+
+/// Size of the random nonce included in every certificate
+const NONCE_SIZE: usize = 32;
+
+/// Size of the timestamp prefix of a signed request
+pub(crate) const TIMESTAMP_SIZE: usize = 4;
+
+/// Size of the timestamp signature of a signed request (timestamp keys are RSA-2048)
+pub(crate) const TIMESTAMP_SIGNATURE_SIZE: usize = 256;
+
+/// Public key types which can be certified (and used as CA keys)
+const SUPPORTED_KEY_TYPES: &[&str] = &[
+    "ssh-rsa",
+    "ssh-ed25519",
+    "ecdsa-sha2-nistp256",
+    "ecdsa-sha2-nistp384",
+    "ecdsa-sha2-nistp521",
+];
+
+/// OpenSSH certificate request: all of the fields of a certificate which
+/// are covered by the CA's signature.
+///
+/// Public keys are given in SSH wire format, i.e. the base64-decoded portion
+/// of an `authorized_keys` line.
+///
+/// <https://cvsweb.openbsd.org/src/usr.bin/ssh/PROTOCOL.certkeys>
+#[derive(Clone, Debug)]
+pub struct CertificateRequest {
+    /// Public key type of the subject (e.g. `ssh-ed25519`)
+    key_type: String,
+
+    /// Public key fields of the subject (i.e. its wire format sans key type)
+    key_fields: Vec<u8>,
+
+    /// Public key of the CA
+    ca_public_key: Vec<u8>,
+
+    /// Public key type of the CA
+    ca_key_type: String,
+
+    /// Random nonce
+    nonce: [u8; NONCE_SIZE],
+
+    /// Serial number
+    serial: u64,
+
+    /// User or host certificate
+    cert_type: CertificateType,
+
+    /// Key identifier (logged by the SSH server)
+    key_id: String,
+
+    /// Principals (usernames or hostnames) this certificate is valid for
+    principals: Vec<String>,
+
+    /// Start of the validity period (seconds since the UNIX epoch)
+    valid_after: u64,
+
+    /// End of the validity period (seconds since the UNIX epoch)
+    valid_before: u64,
+
+    /// Critical options (sorted by name, as OpenSSH requires)
+    critical_options: BTreeMap<String, String>,
+
+    /// Extensions (sorted by name, as OpenSSH requires)
+    extensions: BTreeMap<String, String>,
+}
+
+impl CertificateRequest {
+    /// Create a new request to certify the given subject public key with the
+    /// given CA public key (both in SSH wire format).
+    ///
+    /// By default the request is for a user certificate with serial number 0,
+    /// no principals, and which is valid forever.
+    pub fn new(subject_public_key: &[u8], ca_public_key: &[u8]) -> Result<Self, SshError> {
+        let (key_type, key_fields) = parse_public_key(subject_public_key)?;
+        let (ca_key_type, _) = parse_public_key(ca_public_key)?;
+
+        let mut nonce = [0u8; NONCE_SIZE];
+        OsRng::new().unwrap().fill_bytes(&mut nonce);
+
+        Ok(Self {
+            key_type,
+            key_fields: key_fields.into(),
+            ca_public_key: ca_public_key.into(),
+            ca_key_type,
+            nonce,
+            serial: 0,
+            cert_type: CertificateType::User,
+            key_id: String::new(),
+            principals: vec![],
+            valid_after: 0,
+            valid_before: u64::max_value(),
+            critical_options: BTreeMap::new(),
+            extensions: BTreeMap::new(),
+        })
+    }
+
+    /// Set the serial number of the certificate
+    pub fn serial(mut self, serial: u64) -> Self {
+        self.serial = serial;
+        self
+    }
+
+    /// Set the certificate type (user or host)
+    pub fn cert_type(mut self, cert_type: CertificateType) -> Self {
+        self.cert_type = cert_type;
+        self
+    }
+
+    /// Set the key identifier (logged by the SSH server on use)
+    pub fn key_id<S: Into<String>>(mut self, key_id: S) -> Self {
+        self.key_id = key_id.into();
+        self
+    }
+
+    /// Add a principal (username or hostname) this certificate is valid for
+    pub fn principal<S: Into<String>>(mut self, principal: S) -> Self {
+        self.principals.push(principal.into());
+        self
+    }
+
+    /// Set the start of the validity period (seconds since the UNIX epoch)
+    pub fn valid_after(mut self, timestamp: u64) -> Self {
+        self.valid_after = timestamp;
+        self
+    }
+
+    /// Set the end of the validity period (seconds since the UNIX epoch)
+    pub fn valid_before(mut self, timestamp: u64) -> Self {
+        self.valid_before = timestamp;
+        self
+    }
+
+    /// Add a critical option (e.g. `force-command`). Use an empty value
+    /// for options which are flags.
+    pub fn critical_option<N, V>(mut self, name: N, value: V) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.critical_options.insert(name.into(), value.into());
+        self
+    }
+
+    /// Add an extension (e.g. `permit-pty`). Use an empty value for
+    /// extensions which are flags.
+    pub fn extension<N, V>(mut self, name: N, value: V) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.extensions.insert(name.into(), value.into());
+        self
+    }
+
+    /// Get the principals this certificate is valid for
+    pub fn principals(&self) -> &[String] {
+        &self.principals
+    }
+
+    /// Is this certificate only valid for principals in the given list?
+    ///
+    /// SSH templates can only deny principals, so CAs which issue
+    /// certificates for a fixed set of principals should check requests
+    /// with this before signing them. Certificates without principals are
+    /// valid for any principal, and are never allowed.
+    pub fn are_principals_allowed<S: AsRef<str>>(&self, allowed: &[S]) -> bool {
+        !self.principals.is_empty()
+            && self.principals.iter().all(|principal| {
+                allowed
+                    .iter()
+                    .any(|allowed| allowed.as_ref() == principal.as_str())
+            })
+    }
+
+    /// Serialize the certificate body, i.e. everything but the CA signature
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut encoder = Encoder::new();
+        encoder.string(cert_key_type(&self.key_type).as_bytes());
+        encoder.string(&self.nonce);
+        encoder.raw(&self.key_fields);
+        encoder.u64(self.serial);
+        encoder.u32(self.cert_type as u32);
+        encoder.string(self.key_id.as_bytes());
+
+        let mut principals = Encoder::new();
+        for principal in &self.principals {
+            principals.string(principal.as_bytes());
+        }
+        encoder.string(&principals.into_vec());
+
+        encoder.u64(self.valid_after);
+        encoder.u64(self.valid_before);
+        encoder.string(&encode_options(&self.critical_options));
+        encoder.string(&encode_options(&self.extensions));
+
+        // Reserved
+        encoder.string(&[]);
+
+        encoder.string(&self.ca_public_key);
+        encoder.into_vec()
+    }
+
+    /// Compute the message which must be signed by the SSH template's
+    /// timestamp key to authorize this request at the given time:
+    ///
+    /// `timestamp || SHA-256(CA public key) || SHA-256(certificate body)`
+    pub fn timestamp_message(&self, timestamp: u32) -> Vec<u8> {
+        let mut message = vec![0u8; TIMESTAMP_SIZE];
+        BigEndian::write_u32(&mut message, timestamp);
+        message.extend_from_slice(Sha256::digest(&self.ca_public_key).as_slice());
+        message.extend_from_slice(Sha256::digest(&self.to_bytes()).as_slice());
+        message
+    }
+
+    /// Authorize this request with a timestamp (seconds since the UNIX epoch)
+    /// signed by the SSH template's timestamp key.
+    ///
+    /// The given function is called with the message returned by
+    /// `timestamp_message` and must return its RSASSA-PKCS#1v1.5 signature
+    /// (SHA-256) under the 2048-bit timestamp key.
+    pub fn sign_timestamp<F>(&self, timestamp: u32, sign: F) -> Result<SignedRequest, SshError>
+    where
+        F: FnOnce(&[u8]) -> Vec<u8>,
+    {
+        let timestamp_signature = sign(&self.timestamp_message(timestamp));
+
+        ensure!(
+            timestamp_signature.len() == TIMESTAMP_SIGNATURE_SIZE,
+            AlgorithmUnsupported,
+            "expected {}-byte timestamp signature (got {})",
+            TIMESTAMP_SIGNATURE_SIZE,
+            timestamp_signature.len()
+        );
+
+        Ok(SignedRequest {
+            timestamp,
+            timestamp_signature,
+            body: self.to_bytes(),
+            ca_key_type: self.ca_key_type.clone(),
+        })
+    }
+}
+
+/// Certificate request authorized by a signed timestamp, ready to be sent
+/// to the HSM with `Client::sign_ssh_certificate`.
+#[derive(Clone, Debug)]
+pub struct SignedRequest {
+    /// Time of the request (seconds since the UNIX epoch)
+    timestamp: u32,
+
+    /// Signature of the timestamp message by the template's timestamp key
+    timestamp_signature: Vec<u8>,
+
+    /// Certificate body to be signed by the CA key
+    body: Vec<u8>,
+
+    /// Public key type of the CA
+    ca_key_type: String,
+}
+
+impl SignedRequest {
+    /// Get the timestamp of this request
+    pub fn timestamp(&self) -> u32 {
+        self.timestamp
+    }
+
+    /// Get the certificate body (i.e. the data the CA signs)
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Get the public key type of the CA (e.g. `ssh-ed25519`)
+    pub fn ca_key_type(&self) -> &str {
+        &self.ca_key_type
+    }
+
+    /// Serialize this request in the format expected by the HSM:
+    /// `timestamp || timestamp signature || certificate body`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0u8; TIMESTAMP_SIZE];
+        BigEndian::write_u32(&mut bytes, self.timestamp);
+        bytes.extend_from_slice(&self.timestamp_signature);
+        bytes.extend_from_slice(&self.body);
+        bytes
+    }
+}
+
+/// Parse an SSH public key into its key type and its remaining fields
+fn parse_public_key(public_key: &[u8]) -> Result<(String, &[u8]), SshError> {
+    let mut decoder = Decoder::new(public_key);
+    let key_type = str::from_utf8(decoder.string()?).unwrap_or("");
+
+    if !SUPPORTED_KEY_TYPES.contains(&key_type) {
+        fail!(
+            AlgorithmUnsupported,
+            "unsupported SSH key type: {:?}",
+            key_type
+        );
+    }
+
+    Ok((key_type.to_owned(), decoder.remaining()))
+}
+
+/// Get the certificate type name for a given public key type
+fn cert_key_type(key_type: &str) -> String {
+    format!("{}-cert-v01@openssh.com", key_type)
+}
+
+/// Encode critical options or extensions
+fn encode_options(options: &BTreeMap<String, String>) -> Vec<u8> {
+    let mut encoder = Encoder::new();
+
+    for (name, value) in options {
+        encoder.string(name.as_bytes());
+
+        // Non-empty values are themselves encoded as strings
+        if value.is_empty() {
+            encoder.string(&[]);
+        } else {
+            let mut data = Encoder::new();
+            data.string(value.as_bytes());
+            encoder.string(&data.into_vec());
+        }
+    }
+
+    encoder.into_vec()
+}
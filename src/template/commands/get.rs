@@ -0,0 +1,35 @@
+//! Get an SSH template stored in the device
+//!
+//! <https://developers.yubico.com/YubiHSM2/Commands/Get_Template.html>
+
+use crate::{
+    command::{self, Command},
+    object,
+    response::Response,
+    template,
+};
+
+/// Request parameters for `command::get_template`
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct GetTemplateCommand {
+    /// Object ID of the template to obtain
+    pub object_id: object::Id,
+}
+
+impl Command for GetTemplateCommand {
+    type ResponseType = GetTemplateResponse;
+}
+
+/// Response from `command::get_template`
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct GetTemplateResponse {
+    /// Algorithm of the template
+    pub algorithm: template::Algorithm,
+
+    /// Serialized template
+    pub data: Vec<u8>,
+}
+
+impl Response for GetTemplateResponse {
+    const COMMAND_CODE: command::Code = command::Code::GetTemplate;
+}
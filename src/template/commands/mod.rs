@@ -0,0 +1,6 @@
+//! Commands for working with SSH templates
+
+mod get;
+mod put;
+
+pub(crate) use self::{get::*, put::*};
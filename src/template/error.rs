@@ -0,0 +1,22 @@
+//! Template errors
+
+use crate::error::Error;
+
+/// SSH template errors
+pub type TemplateError = Error<TemplateErrorKind>;
+
+/// SSH template error kinds
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Fail)]
+pub enum TemplateErrorKind {
+    /// Malformed template data
+    #[fail(display = "invalid format")]
+    FormatInvalid,
+
+    /// Unknown TLV tag in template data
+    #[fail(display = "invalid tag")]
+    TagInvalid,
+
+    /// Missing required TLV in template data
+    #[fail(display = "missing field")]
+    FieldMissing,
+}
@@ -1,5 +1,12 @@
 //! SSH certificate templates
 
 mod algorithm;
+pub(crate) mod commands;
+mod error;
+mod ssh;
 
-pub use self::algorithm::Algorithm;
+pub use self::{
+    algorithm::Algorithm,
+    error::{TemplateError, TemplateErrorKind},
+    ssh::SshTemplate,
+};
@@ -0,0 +1,266 @@
+//! SSH certificate templates: constraints on which certificates an SSH CA
+//! key stored in the HSM is allowed to sign.
+//!
+//! Templates are serialized as a sequence of TLV (tag, 16-bit big endian
+//! length, value) fields.
+//!
+//! <https://developers.yubico.com/YubiHSM2/Usage_Guides/YubiHSM2_for_SSH-CA.html>
+
+use super::{
+    TemplateError,
+    TemplateErrorKind::{FieldMissing, FormatInvalid, TagInvalid},
+};
+use crate::{asymmetric, object};
+use byteorder::{BigEndian, ByteOrder};
+use std::{str, time::Duration};
+
+/// Algorithm of the timestamp key
+const TIMESTAMP_KEY_ALGORITHM_TAG: u8 = 0x01;
+
+/// Public key (i.e. RSA modulus) of the timestamp key
+const TIMESTAMP_PUBLIC_KEY_TAG: u8 = 0x02;
+
+/// IDs of CA keys which may be used with this template
+const CA_KEY_IDS_TAG: u8 = 0x03;
+
+/// Maximum number of seconds a certificate may be valid before the timestamp
+const NOT_BEFORE_TAG: u8 = 0x04;
+
+/// Maximum number of seconds a certificate may be valid after the timestamp
+const NOT_AFTER_TAG: u8 = 0x05;
+
+/// Principals which may not appear in certificates
+const DENIED_PRINCIPALS_TAG: u8 = 0x06;
+
+/// SSH certificate template
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SshTemplate {
+    /// Public key used to verify request timestamps
+    timestamp_key: asymmetric::PublicKey,
+
+    /// IDs of CA keys which may sign certificates using this template
+    ca_key_ids: Vec<object::Id>,
+
+    /// Maximum number of seconds a certificate may be valid before the timestamp
+    not_before: u32,
+
+    /// Maximum number of seconds a certificate may be valid after the timestamp
+    not_after: u32,
+
+    /// Principals which may not appear in certificates
+    denied_principals: Vec<String>,
+}
+
+impl SshTemplate {
+    /// Create a new template for requests authorized by the given timestamp
+    /// key (RSA-2048). Certificates signed using this template must be valid
+    /// only at the time of the request until CA keys and durations are added.
+    pub fn new(timestamp_key: asymmetric::PublicKey) -> Self {
+        Self {
+            timestamp_key,
+            ca_key_ids: vec![],
+            not_before: 0,
+            not_after: 0,
+            denied_principals: vec![],
+        }
+    }
+
+    /// Parse a serialized template
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, TemplateError> {
+        let mut timestamp_key_algorithm = None;
+        let mut timestamp_public_key = None;
+        let mut template = Self::new(asymmetric::PublicKey {
+            algorithm: asymmetric::Algorithm::RSA_2048,
+            bytes: vec![],
+        });
+
+        let mut remaining = bytes;
+
+        while !remaining.is_empty() {
+            ensure!(remaining.len() >= 3, FormatInvalid, "truncated TLV header");
+
+            let tag = remaining[0];
+            let len = BigEndian::read_u16(&remaining[1..3]) as usize;
+            ensure!(
+                remaining.len() >= 3 + len,
+                FormatInvalid,
+                "truncated TLV value (tag: 0x{:02x})",
+                tag
+            );
+
+            let value = &remaining[3..3 + len];
+            remaining = &remaining[3 + len..];
+
+            match tag {
+                TIMESTAMP_KEY_ALGORITHM_TAG => {
+                    ensure!(len == 1, FormatInvalid, "bad timestamp key algorithm");
+                    timestamp_key_algorithm = Some(
+                        asymmetric::Algorithm::from_u8(value[0])
+                            .map_err(|e| err!(FormatInvalid, e))?,
+                    );
+                }
+                TIMESTAMP_PUBLIC_KEY_TAG => timestamp_public_key = Some(value.to_vec()),
+                CA_KEY_IDS_TAG => {
+                    ensure!(len % 2 == 0, FormatInvalid, "bad CA key ID list");
+                    template.ca_key_ids = value.chunks(2).map(BigEndian::read_u16).collect();
+                }
+                NOT_BEFORE_TAG => template.not_before = parse_u32(value)?,
+                NOT_AFTER_TAG => template.not_after = parse_u32(value)?,
+                DENIED_PRINCIPALS_TAG => {
+                    for principal in value.split(|&byte| byte == 0) {
+                        if !principal.is_empty() {
+                            template.denied_principals.push(
+                                str::from_utf8(principal)
+                                    .map_err(|e| err!(FormatInvalid, e))?
+                                    .to_owned(),
+                            );
+                        }
+                    }
+                }
+                _ => fail!(TagInvalid, "unknown SSH template tag: 0x{:02x}", tag),
+            }
+        }
+
+        match (timestamp_key_algorithm, timestamp_public_key) {
+            (Some(algorithm), Some(bytes)) => {
+                ensure!(
+                    algorithm == asymmetric::Algorithm::RSA_2048
+                        && bytes.len() == algorithm.key_len(),
+                    FormatInvalid,
+                    "timestamp key must be an RSA-2048 public key"
+                );
+
+                template.timestamp_key = asymmetric::PublicKey { algorithm, bytes }
+            }
+            _ => fail!(FieldMissing, "missing timestamp key"),
+        }
+
+        Ok(template)
+    }
+
+    /// Allow the CA key with the given ID to sign certificates using this template
+    pub fn ca_key_id(mut self, key_id: object::Id) -> Self {
+        self.ca_key_ids.push(key_id);
+        self
+    }
+
+    /// Set how long before the request timestamp certificates may be valid
+    pub fn not_before(mut self, duration: Duration) -> Self {
+        self.not_before = duration_secs(duration);
+        self
+    }
+
+    /// Set how long after the request timestamp certificates may be valid
+    pub fn not_after(mut self, duration: Duration) -> Self {
+        self.not_after = duration_secs(duration);
+        self
+    }
+
+    /// Forbid certificates containing the given principal.
+    ///
+    /// The device has no allow-list of principals: to restrict a request to
+    /// certain principals, see `ssh::CertificateRequest::are_principals_allowed`.
+    pub fn denied_principal<S: Into<String>>(mut self, principal: S) -> Self {
+        self.denied_principals.push(principal.into());
+        self
+    }
+
+    /// Get the public key used to verify request timestamps
+    pub fn timestamp_key(&self) -> &asymmetric::PublicKey {
+        &self.timestamp_key
+    }
+
+    /// Get the IDs of the CA keys allowed to sign certificates using this template
+    pub fn ca_key_ids(&self) -> &[object::Id] {
+        &self.ca_key_ids
+    }
+
+    /// Get how long before the request timestamp certificates may be valid
+    pub fn not_before_duration(&self) -> Duration {
+        Duration::from_secs(u64::from(self.not_before))
+    }
+
+    /// Get how long after the request timestamp certificates may be valid
+    pub fn not_after_duration(&self) -> Duration {
+        Duration::from_secs(u64::from(self.not_after))
+    }
+
+    /// Get the principals which may not appear in certificates
+    pub fn denied_principals(&self) -> &[String] {
+        &self.denied_principals
+    }
+
+    /// Serialize this template
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        push_tlv(
+            &mut bytes,
+            TIMESTAMP_KEY_ALGORITHM_TAG,
+            &[self.timestamp_key.algorithm.to_u8()],
+        );
+        push_tlv(
+            &mut bytes,
+            TIMESTAMP_PUBLIC_KEY_TAG,
+            &self.timestamp_key.bytes,
+        );
+
+        let mut ca_key_ids = vec![0u8; self.ca_key_ids.len() * 2];
+        for (chunk, &key_id) in ca_key_ids.chunks_mut(2).zip(&self.ca_key_ids) {
+            BigEndian::write_u16(chunk, key_id);
+        }
+        push_tlv(&mut bytes, CA_KEY_IDS_TAG, &ca_key_ids);
+
+        let mut duration = [0u8; 4];
+        BigEndian::write_u32(&mut duration, self.not_before);
+        push_tlv(&mut bytes, NOT_BEFORE_TAG, &duration);
+        BigEndian::write_u32(&mut duration, self.not_after);
+        push_tlv(&mut bytes, NOT_AFTER_TAG, &duration);
+
+        if !self.denied_principals.is_empty() {
+            let mut principals = vec![];
+            for principal in &self.denied_principals {
+                principals.extend_from_slice(principal.as_bytes());
+                principals.push(0);
+            }
+            push_tlv(&mut bytes, DENIED_PRINCIPALS_TAG, &principals);
+        }
+
+        bytes
+    }
+}
+
+impl Into<Vec<u8>> for SshTemplate {
+    fn into(self) -> Vec<u8> {
+        self.to_bytes()
+    }
+}
+
+/// Append a TLV field to a serialized template
+fn push_tlv(bytes: &mut Vec<u8>, tag: u8, value: &[u8]) {
+    let mut len = [0u8; 2];
+    BigEndian::write_u16(&mut len, value.len() as u16);
+    bytes.push(tag);
+    bytes.extend_from_slice(&len);
+    bytes.extend_from_slice(value);
+}
+
+/// Parse a 32-bit big endian integer field
+fn parse_u32(value: &[u8]) -> Result<u32, TemplateError> {
+    ensure!(
+        value.len() == 4,
+        FormatInvalid,
+        "expected 4-byte integer, got {}",
+        value.len()
+    );
+
+    Ok(BigEndian::read_u32(value))
+}
+
+/// Convert a duration to seconds, saturating at `u32::max_value()`
+fn duration_secs(duration: Duration) -> u32 {
+    if duration.as_secs() > u64::from(u32::max_value()) {
+        u32::max_value()
+    } else {
+        duration.as_secs() as u32
+    }
+}
@@ -0,0 +1,18 @@
+//! Wrap errors
+
+use crate::error::Error;
+
+/// `wrap::Message` decryption errors
+pub type WrapError = Error<WrapErrorKind>;
+
+/// Kinds of `wrap::Message` decryption errors
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Fail)]
+pub enum WrapErrorKind {
+    /// Message failed to authenticate (e.g. wrong wrap key or corrupted message)
+    #[fail(display = "authentication failed")]
+    AuthenticationFailed,
+
+    /// Decrypted message is malformed
+    #[fail(display = "invalid format")]
+    FormatInvalid,
+}
@@ -1,5 +1,15 @@
-use super::nonce::{self, Nonce};
+use super::{
+    nonce::{self, Nonce},
+    Key, Plaintext, WrapError,
+    WrapErrorKind::{AuthenticationFailed, FormatInvalid},
+    MAC_SIZE,
+};
+use crate::{
+    ccm::Ccm,
+    serialization::{deserialize, serialize},
+};
 use failure::Error;
+use zeroize::Zeroize;
 
 /// Wrap wessage (encrypted HSM object or arbitrary data) encrypted under a wrap key
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -36,6 +46,35 @@ impl Message {
         }
     }
 
+    /// Encrypt an object in software under the given wrap key (using a
+    /// random nonce), producing a message which can be imported into any HSM
+    /// holding the same key with `Client::import_wrapped`.
+    pub fn encrypt(key: &Key, plaintext: &Plaintext) -> Self {
+        let cipher = Ccm::new(&key.data, MAC_SIZE).expect("invalid wrap key");
+        let nonce = Nonce::generate();
+
+        let mut serialized = serialize(plaintext).unwrap();
+        let ciphertext = cipher.seal(nonce.as_ref(), &[], &serialized);
+        serialized.zeroize();
+
+        Self::new(nonce, ciphertext)
+    }
+
+    /// Decrypt this message in software using the given wrap key, e.g. to
+    /// recover an exported object from an escrowed wrap key if no HSM holding
+    /// the key is available.
+    pub fn decrypt(&self, key: &Key) -> Result<Plaintext, WrapError> {
+        let cipher = Ccm::new(&key.data, MAC_SIZE).expect("invalid wrap key");
+
+        let mut plaintext = cipher
+            .open(self.nonce.as_ref(), &[], &self.ciphertext)
+            .ok_or_else(|| err!(AuthenticationFailed, "error decrypting wrapped object"))?;
+
+        let result = deserialize(&plaintext).map_err(|e| err!(FormatInvalid, e));
+        plaintext.zeroize();
+        result
+    }
+
     /// Convert this message into a byte vector
     pub fn into_vec(self) -> Vec<u8> {
         self.into()
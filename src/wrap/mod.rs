@@ -3,8 +3,20 @@
 
 mod algorithm;
 pub(crate) mod commands;
+mod error;
 mod key;
 mod message;
 mod nonce;
+mod plaintext;
 
-pub use self::{algorithm::Algorithm, key::Key, message::Message, nonce::Nonce};
+pub use self::{
+    algorithm::Algorithm,
+    error::{WrapError, WrapErrorKind},
+    key::Key,
+    message::Message,
+    nonce::Nonce,
+    plaintext::Plaintext,
+};
+
+/// Size of the AES-CCM MAC tag on wrapped objects
+pub(crate) const MAC_SIZE: usize = 16;
@@ -1,6 +1,5 @@
 //! Nonces used by the YubiHSM 2's AES-CCM encrypted `wrap::Message`
 
-#[cfg(feature = "mockhsm")]
 use rand_os::{rand_core::RngCore, OsRng};
 
 /// Number of bytes in a nonce used for "wrapping" (i.e AES-CCM encryption)
@@ -12,7 +11,6 @@ pub struct Nonce(pub [u8; SIZE]);
 
 impl Nonce {
     /// Generate a random `wrap::Nonce`
-    #[cfg(feature = "mockhsm")]
     pub fn generate() -> Self {
         let mut rand = OsRng::new().unwrap();
         let mut bytes = [0u8; SIZE];
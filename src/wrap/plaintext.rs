@@ -0,0 +1,64 @@
+//! Decrypted contents of a `wrap::Message`
+
+use crate::{object, Capability};
+use std::fmt::{self, Debug};
+use zeroize::Zeroize;
+
+/// Plaintext of a wrapped object: its metadata followed by its key material
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Plaintext {
+    /// Information about the wrapped object
+    pub object_info: object::Info,
+
+    /// Serialized object data (i.e. key material)
+    pub data: Vec<u8>,
+}
+
+impl Plaintext {
+    /// Create the plaintext of an object to be imported with the given
+    /// parameters and key material, e.g. to encrypt a key generated outside
+    /// of an HSM with `wrap::Message::encrypt`
+    pub fn new<V>(
+        object_type: object::Type,
+        params: object::import::Params,
+        delegated_capabilities: Capability,
+        data: V,
+    ) -> Self
+    where
+        V: Into<Vec<u8>>,
+    {
+        let data = data.into();
+
+        let object_info = object::Info {
+            capabilities: params.capabilities,
+            object_id: params.id,
+            length: data.len() as u16,
+            domains: params.domains,
+            object_type,
+            algorithm: params.algorithm,
+            sequence: 0,
+            origin: object::Origin::WrappedImported,
+            label: params.label,
+            delegated_capabilities,
+        };
+
+        Self { object_info, data }
+    }
+}
+
+impl Debug for Plaintext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Avoid leaking secrets in debug messages
+        write!(
+            f,
+            "yubihsm::wrap::Plaintext {{ object_info: {:?}, data: ... }}",
+            self.object_info
+        )
+    }
+}
+
+impl Drop for Plaintext {
+    fn drop(&mut self) {
+        self.data.zeroize();
+    }
+}
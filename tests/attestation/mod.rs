@@ -0,0 +1,113 @@
+use crate::test_vectors::{
+    ATTESTATION_OTHER_ROOT_PEM, ATTESTATION_ROOT_PEM, ATTESTATION_SAME_NAME_ROOT_PEM,
+    DEVICE_ATTESTATION_PEM, KEY_ATTESTATION_PEM, P256_PUBLIC_KEY,
+};
+use yubihsm::{
+    asymmetric,
+    attestation::{AttestationErrorKind, Certificate},
+    object, Capability, Domain,
+};
+
+#[test]
+fn parse_test() {
+    let certificate = Certificate::from_pem(KEY_ATTESTATION_PEM).unwrap();
+    let info = certificate.parse().unwrap();
+
+    assert_eq!(info.subject, "CN=YubiHSM Attestation id:0x0064");
+    assert_eq!(info.issuer, "CN=YubiHSM Attestation (0012d687)");
+    assert_eq!(info.public_key.algorithm, asymmetric::Algorithm::EC_P256);
+    assert_eq!(info.public_key.as_slice(), P256_PUBLIC_KEY);
+    assert_eq!(info.firmware_version, Some((2, 0, 0)));
+    assert_eq!(info.serial_number.unwrap().to_string(), "0001234567");
+    assert_eq!(info.origin, Some(object::Origin::Generated));
+    assert_eq!(info.domains, Some(Domain::DOM1));
+    assert_eq!(
+        info.capabilities,
+        Some(Capability::SIGN_ECDSA | Capability::EXPORTABLE_UNDER_WRAP)
+    );
+    assert_eq!(info.object_id, Some(100));
+    assert_eq!(info.label.unwrap().to_string(), "attestation test");
+
+    assert_eq!(certificate.to_pem(), KEY_ATTESTATION_PEM);
+}
+
+#[test]
+fn verify_test() {
+    let certificate = Certificate::from_pem(KEY_ATTESTATION_PEM).unwrap();
+    let device_certificate = Certificate::from_pem(DEVICE_ATTESTATION_PEM).unwrap();
+    let roots =
+        Certificate::from_pem_bundle(&[ATTESTATION_OTHER_ROOT_PEM, ATTESTATION_ROOT_PEM].concat())
+            .unwrap();
+
+    let info = certificate.verify(&device_certificate, &roots).unwrap();
+    assert_eq!(info.object_id, Some(100));
+    assert_eq!(info.public_key.as_slice(), P256_PUBLIC_KEY);
+
+    // The device certificate carries no YubiHSM 2 extensions
+    let device_info = device_certificate.parse().unwrap();
+    assert_eq!(
+        device_info.issuer,
+        "C=US, O=Example Test Org, CN=Test YubiHSM Attestation Root"
+    );
+    assert!(device_info.object_id.is_none());
+}
+
+#[test]
+fn same_name_roots_test() {
+    let certificate = Certificate::from_pem(KEY_ATTESTATION_PEM).unwrap();
+    let device_certificate = Certificate::from_pem(DEVICE_ATTESTATION_PEM).unwrap();
+    let roots = Certificate::from_pem_bundle(
+        &[ATTESTATION_SAME_NAME_ROOT_PEM, ATTESTATION_ROOT_PEM].concat(),
+    )
+    .unwrap();
+
+    // The first root has a matching name, but didn't sign the device certificate
+    let info = certificate.verify(&device_certificate, &roots).unwrap();
+    assert_eq!(info.object_id, Some(100));
+
+    let same_name_root = Certificate::from_pem(ATTESTATION_SAME_NAME_ROOT_PEM).unwrap();
+    let err = certificate
+        .verify(&device_certificate, &[same_name_root])
+        .unwrap_err();
+    assert_eq!(err.kind(), AttestationErrorKind::SignatureInvalid);
+}
+
+#[test]
+fn invalid_chain_test() {
+    let certificate = Certificate::from_pem(KEY_ATTESTATION_PEM).unwrap();
+    let device_certificate = Certificate::from_pem(DEVICE_ATTESTATION_PEM).unwrap();
+    let root = Certificate::from_pem(ATTESTATION_ROOT_PEM).unwrap();
+    let other_root = Certificate::from_pem(ATTESTATION_OTHER_ROOT_PEM).unwrap();
+
+    let err = certificate
+        .verify(&device_certificate, &[other_root])
+        .unwrap_err();
+    assert_eq!(err.kind(), AttestationErrorKind::ChainInvalid);
+
+    // Attestation certificate in place of the device certificate
+    let err = certificate
+        .verify(&certificate, &[Certificate(root.as_slice().into())])
+        .unwrap_err();
+    assert_eq!(err.kind(), AttestationErrorKind::ChainInvalid);
+
+    // Corrupt the last byte of the attestation certificate's signature
+    let mut tampered = certificate.into_vec();
+    *tampered.last_mut().unwrap() ^= 1;
+
+    let err = Certificate(tampered)
+        .verify(&device_certificate, &[root])
+        .unwrap_err();
+    assert_eq!(err.kind(), AttestationErrorKind::SignatureInvalid);
+}
+
+#[test]
+fn invalid_format_test() {
+    let err = Certificate::from_pem(&[ATTESTATION_ROOT_PEM, DEVICE_ATTESTATION_PEM].concat())
+        .unwrap_err();
+    assert_eq!(err.kind(), AttestationErrorKind::FormatInvalid);
+
+    let err = Certificate(vec![0x30, 0x03, 0x02, 0x01, 0x00])
+        .parse()
+        .unwrap_err();
+    assert_eq!(err.kind(), AttestationErrorKind::FormatInvalid);
+}
@@ -0,0 +1,157 @@
+use std::{
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+use yubihsm::{
+    audit::{AuditError, AuditErrorKind, AuditOption, Drainer, LogEntry, Sink},
+    command,
+    device::DeviceErrorKind,
+    Client, Connector,
+};
+
+/// Sink which keeps flushed entries in memory
+#[derive(Default)]
+struct MemorySink {
+    /// Entries which have been appended but not flushed
+    pending: Vec<LogEntry>,
+
+    /// Entries which have been flushed
+    flushed: Arc<Mutex<Vec<LogEntry>>>,
+
+    /// Simulate a failing flush
+    fail_flush: Arc<Mutex<bool>>,
+}
+
+impl Sink for MemorySink {
+    fn append(&mut self, entries: &[LogEntry]) -> Result<(), AuditError> {
+        self.pending.extend_from_slice(entries);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), AuditError> {
+        if *self.fail_flush.lock().unwrap() {
+            self.pending.clear();
+            return Err(AuditError::new(AuditErrorKind::IoError, None));
+        }
+
+        self.flushed.lock().unwrap().append(&mut self.pending);
+        Ok(())
+    }
+}
+
+/// Create a client for a fresh MockHsm, so other tests don't touch its log
+fn mockhsm_client() -> Client {
+    Client::open(Connector::mockhsm(), Default::default(), true).unwrap()
+}
+
+#[test]
+fn drain_test() {
+    let client = mockhsm_client();
+    let sink = MemorySink::default();
+    let flushed = Arc::clone(&sink.flushed);
+    let drainer = Drainer::new(client.clone(), sink, None);
+
+    for _ in 0..3 {
+        client.get_pseudo_random(32).unwrap();
+    }
+
+    assert_eq!(drainer.drain().unwrap(), 3);
+    assert_eq!(drainer.drain().unwrap(), 0);
+
+    {
+        let entries = flushed.lock().unwrap();
+        assert_eq!(entries.len(), 3);
+        assert!(entries
+            .iter()
+            .all(|entry| entry.cmd == command::Code::GetPseudoRandom));
+        assert_eq!(
+            drainer.checkpoint().map(|checkpoint| checkpoint.item),
+            Some(entries[2].item)
+        );
+    }
+
+    // Drained entries have been acknowledged
+    assert!(client.get_log_entries().unwrap().entries.is_empty());
+
+    client.get_storage_info().unwrap();
+    assert_eq!(drainer.drain().unwrap(), 1);
+    assert_eq!(flushed.lock().unwrap().len(), 4);
+}
+
+#[test]
+fn failed_flush_test() {
+    let client = mockhsm_client();
+    let sink = MemorySink::default();
+    let flushed = Arc::clone(&sink.flushed);
+    let fail_flush = Arc::clone(&sink.fail_flush);
+    let drainer = Drainer::new(client.clone(), sink, None);
+
+    client.get_pseudo_random(32).unwrap();
+    *fail_flush.lock().unwrap() = true;
+
+    let err = drainer.drain().unwrap_err();
+    assert_eq!(err.kind(), AuditErrorKind::IoError);
+    assert!(drainer.checkpoint().is_none());
+
+    // Entries must not be acknowledged until they've been flushed
+    assert_eq!(client.get_log_entries().unwrap().entries.len(), 1);
+
+    *fail_flush.lock().unwrap() = false;
+    assert_eq!(drainer.drain().unwrap(), 1);
+    assert_eq!(flushed.lock().unwrap().len(), 1);
+    assert!(client.get_log_entries().unwrap().entries.is_empty());
+}
+
+#[test]
+fn background_drain_test() {
+    let client = mockhsm_client();
+    let sink = MemorySink::default();
+    let flushed = Arc::clone(&sink.flushed);
+    let fail_flush = Arc::clone(&sink.fail_flush);
+    let drainer = Drainer::new(client.clone(), sink, None);
+
+    client.get_pseudo_random(32).unwrap();
+    *fail_flush.lock().unwrap() = true;
+
+    let (errors_tx, errors_rx) = mpsc::channel();
+    let handle = drainer.spawn(Duration::from_millis(10), errors_tx);
+
+    // Errors are reported to the caller...
+    let err = errors_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(err.kind(), AuditErrorKind::IoError);
+
+    // ...and draining continues once they're resolved
+    *fail_flush.lock().unwrap() = false;
+    let deadline = Instant::now() + Duration::from_secs(5);
+
+    while flushed.lock().unwrap().is_empty() {
+        assert!(Instant::now() < deadline, "background draining stopped");
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    drainer.stop();
+    handle.join().unwrap();
+    assert!(client.get_log_entries().unwrap().entries.is_empty());
+}
+
+#[test]
+fn force_audit_test() {
+    let client = mockhsm_client();
+    client.set_force_audit_option(AuditOption::On).unwrap();
+
+    let mut result = Ok(vec![]);
+
+    while result.is_ok() {
+        result = client.get_pseudo_random(1);
+    }
+
+    assert_eq!(
+        result.unwrap_err().kind().device_error(),
+        Some(DeviceErrorKind::LogFull)
+    );
+
+    let drainer = Drainer::new(client.clone(), MemorySink::default(), None);
+    assert!(drainer.drain().unwrap() > 0);
+    client.get_pseudo_random(1).unwrap();
+}
@@ -0,0 +1,184 @@
+use ring::digest;
+use yubihsm::{
+    audit::{
+        self, analysis::Analysis, AuditErrorKind, Checkpoint, LogDigest, LogEntries, LogEntry,
+        LOG_DIGEST_SIZE,
+    },
+    command, response,
+};
+
+/// Audit log drainer tests
+#[cfg(feature = "mockhsm")]
+mod drainer;
+
+/// Command logged in synthetic log entries
+const LOGGED_COMMAND: command::Code = command::Code::SignEddsa;
+
+/// Build a chain of synthetic log entries starting at the given item number,
+/// computing digests independently of `LogEntry::compute_digest`
+fn log_entries(first_item: u16, count: u16, previous_digest: LogDigest) -> LogEntries {
+    let mut entries = vec![];
+    let mut previous_digest = previous_digest;
+
+    for item in first_item..(first_item + count) {
+        let entry = LogEntry {
+            item,
+            cmd: LOGGED_COMMAND,
+            length: 64,
+            session_key: 1,
+            target_key: 100,
+            second_key: 0xffff,
+            result: response::Code::Success(LOGGED_COMMAND),
+            tick: u32::from(item) * 1000,
+            digest: previous_digest,
+        };
+
+        let mut data = vec![];
+        push_be(&mut data, entry.item.into(), 2);
+        data.push(entry.cmd.to_u8());
+        push_be(&mut data, entry.length.into(), 2);
+        push_be(&mut data, entry.session_key.into(), 2);
+        push_be(&mut data, entry.target_key.into(), 2);
+        push_be(&mut data, entry.second_key.into(), 2);
+        data.push(entry.result.to_u8());
+        push_be(&mut data, entry.tick, 4);
+        data.extend_from_slice(&previous_digest.0);
+
+        let mut digest_bytes = [0u8; LOG_DIGEST_SIZE];
+        digest_bytes
+            .copy_from_slice(&digest::digest(&digest::SHA256, &data).as_ref()[..LOG_DIGEST_SIZE]);
+        previous_digest = LogDigest(digest_bytes);
+
+        entries.push(LogEntry {
+            digest: previous_digest,
+            ..entry
+        });
+    }
+
+    LogEntries {
+        unlogged_boot_events: 0,
+        unlogged_auth_events: 0,
+        num_entries: entries.len() as u8,
+        entries,
+    }
+}
+
+/// Append a big endian integer of the given size in bytes
+fn push_be(data: &mut Vec<u8>, value: u32, size: usize) {
+    for i in (0..size).rev() {
+        data.push((value >> (i * 8)) as u8);
+    }
+}
+
+#[test]
+fn verify_chain_test() {
+    let log = log_entries(1, 10, LogDigest([0u8; LOG_DIGEST_SIZE]));
+
+    let checkpoint = audit::verify_chain(None, &log).unwrap().unwrap();
+    assert_eq!(checkpoint, Checkpoint::from(&log.entries[9]));
+
+    // Entries which haven't been acknowledged are returned again
+    let next = log_entries(11, 5, checkpoint.digest);
+    let mut overlapping = log.clone();
+    overlapping.entries.extend_from_slice(&next.entries);
+
+    let next_checkpoint = audit::verify_chain(Some(&checkpoint), &overlapping)
+        .unwrap()
+        .unwrap();
+    assert_eq!(next_checkpoint, Checkpoint::from(&next.entries[4]));
+
+    assert_eq!(
+        audit::verify_chain(Some(&checkpoint), &next).unwrap(),
+        Some(next_checkpoint)
+    );
+
+    let empty = log_entries(1, 0, checkpoint.digest);
+    assert_eq!(audit::verify_chain(None, &empty).unwrap(), None);
+    assert_eq!(
+        audit::verify_chain(Some(&checkpoint), &empty).unwrap(),
+        Some(checkpoint)
+    );
+}
+
+#[test]
+fn compute_digest_test() {
+    let log = log_entries(1, 2, LogDigest([0u8; LOG_DIGEST_SIZE]));
+    let expected = log.entries[1].digest;
+    assert_eq!(
+        log.entries[1].compute_digest(&log.entries[0].digest),
+        expected
+    );
+}
+
+#[test]
+fn tampered_entry_test() {
+    let mut log = log_entries(1, 10, LogDigest([0u8; LOG_DIGEST_SIZE]));
+    log.entries[5].target_key = 200;
+
+    let err = audit::verify_chain(None, &log).unwrap_err();
+    assert_eq!(err.kind(), AuditErrorKind::DigestMismatch);
+
+    // Checkpoint from a different chain
+    let log = log_entries(1, 10, LogDigest([0u8; LOG_DIGEST_SIZE]));
+    let other = log_entries(1, 10, LogDigest([1u8; LOG_DIGEST_SIZE]));
+    let checkpoint = Checkpoint::from(&other.entries[4]);
+
+    let err = audit::verify_chain(Some(&checkpoint), &log).unwrap_err();
+    assert_eq!(err.kind(), AuditErrorKind::DigestMismatch);
+}
+
+#[test]
+fn gap_test() {
+    let mut log = log_entries(1, 10, LogDigest([0u8; LOG_DIGEST_SIZE]));
+    log.entries.remove(5);
+
+    let err = audit::verify_chain(None, &log).unwrap_err();
+    assert_eq!(err.kind(), AuditErrorKind::Gap);
+
+    let log = log_entries(1, 10, LogDigest([0u8; LOG_DIGEST_SIZE]));
+    let checkpoint = Checkpoint::from(&log.entries[9]);
+    let later = log_entries(20, 5, checkpoint.digest);
+
+    let err = audit::verify_chain(Some(&checkpoint), &later).unwrap_err();
+    assert_eq!(err.kind(), AuditErrorKind::Gap);
+}
+
+#[test]
+fn reset_test() {
+    let log = log_entries(1, 10, LogDigest([0u8; LOG_DIGEST_SIZE]));
+    let checkpoint = Checkpoint::from(&log.entries[9]);
+    let after_reset = log_entries(1, 3, LogDigest([0u8; LOG_DIGEST_SIZE]));
+
+    let err = audit::verify_chain(Some(&checkpoint), &after_reset).unwrap_err();
+    assert_eq!(err.kind(), AuditErrorKind::Reset);
+}
+
+#[test]
+fn analysis_test() {
+    let mut log = log_entries(1, 10, LogDigest([0u8; LOG_DIGEST_SIZE]));
+    log.entries[3].session_key = 2;
+    log.entries[4].result = response::Code::DeviceInvalidData;
+    log.entries[7].cmd = command::Code::GetPseudoRandom;
+    log.entries[7].target_key = 0xffff;
+
+    let analysis: Analysis = log.entries.iter().collect();
+
+    let session_usage = &analysis.session_keys[&1];
+    assert_eq!(session_usage.total_operations(), 9);
+    assert_eq!(session_usage.operations[&LOGGED_COMMAND], 8);
+    assert_eq!(session_usage.operations[&command::Code::GetPseudoRandom], 1);
+    assert_eq!(session_usage.first_tick, 1000);
+    assert_eq!(session_usage.last_tick, 10_000);
+
+    let target_usage = &analysis.target_keys[&100];
+    assert_eq!(target_usage.total_operations(), 9);
+    assert_eq!(target_usage.total_failures(), 1);
+    assert_eq!(target_usage.failures[&response::Code::DeviceInvalidData], 1);
+    assert!(!analysis.target_keys.contains_key(&0xffff));
+
+    assert_eq!(
+        analysis.session_keys_for(100, LOGGED_COMMAND),
+        vec![(1, 8), (2, 1)]
+    );
+    assert_eq!(analysis.key_pairs[&(2, 100)].first_tick, 4000);
+}
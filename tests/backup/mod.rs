@@ -0,0 +1,321 @@
+use yubihsm::{
+    asymmetric,
+    backup::{self, Archive, BackupErrorKind, Outcome, SkipReason},
+    object, wrap, Capability, Client, Connector, Domain,
+};
+
+/// Key ID of the wrap key used for backups
+const BACKUP_WRAP_KEY_ID: object::Id = 0x0200;
+
+/// Key material for the backup wrap key
+const BACKUP_WRAP_KEY: [u8; 32] = [0x42; 32];
+
+/// Key ID of the wrap key backups are rotated to
+const ROTATED_WRAP_KEY_ID: object::Id = 0x0201;
+
+/// Key material for the rotated wrap key
+const ROTATED_WRAP_KEY: [u8; 32] = [0x43; 32];
+
+/// ID of the exportable key to back up
+const EXPORTABLE_KEY_ID: object::Id = 100;
+
+/// ID of the key which isn't exportable
+const NON_EXPORTABLE_KEY_ID: object::Id = 101;
+
+/// Create a client for a fresh MockHsm containing the backup wrap key
+fn mockhsm_client() -> Client {
+    let client = Client::open(Connector::mockhsm(), Default::default(), true).unwrap();
+    put_wrap_key(&client, BACKUP_WRAP_KEY_ID, BACKUP_WRAP_KEY);
+    client
+}
+
+/// Put a wrap key which can be used for backups into the HSM
+fn put_wrap_key(client: &Client, key_id: object::Id, key: [u8; 32]) {
+    client
+        .put_wrap_key(
+            key_id,
+            "backup wrap key".into(),
+            Domain::all(),
+            Capability::EXPORT_WRAPPED | Capability::IMPORT_WRAPPED,
+            Capability::all(),
+            wrap::Algorithm::AES256_CCM,
+            key.to_vec(),
+        )
+        .unwrap();
+}
+
+/// Create a client with an exportable and a non-exportable key
+fn source_client() -> Client {
+    let client = mockhsm_client();
+
+    for &(key_id, capabilities) in &[
+        (
+            EXPORTABLE_KEY_ID,
+            Capability::SIGN_EDDSA | Capability::EXPORTABLE_UNDER_WRAP,
+        ),
+        (NON_EXPORTABLE_KEY_ID, Capability::SIGN_EDDSA),
+    ] {
+        client
+            .generate_asymmetric_key(
+                key_id,
+                "backup test key".into(),
+                Domain::DOM1,
+                capabilities,
+                asymmetric::Algorithm::Ed25519,
+            )
+            .unwrap();
+    }
+
+    client
+}
+
+#[test]
+fn backup_and_restore_test() {
+    let source = source_client();
+    let archive = backup::backup(&source, BACKUP_WRAP_KEY_ID).unwrap();
+
+    let entry = archive
+        .entries
+        .iter()
+        .find(|entry| entry.manifest.object_id == EXPORTABLE_KEY_ID)
+        .unwrap();
+    assert_eq!(entry.manifest.object_type, object::Type::AsymmetricKey);
+    assert_eq!(entry.manifest.domains, Domain::DOM1);
+
+    let skip_reason = |object_id| {
+        archive
+            .skipped
+            .iter()
+            .find(|skipped| skipped.manifest.object_id == object_id)
+            .map(|skipped| skipped.reason)
+    };
+
+    assert_eq!(
+        skip_reason(NON_EXPORTABLE_KEY_ID),
+        Some(SkipReason::NotExportable)
+    );
+    assert_eq!(skip_reason(BACKUP_WRAP_KEY_ID), Some(SkipReason::WrapKey));
+
+    let archive = Archive::from_bytes(&archive.to_bytes()).unwrap();
+
+    let destination = mockhsm_client();
+    let restored = backup::restore(&destination, BACKUP_WRAP_KEY_ID, &archive).unwrap();
+
+    // The default authentication key is present in every MockHsm
+    assert_eq!(
+        restored.existing,
+        vec![object::Handle::new(1, object::Type::AuthenticationKey)]
+    );
+    assert_eq!(restored.objects.len(), archive.entries.len() - 1);
+    assert_eq!(restored.skipped.len(), 2);
+    assert_eq!(
+        destination.get_public_key(EXPORTABLE_KEY_ID).unwrap(),
+        source.get_public_key(EXPORTABLE_KEY_ID).unwrap()
+    );
+    assert!(destination
+        .get_object_info(NON_EXPORTABLE_KEY_ID, object::Type::AsymmetricKey)
+        .is_err());
+}
+
+#[test]
+fn manifest_mismatch_test() {
+    let mut archive = backup::backup(&source_client(), BACKUP_WRAP_KEY_ID).unwrap();
+
+    for entry in &mut archive.entries {
+        if entry.manifest.object_id == EXPORTABLE_KEY_ID {
+            entry.manifest.label = "tampered".into();
+        }
+    }
+
+    let err = backup::restore(&mockhsm_client(), BACKUP_WRAP_KEY_ID, &archive).unwrap_err();
+    assert_eq!(err.kind(), BackupErrorKind::ManifestMismatch);
+}
+
+#[test]
+fn restore_existing_object_test() {
+    let archive = backup::backup(&source_client(), BACKUP_WRAP_KEY_ID).unwrap();
+
+    // Different object with the same ID as the exportable key
+    let destination = mockhsm_client();
+    destination
+        .generate_asymmetric_key(
+            EXPORTABLE_KEY_ID,
+            "other key".into(),
+            Domain::DOM1,
+            Capability::SIGN_EDDSA,
+            asymmetric::Algorithm::Ed25519,
+        )
+        .unwrap();
+
+    let err = backup::restore(&destination, BACKUP_WRAP_KEY_ID, &archive).unwrap_err();
+    assert_eq!(err.kind(), BackupErrorKind::ObjectExists);
+}
+
+#[test]
+fn invalid_archive_test() {
+    let archive = backup::backup(&source_client(), BACKUP_WRAP_KEY_ID).unwrap();
+    let bytes = archive.to_bytes();
+
+    for invalid in &[&bytes[..bytes.len() - 1], &bytes[1..], &[][..]] {
+        let err = Archive::from_bytes(invalid).unwrap_err();
+        assert_eq!(err.kind(), BackupErrorKind::FormatInvalid);
+    }
+
+    // Version byte follows the magic bytes
+    let mut unsupported_version = bytes.clone();
+    unsupported_version[b"yubihsm.rs backup".len()] = 2;
+
+    let err = Archive::from_bytes(&unsupported_version).unwrap_err();
+    assert_eq!(err.kind(), BackupErrorKind::FormatInvalid);
+}
+
+#[test]
+fn replicate_test() {
+    let source = source_client();
+    let destination = mockhsm_client();
+
+    let report = backup::replicate(&source, &destination, BACKUP_WRAP_KEY_ID, &[]).unwrap();
+
+    let outcome = |object_id, object_type| {
+        report
+            .iter()
+            .find(|replication| {
+                replication.manifest.object_id == object_id
+                    && replication.manifest.object_type == object_type
+            })
+            .map(|replication| replication.outcome)
+    };
+
+    assert_eq!(
+        outcome(EXPORTABLE_KEY_ID, object::Type::AsymmetricKey),
+        Some(Outcome::Replicated)
+    );
+    assert_eq!(
+        outcome(NON_EXPORTABLE_KEY_ID, object::Type::AsymmetricKey),
+        Some(Outcome::Skipped(SkipReason::NotExportable))
+    );
+    assert_eq!(
+        outcome(BACKUP_WRAP_KEY_ID, object::Type::WrapKey),
+        Some(Outcome::Unchanged)
+    );
+    assert_eq!(
+        destination.get_public_key(EXPORTABLE_KEY_ID).unwrap(),
+        source.get_public_key(EXPORTABLE_KEY_ID).unwrap()
+    );
+
+    // Replicating again leaves existing objects alone
+    let filters = [object::Filter::Type(object::Type::AsymmetricKey)];
+    let report = backup::replicate(&source, &destination, BACKUP_WRAP_KEY_ID, &filters).unwrap();
+
+    assert_eq!(report.len(), 2);
+    assert_eq!(report[0].manifest.object_id, EXPORTABLE_KEY_ID);
+    assert_eq!(report[0].outcome, Outcome::Unchanged);
+}
+
+#[test]
+fn replicate_conflict_test() {
+    let source = source_client();
+    let destination = mockhsm_client();
+
+    destination
+        .generate_asymmetric_key(
+            EXPORTABLE_KEY_ID,
+            "different key".into(),
+            Domain::DOM1,
+            Capability::SIGN_EDDSA,
+            asymmetric::Algorithm::Ed25519,
+        )
+        .unwrap();
+
+    let filters = [object::Filter::Id(EXPORTABLE_KEY_ID)];
+    let report = backup::replicate(&source, &destination, BACKUP_WRAP_KEY_ID, &filters).unwrap();
+
+    assert_eq!(report.len(), 1);
+    assert_eq!(report[0].outcome, Outcome::Conflict);
+    assert_ne!(
+        destination.get_public_key(EXPORTABLE_KEY_ID).unwrap(),
+        source.get_public_key(EXPORTABLE_KEY_ID).unwrap()
+    );
+}
+
+#[test]
+fn rewrap_test() {
+    let source = source_client();
+    let mut archive = backup::backup(&source, BACKUP_WRAP_KEY_ID).unwrap();
+
+    // The default authentication key is present in every MockHsm
+    archive
+        .entries
+        .retain(|entry| entry.manifest.object_type != object::Type::AuthenticationKey);
+
+    let rotation = mockhsm_client();
+    put_wrap_key(&rotation, ROTATED_WRAP_KEY_ID, ROTATED_WRAP_KEY);
+
+    let rewrapped = backup::rewrap(&rotation, &archive, ROTATED_WRAP_KEY_ID).unwrap();
+    assert!(rewrapped.existing.is_empty());
+    assert!(rewrapped.duplicates.is_empty());
+
+    let rewrapped = rewrapped.archive;
+    assert_eq!(rewrapped.wrap_key_id, ROTATED_WRAP_KEY_ID);
+    assert_eq!(rewrapped.previous_wrap_key_ids, vec![BACKUP_WRAP_KEY_ID]);
+    assert_eq!(rewrapped.entries.len(), archive.entries.len());
+    assert_eq!(rewrapped.skipped.len(), archive.skipped.len());
+
+    // Temporarily imported objects are cleaned up
+    assert!(rotation
+        .get_object_info(EXPORTABLE_KEY_ID, object::Type::AsymmetricKey)
+        .is_err());
+
+    let rewrapped = Archive::from_bytes(&rewrapped.to_bytes()).unwrap();
+    assert_eq!(rewrapped.previous_wrap_key_ids, vec![BACKUP_WRAP_KEY_ID]);
+
+    let destination = Client::open(Connector::mockhsm(), Default::default(), true).unwrap();
+    put_wrap_key(&destination, ROTATED_WRAP_KEY_ID, ROTATED_WRAP_KEY);
+
+    backup::restore(&destination, ROTATED_WRAP_KEY_ID, &rewrapped).unwrap();
+    assert_eq!(
+        destination.get_public_key(EXPORTABLE_KEY_ID).unwrap(),
+        source.get_public_key(EXPORTABLE_KEY_ID).unwrap()
+    );
+}
+
+#[test]
+fn rewrap_existing_object_test() {
+    let source = source_client();
+    let archive = backup::backup(&source, BACKUP_WRAP_KEY_ID).unwrap();
+    put_wrap_key(&source, ROTATED_WRAP_KEY_ID, ROTATED_WRAP_KEY);
+
+    // Objects still present in the HSM are exported under the new key directly
+    let rewrapped = backup::rewrap(&source, &archive, ROTATED_WRAP_KEY_ID).unwrap();
+    assert_eq!(rewrapped.existing.len(), archive.entries.len());
+    assert!(rewrapped.duplicates.is_empty());
+    assert_eq!(rewrapped.archive.entries.len(), archive.entries.len());
+
+    // ...and left in place
+    assert!(source
+        .get_object_info(EXPORTABLE_KEY_ID, object::Type::AsymmetricKey)
+        .is_ok());
+
+    let destination = Client::open(Connector::mockhsm(), Default::default(), true).unwrap();
+    put_wrap_key(&destination, ROTATED_WRAP_KEY_ID, ROTATED_WRAP_KEY);
+
+    backup::restore(&destination, ROTATED_WRAP_KEY_ID, &rewrapped.archive).unwrap();
+    assert_eq!(
+        destination.get_public_key(EXPORTABLE_KEY_ID).unwrap(),
+        source.get_public_key(EXPORTABLE_KEY_ID).unwrap()
+    );
+}
+
+#[test]
+fn rewrap_conflicting_object_test() {
+    let source = source_client();
+    let mut archive = backup::backup(&source, BACKUP_WRAP_KEY_ID).unwrap();
+    put_wrap_key(&source, ROTATED_WRAP_KEY_ID, ROTATED_WRAP_KEY);
+
+    for entry in &mut archive.entries {
+        entry.manifest.label = "conflicting label".into();
+    }
+
+    let err = backup::rewrap(&source, &archive, ROTATED_WRAP_KEY_ID).unwrap_err();
+    assert_eq!(err.kind(), BackupErrorKind::ObjectExists);
+}
@@ -0,0 +1,111 @@
+use crate::{clear_test_key_slot, TEST_DOMAINS, TEST_KEY_ID, TEST_KEY_LABEL, TEST_MESSAGE};
+use yubihsm::{authentication, object, Capability, Client, Credentials};
+
+/// Change an authentication key in place, then authenticate with the new key
+#[test]
+fn change_authentication_key_test() {
+    let client = crate::get_hsm_client();
+    let old_authentication_key = authentication::Key::derive_from_password(b"old password");
+    let new_authentication_key = authentication::Key::derive_from_password(TEST_MESSAGE);
+
+    clear_test_key_slot(&client, object::Type::AuthenticationKey);
+
+    client
+        .put_authentication_key(
+            TEST_KEY_ID,
+            TEST_KEY_LABEL.into(),
+            TEST_DOMAINS,
+            Capability::CHANGE_AUTHENTICATION_KEY | Capability::GET_PSEUDO_RANDOM,
+            Capability::empty(),
+            authentication::Algorithm::YUBICO_AES,
+            old_authentication_key.clone(),
+        )
+        .unwrap_or_else(|err| panic!("error putting auth key: {}", err));
+
+    let role_client = Client::open(
+        crate::HSM_CONNECTOR.clone(),
+        Credentials::new(TEST_KEY_ID, old_authentication_key),
+        false,
+    )
+    .unwrap_or_else(|err| panic!("error authenticating with old key: {}", err));
+
+    // Only the key used to establish the session can be changed
+    assert!(role_client
+        .change_authentication_key(1, new_authentication_key.clone())
+        .is_err());
+
+    role_client
+        .change_authentication_key(TEST_KEY_ID, new_authentication_key.clone())
+        .unwrap_or_else(|err| panic!("error changing auth key: {}", err));
+
+    let new_client = Client::open(
+        crate::HSM_CONNECTOR.clone(),
+        Credentials::new(TEST_KEY_ID, new_authentication_key),
+        false,
+    )
+    .unwrap_or_else(|err| panic!("error authenticating with new key: {}", err));
+
+    assert_eq!(new_client.get_pseudo_random(32).unwrap().len(), 32);
+
+    let object_info = client
+        .get_object_info(TEST_KEY_ID, object::Type::AuthenticationKey)
+        .unwrap_or_else(|err| panic!("error getting object info: {}", err));
+
+    assert_eq!(object_info.object_id, TEST_KEY_ID);
+    assert_eq!(&object_info.label.to_string(), TEST_KEY_LABEL);
+}
+
+/// Rotate a role's password, then ensure only the new password authenticates
+#[test]
+fn rotate_password_test() {
+    let client = crate::get_hsm_client();
+    let old_password = b"old role password";
+    let new_password = b"new role password";
+
+    clear_test_key_slot(&client, object::Type::AuthenticationKey);
+
+    client
+        .put_authentication_key(
+            TEST_KEY_ID,
+            TEST_KEY_LABEL.into(),
+            TEST_DOMAINS,
+            Capability::CHANGE_AUTHENTICATION_KEY | Capability::GET_PSEUDO_RANDOM,
+            Capability::empty(),
+            authentication::Algorithm::YUBICO_AES,
+            authentication::Key::derive_from_password(old_password),
+        )
+        .unwrap_or_else(|err| panic!("error putting auth key: {}", err));
+
+    let role_client = Client::open(
+        crate::HSM_CONNECTOR.clone(),
+        Credentials::from_password(TEST_KEY_ID, old_password),
+        true,
+    )
+    .unwrap_or_else(|err| panic!("error authenticating with old password: {}", err));
+
+    role_client
+        .change_authentication_key(
+            TEST_KEY_ID,
+            authentication::Key::derive_from_password(new_password),
+        )
+        .unwrap_or_else(|err| panic!("error changing auth key: {}", err));
+
+    // The session used to rotate the key remains usable
+    assert_eq!(role_client.get_pseudo_random(32).unwrap().len(), 32);
+
+    let new_client = Client::open(
+        crate::HSM_CONNECTOR.clone(),
+        Credentials::from_password(TEST_KEY_ID, new_password),
+        false,
+    )
+    .unwrap_or_else(|err| panic!("error authenticating with new password: {}", err));
+
+    assert_eq!(new_client.get_pseudo_random(32).unwrap().len(), 32);
+
+    assert!(Client::open(
+        crate::HSM_CONNECTOR.clone(),
+        Credentials::from_password(TEST_KEY_ID, old_password),
+        false,
+    )
+    .is_err());
+}
@@ -0,0 +1,52 @@
+use crate::{put_otp_aead_key, test_vectors::OTP_TEST_VECTORS, TEST_KEY_ID};
+use yubihsm::{Capability, DeviceErrorKind};
+
+/// AEAD key to encrypt the test vectors' OTP keys and private IDs under
+const TEST_AEAD_KEY: &[u8] = b"\x10\x11\x12\x13\x14\x15\x16\x17\x18\x19\x1A\x1B\x1C\x1D\x1E\x1F";
+
+/// Decrypt Yubico OTPs using AEADs created with `create_otp_aead`
+#[test]
+fn otp_test_vectors() {
+    let client = crate::get_hsm_client();
+    put_otp_aead_key(
+        &client,
+        Capability::CREATE_OTP_AEAD | Capability::DECRYPT_OTP,
+        TEST_AEAD_KEY,
+    );
+
+    for vector in OTP_TEST_VECTORS {
+        let aead = client
+            .create_otp_aead(TEST_KEY_ID, vector.key, vector.private_id)
+            .unwrap_or_else(|err| panic!("error creating OTP AEAD: {}", err));
+
+        let decrypted_otp = client
+            .decrypt_otp(TEST_KEY_ID, &aead, vector.otp)
+            .unwrap_or_else(|err| panic!("error decrypting OTP: {}", err));
+
+        assert_eq!(decrypted_otp.use_counter, vector.use_counter);
+        assert_eq!(decrypted_otp.session_counter, vector.session_counter);
+        assert_eq!(decrypted_otp.timestamp, vector.timestamp);
+    }
+}
+
+/// Ensure OTPs generated under a different private ID are rejected
+#[test]
+fn wrong_private_id_test() {
+    let client = crate::get_hsm_client();
+    put_otp_aead_key(
+        &client,
+        Capability::CREATE_OTP_AEAD | Capability::DECRYPT_OTP,
+        TEST_AEAD_KEY,
+    );
+
+    let vector = &OTP_TEST_VECTORS[0];
+    let aead = client
+        .create_otp_aead(TEST_KEY_ID, vector.key, OTP_TEST_VECTORS[1].private_id)
+        .unwrap_or_else(|err| panic!("error creating OTP AEAD: {}", err));
+
+    let err = client
+        .decrypt_otp(TEST_KEY_ID, &aead, vector.otp)
+        .unwrap_err();
+
+    assert_eq!(err.kind().device_error(), Some(DeviceErrorKind::InvalidOtp));
+}
@@ -0,0 +1,45 @@
+use crate::{put_asymmetric_key, test_vectors::RSA_OAEP_TEST_VECTORS, TEST_KEY_ID};
+use yubihsm::{asymmetric, rsa, Capability};
+
+/// Test RSAES-OAEP decryption against test vectors
+#[test]
+fn test_vectors() {
+    let client = crate::get_hsm_client();
+
+    for vector in RSA_OAEP_TEST_VECTORS {
+        put_asymmetric_key(
+            &client,
+            asymmetric::Algorithm::RSA_2048,
+            Capability::DECRYPT_OAEP,
+            vector.primes,
+        );
+
+        let label_hash = rsa::oaep::hash_label(vector.algorithm, vector.label).unwrap();
+
+        let plaintext = client
+            .decrypt_rsa_oaep(TEST_KEY_ID, vector.mgf1_hash_alg, &label_hash, vector.ct)
+            .unwrap_or_else(|err| panic!("error performing RSA decryption: {}", err));
+
+        assert_eq!(plaintext, vector.msg);
+    }
+}
+
+/// Decryption must fail if the label doesn't match
+#[test]
+fn wrong_label_test() {
+    let client = crate::get_hsm_client();
+    let vector = &RSA_OAEP_TEST_VECTORS[0];
+
+    put_asymmetric_key(
+        &client,
+        asymmetric::Algorithm::RSA_2048,
+        Capability::DECRYPT_OAEP,
+        vector.primes,
+    );
+
+    let label_hash = rsa::oaep::hash_label(vector.algorithm, b"wrong label").unwrap();
+
+    assert!(client
+        .decrypt_rsa_oaep(TEST_KEY_ID, vector.mgf1_hash_alg, &label_hash, vector.ct)
+        .is_err());
+}
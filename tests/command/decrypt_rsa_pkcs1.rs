@@ -0,0 +1,30 @@
+use crate::{put_asymmetric_key, test_vectors::RSA_PKCS1V15_TEST_VECTORS, TEST_KEY_ID};
+use yubihsm::{asymmetric, Capability};
+
+/// Test RSAES-PKCS#1v1.5 decryption against test vectors
+#[test]
+fn test_vectors() {
+    let client = crate::get_hsm_client();
+
+    for vector in RSA_PKCS1V15_TEST_VECTORS {
+        put_asymmetric_key(
+            &client,
+            asymmetric::Algorithm::RSA_2048,
+            Capability::DECRYPT_PKCS,
+            vector.primes,
+        );
+
+        let pubkey_response = client
+            .get_public_key(TEST_KEY_ID)
+            .unwrap_or_else(|err| panic!("error getting public key: {}", err));
+
+        assert_eq!(pubkey_response.algorithm, asymmetric::Algorithm::RSA_2048);
+        assert_eq!(pubkey_response.bytes, vector.modulus);
+
+        let plaintext = client
+            .decrypt_rsa_pkcs1v15(TEST_KEY_ID, vector.ct)
+            .unwrap_or_else(|err| panic!("error performing RSA decryption: {}", err));
+
+        assert_eq!(plaintext, vector.msg);
+    }
+}
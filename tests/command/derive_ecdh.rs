@@ -0,0 +1,126 @@
+use crate::{
+    generate_asymmetric_key, put_asymmetric_key, test_vectors::ECDH_TEST_VECTORS, TEST_KEY_ID,
+};
+use ring::{agreement, rand::SystemRandom};
+use untrusted;
+use yubihsm::{
+    asymmetric::{self, PublicKey},
+    client::ClientErrorKind,
+    Capability,
+};
+
+/// Test ECDH against test vectors
+#[test]
+fn test_vectors() {
+    let client = crate::get_hsm_client();
+
+    for vector in ECDH_TEST_VECTORS {
+        put_asymmetric_key(
+            &client,
+            vector.algorithm,
+            Capability::DERIVE_ECDH,
+            vector.sk,
+        );
+
+        let pubkey_response = client
+            .get_public_key(TEST_KEY_ID)
+            .unwrap_or_else(|err| panic!("error getting public key: {}", err));
+
+        assert_eq!(pubkey_response.algorithm, vector.algorithm);
+        assert_eq!(pubkey_response.bytes, vector.pk);
+
+        let shared_secret = client
+            .derive_ecdh(TEST_KEY_ID, vector.peer_pk)
+            .unwrap_or_else(|err| panic!("error performing ECDH: {}", err));
+
+        assert_eq!(shared_secret.as_slice(), vector.shared_secret);
+
+        // SEC1 uncompressed and compressed forms of the same point
+        let peer_pk = PublicKey::new(vector.algorithm, vector.peer_pk).unwrap();
+
+        for &compress in &[false, true] {
+            let sec1_peer_pk = peer_pk.to_sec1(compress).unwrap();
+
+            let shared_secret = client
+                .derive_ecdh(TEST_KEY_ID, &sec1_peer_pk)
+                .unwrap_or_else(|err| panic!("error performing ECDH: {}", err));
+
+            assert_eq!(shared_secret.as_slice(), vector.shared_secret);
+        }
+    }
+}
+
+/// Test ECDH with a randomly generated HSM key against *ring*
+#[test]
+fn generated_key_test() {
+    let client = crate::get_hsm_client();
+
+    generate_asymmetric_key(
+        &client,
+        asymmetric::Algorithm::EC_P256,
+        Capability::DERIVE_ECDH,
+    );
+
+    let hsm_public_key = client
+        .get_public_key(TEST_KEY_ID)
+        .unwrap_or_else(|err| panic!("error getting public key: {}", err));
+
+    let rng = SystemRandom::new();
+    let private_key =
+        agreement::EphemeralPrivateKey::generate(&agreement::ECDH_P256, &rng).unwrap();
+    let public_key = private_key.compute_public_key().unwrap();
+
+    let shared_secret = client
+        .derive_ecdh(TEST_KEY_ID, public_key.as_ref())
+        .unwrap_or_else(|err| panic!("error performing ECDH: {}", err));
+
+    let mut tagged_hsm_public_key = vec![0x04];
+    tagged_hsm_public_key.extend_from_slice(hsm_public_key.as_slice());
+
+    agreement::agree_ephemeral(
+        private_key,
+        &agreement::ECDH_P256,
+        untrusted::Input::from(&tagged_hsm_public_key),
+        (),
+        |expected| {
+            assert_eq!(shared_secret.as_slice(), expected);
+            Ok(())
+        },
+    )
+    .unwrap();
+}
+
+/// Points which aren't on the curve must be rejected
+#[test]
+fn invalid_point_test() {
+    let client = crate::get_hsm_client();
+    let vector = &ECDH_TEST_VECTORS[0];
+
+    put_asymmetric_key(
+        &client,
+        vector.algorithm,
+        Capability::DERIVE_ECDH,
+        vector.sk,
+    );
+
+    let mut invalid_point = vector.peer_pk.to_vec();
+    *invalid_point.last_mut().unwrap() ^= 1;
+
+    assert!(client.derive_ecdh(TEST_KEY_ID, &invalid_point).is_err());
+
+    // Compressed point whose x-coordinate isn't on the curve
+    let mut invalid_compressed_point = vec![0x02];
+    invalid_compressed_point.extend_from_slice(&[0xff; 32]);
+
+    let err = client
+        .derive_ecdh(TEST_KEY_ID, &invalid_compressed_point)
+        .unwrap_err();
+
+    assert_eq!(err.kind(), ClientErrorKind::InvalidPeerKey);
+
+    let err = client
+        .derive_ecdh(TEST_KEY_ID, &vector.peer_pk[1..])
+        .unwrap_err();
+
+    assert_eq!(err.kind(), ClientErrorKind::InvalidPeerKey);
+}
@@ -5,7 +5,6 @@ use crate::{
 use yubihsm::{asymmetric, object, wrap, Capability};
 
 /// Test wrap key workflow using randomly generated keys
-// TODO: test against RFC 3610 vectors
 #[test]
 fn wrap_key_test() {
     let client = crate::get_hsm_client();
@@ -50,6 +49,31 @@ fn wrap_key_test() {
         .export_wrapped(TEST_KEY_ID, exported_key_type, TEST_EXPORTED_KEY_ID)
         .unwrap_or_else(|err| panic!("error exporting key: {}", err));
 
+    // Decrypt the wrapped key in software using the same wrap key
+    let wrap_key = wrap::Key::from_bytes(TEST_KEY_ID, AESCCM_TEST_VECTORS[0].key).unwrap();
+    let plaintext = wrap_data
+        .decrypt(&wrap_key)
+        .unwrap_or_else(|err| panic!("error decrypting wrapped key: {}", err));
+
+    assert_eq!(plaintext.object_info.object_id, TEST_EXPORTED_KEY_ID);
+    assert_eq!(plaintext.object_info.object_type, exported_key_type);
+    assert_eq!(
+        plaintext.object_info.algorithm,
+        exported_key_algorithm.into()
+    );
+    assert_eq!(
+        plaintext.object_info.capabilities,
+        exported_key_capabilities
+    );
+
+    // Decrypting under the wrong wrap key must fail
+    let wrong_key_bytes = AESCCM_TEST_VECTORS.last().unwrap().key;
+    let wrong_key = wrap::Key::from_bytes(TEST_KEY_ID, wrong_key_bytes).unwrap();
+    assert_eq!(
+        wrap_data.decrypt(&wrong_key).unwrap_err().kind(),
+        wrap::WrapErrorKind::AuthenticationFailed
+    );
+
     // Delete the object from the HSM prior to re-importing it
     assert!(client
         .delete_object(TEST_EXPORTED_KEY_ID, exported_key_type)
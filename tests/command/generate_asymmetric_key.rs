@@ -1,5 +1,6 @@
-use crate::{generate_asymmetric_key, TEST_DOMAINS, TEST_KEY_ID, TEST_KEY_LABEL};
-use yubihsm::{asymmetric, object, Capability};
+use crate::{generate_asymmetric_key, TEST_DOMAINS, TEST_KEY_ID, TEST_KEY_LABEL, TEST_MESSAGE};
+use untrusted;
+use yubihsm::{asymmetric, object, rsa, Capability};
 
 /// Generate an Ed25519 key
 #[test]
@@ -46,3 +47,46 @@ fn nistp256_key_test() {
     assert_eq!(object_info.origin, object::Origin::Generated);
     assert_eq!(&object_info.label.to_string(), TEST_KEY_LABEL);
 }
+
+/// Generate an RSA-2048 key
+#[test]
+fn rsa2048_key_test() {
+    let client = crate::get_hsm_client();
+    let algorithm = asymmetric::Algorithm::RSA_2048;
+    let capabilities = Capability::SIGN_PKCS | Capability::SIGN_PSS;
+
+    generate_asymmetric_key(&client, algorithm, capabilities);
+
+    let object_info = client
+        .get_object_info(TEST_KEY_ID, object::Type::AsymmetricKey)
+        .unwrap_or_else(|err| panic!("error getting object info: {}", err));
+
+    assert_eq!(object_info.capabilities, capabilities);
+    assert_eq!(object_info.algorithm, algorithm.into());
+    assert_eq!(object_info.origin, object::Origin::Generated);
+
+    let public_key = client
+        .get_public_key(TEST_KEY_ID)
+        .unwrap_or_else(|err| panic!("error getting public key: {}", err))
+        .rsa()
+        .unwrap();
+
+    assert_eq!(public_key.algorithm(), algorithm);
+    assert_eq!(public_key.bits(), 2048);
+    assert_eq!(public_key.exponent(), rsa::PUBLIC_EXPONENT);
+
+    let signature = client
+        .sign_rsa_pss_sha256(TEST_KEY_ID, TEST_MESSAGE)
+        .unwrap_or_else(|err| panic!("error computing RSA signature: {}", err));
+
+    ring::signature::primitive::verify_rsa(
+        &ring::signature::RSA_PSS_2048_8192_SHA256,
+        (
+            untrusted::Input::from(public_key.modulus()),
+            untrusted::Input::from(public_key.exponent_bytes()),
+        ),
+        untrusted::Input::from(TEST_MESSAGE),
+        untrusted::Input::from(signature.as_slice()),
+    )
+    .unwrap();
+}
@@ -0,0 +1,38 @@
+use crate::{clear_test_key_slot, TEST_DOMAINS, TEST_KEY_ID, TEST_KEY_LABEL};
+use yubihsm::{object, otp, Capability};
+
+/// Generate an OTP AEAD key
+#[test]
+fn otp_aead_key_test() {
+    let client = crate::get_hsm_client();
+
+    let algorithm = otp::Algorithm::AES256;
+    let capabilities = Capability::CREATE_OTP_AEAD | Capability::DECRYPT_OTP;
+
+    clear_test_key_slot(&client, object::Type::OtpAeadKey);
+
+    let key_id = client
+        .generate_otp_aead_key(
+            TEST_KEY_ID,
+            TEST_KEY_LABEL.into(),
+            TEST_DOMAINS,
+            capabilities,
+            algorithm,
+            0x0102_0304,
+        )
+        .unwrap_or_else(|err| panic!("error generating OTP AEAD key: {}", err));
+
+    assert_eq!(key_id, TEST_KEY_ID);
+
+    let object_info = client
+        .get_object_info(TEST_KEY_ID, object::Type::OtpAeadKey)
+        .unwrap_or_else(|err| panic!("error getting object info: {}", err));
+
+    assert_eq!(object_info.capabilities, capabilities);
+    assert_eq!(object_info.object_id, TEST_KEY_ID);
+    assert_eq!(object_info.domains, TEST_DOMAINS);
+    assert_eq!(object_info.object_type, object::Type::OtpAeadKey);
+    assert_eq!(object_info.algorithm, algorithm.into());
+    assert_eq!(object_info.origin, object::Origin::Generated);
+    assert_eq!(&object_info.label.to_string(), TEST_KEY_LABEL);
+}
@@ -1,10 +1,14 @@
+use yubihsm::audit;
+
 /// Get audit log
 #[test]
 fn get_audit_logs_test() {
     let client = crate::get_hsm_client();
 
-    // TODO: test audit logging functionality
-    client
+    let log_entries = client
         .get_log_entries()
         .unwrap_or_else(|err| panic!("error getting logs: {}", err));
+
+    audit::verify_chain(None, &log_entries)
+        .unwrap_or_else(|err| panic!("error verifying logs: {}", err));
 }
@@ -0,0 +1,75 @@
+use crate::{
+    clear_test_key_slot,
+    test_vectors::{AESCCM_TEST_VECTORS, ED25519_TEST_VECTORS},
+    TEST_DOMAINS, TEST_EXPORTED_KEY_ID, TEST_EXPORTED_KEY_LABEL, TEST_KEY_ID, TEST_KEY_LABEL,
+};
+use yubihsm::{asymmetric, object, wrap, Capability};
+
+/// Test importing a key wrapped in software
+#[test]
+fn software_wrapped_key_test() {
+    let client = crate::get_hsm_client();
+    let key_bytes = AESCCM_TEST_VECTORS[0].key;
+
+    clear_test_key_slot(&client, object::Type::WrapKey);
+
+    client
+        .put_wrap_key(
+            TEST_KEY_ID,
+            TEST_KEY_LABEL.into(),
+            TEST_DOMAINS,
+            Capability::IMPORT_WRAPPED,
+            Capability::all(),
+            wrap::Algorithm::AES128_CCM,
+            key_bytes,
+        )
+        .unwrap_or_else(|err| panic!("error putting wrap key: {}", err));
+
+    let vector = &ED25519_TEST_VECTORS[0];
+    let capabilities = Capability::SIGN_EDDSA;
+
+    let mut params =
+        object::import::Params::new(TEST_EXPORTED_KEY_ID, asymmetric::Algorithm::Ed25519.into());
+    params.label = TEST_EXPORTED_KEY_LABEL.into();
+    params.domains = TEST_DOMAINS;
+    params.capabilities = capabilities;
+
+    let plaintext = wrap::Plaintext::new(
+        object::Type::AsymmetricKey,
+        params,
+        Capability::empty(),
+        vector.sk,
+    );
+
+    let wrap_key = wrap::Key::from_bytes(TEST_KEY_ID, key_bytes).unwrap();
+    let message = wrap::Message::encrypt(&wrap_key, &plaintext);
+
+    // Round trip the message through software decryption
+    let decrypted = message.decrypt(&wrap_key).unwrap();
+    assert_eq!(decrypted.object_info.object_id, TEST_EXPORTED_KEY_ID);
+    assert_eq!(decrypted.data, vector.sk);
+
+    let _ = client.delete_object(TEST_EXPORTED_KEY_ID, object::Type::AsymmetricKey);
+
+    let handle = client
+        .import_wrapped(TEST_KEY_ID, message)
+        .unwrap_or_else(|err| panic!("error importing key: {}", err));
+
+    assert_eq!(handle.object_type, object::Type::AsymmetricKey);
+    assert_eq!(handle.object_id, TEST_EXPORTED_KEY_ID);
+
+    let info = client
+        .get_object_info(TEST_EXPORTED_KEY_ID, object::Type::AsymmetricKey)
+        .unwrap_or_else(|err| panic!("error getting object info: {}", err));
+
+    assert_eq!(info.capabilities, capabilities);
+    assert_eq!(info.domains, TEST_DOMAINS);
+    assert_eq!(info.algorithm, asymmetric::Algorithm::Ed25519.into());
+    assert_eq!(&info.label.to_string(), TEST_EXPORTED_KEY_LABEL);
+
+    let signature = client
+        .sign_ed25519(TEST_EXPORTED_KEY_ID, vector.msg)
+        .unwrap_or_else(|err| panic!("error performing Ed25519 signature: {}", err));
+
+    assert_eq!(signature.as_ref(), vector.sig);
+}
@@ -1,27 +1,42 @@
 //! Integration tests for YubiHSM 2 commands
 
 pub mod blink_device;
+pub mod change_authentication_key;
+pub mod decrypt_otp;
+pub mod decrypt_rsa_oaep;
+pub mod decrypt_rsa_pkcs1;
 pub mod delete_object;
+pub mod derive_ecdh;
 pub mod device_info;
 pub mod export_wrapped;
 pub mod generate_asymmetric_key;
 pub mod generate_hmac_key;
+pub mod generate_otp_aead_key;
 pub mod generate_wrap_key;
 pub mod get_log_entries;
 pub mod get_object_info;
 pub mod get_option;
 pub mod get_pseudo_random;
 pub mod get_storage_info;
+pub mod import_wrapped;
 pub mod list_objects;
 pub mod put_asymmetric_key;
 pub mod put_authentication_key;
 pub mod put_opaque;
+pub mod put_otp_aead_key;
+pub mod put_template;
+pub mod randomize_otp_aead;
 #[cfg(feature = "mockhsm")]
 pub mod reset_device;
+pub mod rewrap_otp_aead;
 pub mod set_option;
 #[cfg(not(feature = "mockhsm"))]
 pub mod sign_attestation_certificate;
 #[cfg(not(feature = "mockhsm"))]
 pub mod sign_ecdsa;
 pub mod sign_eddsa;
+pub mod sign_rsa_pkcs1;
+pub mod sign_rsa_pss;
+#[cfg(feature = "mockhsm")]
+pub mod sign_ssh_certificate;
 pub mod verify_hmac;
@@ -1,7 +1,7 @@
-use yubihsm::{asymmetric, object, Capability};
+use yubihsm::{asymmetric, device::DeviceErrorKind, object, Capability};
 
-use crate::test_vectors::ED25519_TEST_VECTORS;
-use crate::{put_asymmetric_key, TEST_DOMAINS, TEST_KEY_ID, TEST_KEY_LABEL};
+use crate::test_vectors::{ED25519_TEST_VECTORS, RSA_2048_PRIMES};
+use crate::{clear_test_key_slot, put_asymmetric_key, TEST_DOMAINS, TEST_KEY_ID, TEST_KEY_LABEL};
 
 /// Put an Ed25519 key
 #[test]
@@ -25,3 +25,33 @@ fn ed25519_key_test() {
     assert_eq!(object_info.origin, object::Origin::Imported);
     assert_eq!(&object_info.label.to_string(), TEST_KEY_LABEL);
 }
+
+/// Put an RSA key whose primes are invalid
+#[test]
+fn invalid_rsa_primes_test() {
+    let client = crate::get_hsm_client();
+    clear_test_key_slot(&client, object::Type::AsymmetricKey);
+
+    // Clearing the low bit of `p` makes it even (and therefore not prime)
+    let mut primes = RSA_2048_PRIMES.to_vec();
+    primes[asymmetric::Algorithm::RSA_2048.key_len() / 2 - 1] &= 0xfe;
+
+    let err = client
+        .put_asymmetric_key(
+            TEST_KEY_ID,
+            TEST_KEY_LABEL.into(),
+            TEST_DOMAINS,
+            Capability::DECRYPT_PKCS,
+            asymmetric::Algorithm::RSA_2048,
+            primes,
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err.kind().device_error(),
+        Some(DeviceErrorKind::InvalidData)
+    );
+    assert!(client
+        .get_object_info(TEST_KEY_ID, object::Type::AsymmetricKey)
+        .is_err());
+}
@@ -0,0 +1,38 @@
+use yubihsm::{object, otp, Capability};
+
+use crate::{
+    clear_test_key_slot, test_vectors::OTP_TEST_VECTORS, TEST_DOMAINS, TEST_KEY_ID, TEST_KEY_LABEL,
+};
+
+/// Put an OTP AEAD key with a random nonce ID and use it to decrypt an OTP
+#[test]
+fn otp_aead_key_test() {
+    let client = crate::get_hsm_client();
+
+    clear_test_key_slot(&client, object::Type::OtpAeadKey);
+
+    let key_id = client
+        .put_otp_aead_key(
+            TEST_KEY_ID,
+            TEST_KEY_LABEL.into(),
+            TEST_DOMAINS,
+            Capability::CREATE_OTP_AEAD | Capability::DECRYPT_OTP,
+            otp::Algorithm::AES128,
+            [0x42; 16].to_vec(),
+        )
+        .unwrap_or_else(|err| panic!("error putting OTP AEAD key: {}", err));
+
+    assert_eq!(key_id, TEST_KEY_ID);
+
+    let vector = &OTP_TEST_VECTORS[0];
+
+    let aead = client
+        .create_otp_aead(TEST_KEY_ID, vector.key, vector.private_id)
+        .unwrap_or_else(|err| panic!("error creating OTP AEAD: {}", err));
+
+    let decrypted_otp = client
+        .decrypt_otp(TEST_KEY_ID, &aead, vector.otp)
+        .unwrap_or_else(|err| panic!("error decrypting OTP: {}", err));
+
+    assert_eq!(decrypted_otp.use_counter, vector.use_counter);
+}
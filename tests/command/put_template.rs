@@ -0,0 +1,87 @@
+use std::time::Duration;
+use yubihsm::{asymmetric, object, template, Capability, DeviceErrorKind};
+
+use crate::{clear_test_key_slot, TEST_DOMAINS, TEST_KEY_ID, TEST_KEY_LABEL};
+
+/// Put an SSH template and read it back
+#[test]
+fn ssh_template_test() {
+    let client = crate::get_hsm_client();
+
+    clear_test_key_slot(&client, object::Type::Template);
+
+    // Placeholder RSA-2048 timestamp key (the HSM doesn't validate it until
+    // a certificate request is signed)
+    let timestamp_key = asymmetric::PublicKey {
+        algorithm: asymmetric::Algorithm::RSA_2048,
+        bytes: vec![0xA5; 256],
+    };
+
+    let ssh_template = template::SshTemplate::new(timestamp_key)
+        .ca_key_id(TEST_KEY_ID)
+        .ca_key_id(TEST_KEY_ID + 1)
+        .not_before(Duration::from_secs(60))
+        .not_after(Duration::from_secs(3600))
+        .denied_principal("root");
+
+    let object_id = client
+        .put_template(
+            TEST_KEY_ID,
+            TEST_KEY_LABEL.into(),
+            TEST_DOMAINS,
+            Capability::default(),
+            template::Algorithm::SSH,
+            ssh_template.clone(),
+        )
+        .unwrap_or_else(|err| panic!("error putting template: {}", err));
+
+    assert_eq!(object_id, TEST_KEY_ID);
+
+    let template_data = client
+        .get_template(TEST_KEY_ID)
+        .unwrap_or_else(|err| panic!("error getting template: {}", err));
+
+    assert_eq!(template_data, ssh_template.to_bytes());
+    assert_eq!(
+        template::SshTemplate::from_bytes(&template_data).unwrap(),
+        ssh_template
+    );
+}
+
+/// Ensure malformed SSH templates are rejected
+#[test]
+fn invalid_ssh_template_test() {
+    let client = crate::get_hsm_client();
+
+    clear_test_key_slot(&client, object::Type::Template);
+
+    // Timestamp keys must be RSA-2048
+    let timestamp_key = asymmetric::PublicKey {
+        algorithm: asymmetric::Algorithm::RSA_2048,
+        bytes: vec![0xA5; 128],
+    };
+
+    let ssh_template = template::SshTemplate::new(timestamp_key).ca_key_id(TEST_KEY_ID);
+
+    for data in &[ssh_template.to_bytes(), vec![0xff; 4]] {
+        let err = client
+            .put_template(
+                TEST_KEY_ID,
+                TEST_KEY_LABEL.into(),
+                TEST_DOMAINS,
+                Capability::default(),
+                template::Algorithm::SSH,
+                data.clone(),
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            err.kind().device_error(),
+            Some(DeviceErrorKind::InvalidData)
+        );
+    }
+
+    assert!(client
+        .get_object_info(TEST_KEY_ID, object::Type::Template)
+        .is_err());
+}
@@ -0,0 +1,25 @@
+use crate::{put_otp_aead_key, TEST_KEY_ID};
+use yubihsm::{otp, Capability};
+
+/// Create OTP AEADs from random data
+#[test]
+fn randomize_otp_aead_test() {
+    let client = crate::get_hsm_client();
+    put_otp_aead_key(
+        &client,
+        Capability::RANDOMIZE_OTP_AEAD,
+        b"\x10\x11\x12\x13\x14\x15\x16\x17\x18\x19\x1A\x1B\x1C\x1D\x1E\x1F",
+    );
+
+    let aead1 = client
+        .randomize_otp_aead(TEST_KEY_ID)
+        .unwrap_or_else(|err| panic!("error randomizing OTP AEAD: {}", err));
+
+    let aead2 = client
+        .randomize_otp_aead(TEST_KEY_ID)
+        .unwrap_or_else(|err| panic!("error randomizing OTP AEAD: {}", err));
+
+    assert_eq!(aead1.len(), otp::AEAD_SIZE);
+    assert_eq!(aead2.len(), otp::AEAD_SIZE);
+    assert_ne!(aead1, aead2);
+}
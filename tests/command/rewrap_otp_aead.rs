@@ -0,0 +1,50 @@
+use crate::{
+    put_otp_aead_key, test_vectors::OTP_TEST_VECTORS, TEST_DOMAINS, TEST_EXPORTED_KEY_ID,
+    TEST_EXPORTED_KEY_LABEL, TEST_KEY_ID,
+};
+use yubihsm::{object, otp, Capability};
+
+/// Rewrap an OTP AEAD under a freshly generated key and decrypt an OTP with it
+#[test]
+fn rewrap_otp_aead_test() {
+    let client = crate::get_hsm_client();
+    let vector = &OTP_TEST_VECTORS[0];
+
+    put_otp_aead_key(
+        &client,
+        Capability::CREATE_OTP_AEAD | Capability::REWRAP_FROM_OTP_AEAD_KEY,
+        b"\x10\x11\x12\x13\x14\x15\x16\x17\x18\x19\x1A\x1B\x1C\x1D\x1E\x1F",
+    );
+
+    let _ = client.delete_object(TEST_EXPORTED_KEY_ID, object::Type::OtpAeadKey);
+
+    client
+        .generate_otp_aead_key(
+            TEST_EXPORTED_KEY_ID,
+            TEST_EXPORTED_KEY_LABEL.into(),
+            TEST_DOMAINS,
+            Capability::REWRAP_TO_OTP_AEAD_KEY | Capability::DECRYPT_OTP,
+            otp::Algorithm::AES192,
+            0x0506_0708,
+        )
+        .unwrap_or_else(|err| panic!("error generating OTP AEAD key: {}", err));
+
+    let aead = client
+        .create_otp_aead(TEST_KEY_ID, vector.key, vector.private_id)
+        .unwrap_or_else(|err| panic!("error creating OTP AEAD: {}", err));
+
+    let rewrapped_aead = client
+        .rewrap_otp_aead(TEST_KEY_ID, TEST_EXPORTED_KEY_ID, aead.clone())
+        .unwrap_or_else(|err| panic!("error rewrapping OTP AEAD: {}", err));
+
+    assert_eq!(rewrapped_aead.len(), otp::AEAD_SIZE);
+    assert_ne!(rewrapped_aead, aead);
+
+    let decrypted_otp = client
+        .decrypt_otp(TEST_EXPORTED_KEY_ID, &rewrapped_aead, vector.otp)
+        .unwrap_or_else(|err| panic!("error decrypting OTP: {}", err));
+
+    assert_eq!(decrypted_otp.use_counter, vector.use_counter);
+    assert_eq!(decrypted_otp.session_counter, vector.session_counter);
+    assert_eq!(decrypted_otp.timestamp, vector.timestamp);
+}
@@ -16,6 +16,17 @@ fn attest_asymmetric_test() {
         .sign_attestation_certificate(TEST_KEY_ID, None)
         .unwrap_or_else(|err| panic!("error getting attestation certificate: {}", err));
 
-    // TODO: more tests, e.g. test that the certificate validates
     assert!(certificate.len() > EC_P256_PUBLIC_KEY_SIZE);
+
+    let info = certificate
+        .parse()
+        .unwrap_or_else(|err| panic!("error parsing attestation certificate: {}", err));
+
+    let public_key = client
+        .get_public_key(TEST_KEY_ID)
+        .unwrap_or_else(|err| panic!("error getting public key: {}", err));
+
+    assert_eq!(info.public_key, public_key);
+    assert_eq!(info.object_id, Some(TEST_KEY_ID));
+    assert_eq!(info.capabilities, Some(Capability::SIGN_ECDSA));
 }
@@ -0,0 +1,119 @@
+use crate::{
+    put_asymmetric_key,
+    test_vectors::{RSA_2048_MODULUS, RSA_2048_PRIMES},
+    TEST_KEY_ID, TEST_MESSAGE,
+};
+use ring::signature::{self, primitive::verify_rsa, RsaParameters};
+use untrusted;
+use yubihsm::{asymmetric, rsa, Capability, Client};
+
+/// RSASSA-PKCS#1v1.5 algorithms and their corresponding `ring` verifiers
+static ALGORITHMS: &[(rsa::Algorithm, &RsaParameters)] = &[
+    (
+        rsa::Algorithm::PKCS1_SHA1,
+        &signature::RSA_PKCS1_2048_8192_SHA1,
+    ),
+    (
+        rsa::Algorithm::PKCS1_SHA256,
+        &signature::RSA_PKCS1_2048_8192_SHA256,
+    ),
+    (
+        rsa::Algorithm::PKCS1_SHA384,
+        &signature::RSA_PKCS1_2048_8192_SHA384,
+    ),
+    (
+        rsa::Algorithm::PKCS1_SHA512,
+        &signature::RSA_PKCS1_2048_8192_SHA512,
+    ),
+];
+
+/// Compute RSASSA-PKCS#1v1.5 signatures of a message and verify them
+#[test]
+fn message_test() {
+    let client = crate::get_hsm_client();
+
+    put_asymmetric_key(
+        &client,
+        asymmetric::Algorithm::RSA_2048,
+        Capability::SIGN_PKCS,
+        RSA_2048_PRIMES,
+    );
+
+    let public_key = get_rsa_public_key(&client);
+
+    for &(algorithm, verifier) in ALGORITHMS {
+        let signature = client
+            .sign_rsa_pkcs1v15(TEST_KEY_ID, algorithm, TEST_MESSAGE)
+            .unwrap_or_else(|err| panic!("error computing RSA signature: {}", err));
+
+        verify(verifier, &public_key, TEST_MESSAGE, signature.as_slice());
+    }
+}
+
+/// Compute RSASSA-PKCS#1v1.5 signatures of a digest and verify them
+#[test]
+fn prehashed_test() {
+    let client = crate::get_hsm_client();
+
+    put_asymmetric_key(
+        &client,
+        asymmetric::Algorithm::RSA_2048,
+        Capability::SIGN_PKCS,
+        RSA_2048_PRIMES,
+    );
+
+    let public_key = get_rsa_public_key(&client);
+
+    for &(algorithm, verifier) in ALGORITHMS {
+        let signature = client
+            .sign_rsa_pkcs1v15_prehashed(TEST_KEY_ID, algorithm, &algorithm.digest(TEST_MESSAGE))
+            .unwrap_or_else(|err| panic!("error computing RSA signature: {}", err));
+
+        verify(verifier, &public_key, TEST_MESSAGE, signature.as_slice());
+    }
+}
+
+/// Digests of the wrong length are rejected
+#[test]
+fn invalid_digest_length_test() {
+    let client = crate::get_hsm_client();
+
+    put_asymmetric_key(
+        &client,
+        asymmetric::Algorithm::RSA_2048,
+        Capability::SIGN_PKCS,
+        RSA_2048_PRIMES,
+    );
+
+    let result =
+        client.sign_rsa_pkcs1v15_prehashed(TEST_KEY_ID, rsa::Algorithm::PKCS1_SHA256, &[0u8; 20]);
+
+    assert!(result.is_err());
+}
+
+/// Get the test key's RSA public key
+fn get_rsa_public_key(client: &Client) -> rsa::PublicKey {
+    let public_key = client
+        .get_public_key(TEST_KEY_ID)
+        .unwrap_or_else(|err| panic!("error getting public key: {}", err))
+        .rsa()
+        .unwrap();
+
+    assert_eq!(public_key.algorithm(), asymmetric::Algorithm::RSA_2048);
+    assert_eq!(public_key.modulus(), RSA_2048_MODULUS);
+    public_key
+}
+
+/// Verify a signature with the given RSA public key
+fn verify(params: &RsaParameters, public_key: &rsa::PublicKey, msg: &[u8], signature: &[u8]) {
+    verify_rsa(
+        params,
+        (
+            untrusted::Input::from(public_key.modulus()),
+            untrusted::Input::from(public_key.exponent_bytes()),
+        ),
+        untrusted::Input::from(msg),
+        untrusted::Input::from(signature),
+    )
+    .unwrap();
+}
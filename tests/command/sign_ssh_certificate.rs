@@ -0,0 +1,350 @@
+use crate::{
+    clear_test_key_slot, put_asymmetric_key,
+    test_vectors::{ECDH_TEST_VECTORS, ED25519_TEST_VECTORS, RSA_2048_MODULUS, RSA_2048_PKCS1_PEM},
+    TEST_DOMAINS, TEST_KEY_ID, TEST_KEY_LABEL,
+};
+use byteorder::{BigEndian, ByteOrder};
+use ring::{
+    self,
+    rand::SystemRandom,
+    signature::{RsaKeyPair, RSA_PKCS1_SHA256},
+};
+use std::time::Duration;
+use subtle_encoding::base64;
+use untrusted;
+use yubihsm::{asymmetric, ecdsa, object, ssh, template, Capability, Client, DeviceErrorKind};
+
+/// Timestamp to authorize certificate requests with
+const TEST_TIMESTAMP: u32 = 1_546_300_800;
+
+/// Encode a list of fields (e.g. a public key) in SSH wire format
+fn ssh_wire_format(fields: &[&[u8]]) -> Vec<u8> {
+    let mut blob = vec![];
+
+    for field in fields {
+        let mut len = [0u8; 4];
+        BigEndian::write_u32(&mut len, field.len() as u32);
+        blob.extend_from_slice(&len);
+        blob.extend_from_slice(field);
+    }
+
+    blob
+}
+
+/// Encode an Ed25519 public key in SSH wire format
+fn ssh_ed25519_public_key(public_key: &[u8]) -> Vec<u8> {
+    ssh_wire_format(&[b"ssh-ed25519", public_key])
+}
+
+/// Put the given SSH template into the test slot
+fn put_ssh_template(client: &Client, ssh_template: template::SshTemplate) {
+    clear_test_key_slot(client, object::Type::Template);
+
+    client
+        .put_template(
+            TEST_KEY_ID,
+            TEST_KEY_LABEL.into(),
+            TEST_DOMAINS,
+            Capability::default(),
+            template::Algorithm::SSH,
+            ssh_template,
+        )
+        .unwrap_or_else(|err| panic!("error putting template: {}", err));
+}
+
+/// SSH template (with `RSA_2048_MODULUS` as the timestamp key) allowing the
+/// given CA key to sign certificates valid for up to an hour after the
+/// request timestamp
+fn ssh_template(ca_key_id: object::Id) -> template::SshTemplate {
+    let timestamp_key = asymmetric::PublicKey {
+        algorithm: asymmetric::Algorithm::RSA_2048,
+        bytes: RSA_2048_MODULUS.into(),
+    };
+
+    template::SshTemplate::new(timestamp_key)
+        .ca_key_id(ca_key_id)
+        .not_after(Duration::from_secs(3600))
+}
+
+/// Create a request for a certificate using the given CA key (SSH wire format)
+fn certificate_request(ca_public_key: &[u8]) -> ssh::CertificateRequest {
+    ssh::CertificateRequest::new(
+        &ssh_ed25519_public_key(ED25519_TEST_VECTORS[1].pk),
+        ca_public_key,
+    )
+    .unwrap()
+    .serial(42)
+    .key_id("yubihsm.rs test certificate")
+    .principal("alice")
+    .valid_after(u64::from(TEST_TIMESTAMP))
+    .valid_before(u64::from(TEST_TIMESTAMP) + 3600)
+    .extension("permit-pty", "")
+}
+
+/// Authorize a request by signing its timestamp with the RSA-2048 test key
+fn sign_timestamp(request: &ssh::CertificateRequest) -> ssh::SignedRequest {
+    let pem_body: String = RSA_2048_PKCS1_PEM
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect();
+
+    let der = base64::decode(&pem_body).unwrap();
+    let keypair = RsaKeyPair::from_der(untrusted::Input::from(&der)).unwrap();
+
+    request
+        .sign_timestamp(TEST_TIMESTAMP, |message| {
+            let mut signature = vec![0u8; keypair.public_modulus_len()];
+            keypair
+                .sign(
+                    &RSA_PKCS1_SHA256,
+                    &SystemRandom::new(),
+                    message,
+                    &mut signature,
+                )
+                .unwrap();
+            signature
+        })
+        .unwrap()
+}
+
+/// Put an Ed25519 CA key and a template allowing it into the test slots,
+/// returning the CA's public key in SSH wire format
+fn put_ed25519_ca(client: &Client, ssh_template: template::SshTemplate) -> Vec<u8> {
+    let ca_key = &ED25519_TEST_VECTORS[0];
+
+    put_asymmetric_key(
+        client,
+        asymmetric::Algorithm::Ed25519,
+        Capability::SIGN_SSH_CERTIFICATE,
+        ca_key.sk,
+    );
+
+    put_ssh_template(client, ssh_template);
+    ssh_ed25519_public_key(ca_key.pk)
+}
+
+/// Sign a request with the Ed25519 CA key, expecting a constraint violation
+fn assert_constraint_violation(client: &Client, signed_request: &ssh::SignedRequest) {
+    let err = client
+        .sign_ssh_certificate(
+            TEST_KEY_ID,
+            TEST_KEY_ID,
+            asymmetric::Algorithm::Ed25519,
+            signed_request,
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err.kind().device_error(),
+        Some(DeviceErrorKind::SshCaConstraintViolation)
+    );
+}
+
+/// Sign an OpenSSH user certificate with an Ed25519 CA key
+#[test]
+fn ed25519_ca_test() {
+    let client = crate::get_hsm_client();
+    let ca_public_key = put_ed25519_ca(&client, ssh_template(TEST_KEY_ID));
+    let signed_request = sign_timestamp(&certificate_request(&ca_public_key));
+
+    let certificate = client
+        .sign_ssh_certificate(
+            TEST_KEY_ID,
+            TEST_KEY_ID,
+            asymmetric::Algorithm::Ed25519,
+            &signed_request,
+        )
+        .unwrap_or_else(|err| panic!("error signing SSH certificate: {}", err));
+
+    assert!(certificate
+        .to_openssh()
+        .starts_with("ssh-ed25519-cert-v01@openssh.com "));
+
+    // Certificate is the body followed by an `ssh-ed25519` signature blob
+    let body = signed_request.body();
+    let (cert_body, signature_blob) = certificate.as_slice().split_at(body.len());
+    assert_eq!(cert_body, body);
+    assert_eq!(signature_blob.len(), 4 + 4 + 11 + 4 + 64);
+    assert_eq!(&signature_blob[8..19], b"ssh-ed25519");
+
+    ring::signature::verify(
+        &ring::signature::ED25519,
+        untrusted::Input::from(ED25519_TEST_VECTORS[0].pk),
+        untrusted::Input::from(body),
+        untrusted::Input::from(&signature_blob[23..]),
+    )
+    .unwrap();
+}
+
+/// Sign an OpenSSH user certificate with an ECDSA (P-256) CA key
+#[test]
+fn ecdsa_ca_test() {
+    let client = crate::get_hsm_client();
+    let ca_key = &ECDH_TEST_VECTORS[0];
+    assert_eq!(ca_key.algorithm, asymmetric::Algorithm::EC_P256);
+
+    put_asymmetric_key(
+        &client,
+        ca_key.algorithm,
+        Capability::SIGN_SSH_CERTIFICATE,
+        ca_key.sk,
+    );
+
+    put_ssh_template(&client, ssh_template(TEST_KEY_ID));
+
+    let mut point = vec![0x04];
+    point.extend_from_slice(ca_key.pk);
+
+    let ca_public_key = ssh_wire_format(&[b"ecdsa-sha2-nistp256", b"nistp256", &point]);
+    let signed_request = sign_timestamp(&certificate_request(&ca_public_key));
+
+    let certificate = client
+        .sign_ssh_certificate(
+            TEST_KEY_ID,
+            TEST_KEY_ID,
+            ecdsa::Algorithm::SHA256,
+            &signed_request,
+        )
+        .unwrap_or_else(|err| panic!("error signing SSH certificate: {}", err));
+
+    assert!(certificate
+        .to_openssh()
+        .starts_with("ecdsa-sha2-nistp256-cert-v01@openssh.com "));
+
+    // Signature blob is `string(key type) || string(mpint(r) || mpint(s))`
+    let body = signed_request.body();
+    let (cert_body, signature_blob) = certificate.as_slice().split_at(body.len());
+    assert_eq!(cert_body, body);
+
+    let mut fields = vec![];
+    let mut remaining = &signature_blob[4..];
+
+    while !remaining.is_empty() {
+        let len = BigEndian::read_u32(remaining) as usize;
+        fields.push(&remaining[4..4 + len]);
+        remaining = &remaining[4 + len..];
+    }
+
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields[0], b"ecdsa-sha2-nistp256");
+
+    // Convert `r` and `s` into the fixed-size form *ring* expects
+    let mut signature = vec![];
+    let mut remaining = fields[1];
+
+    while !remaining.is_empty() {
+        let len = BigEndian::read_u32(remaining) as usize;
+        let mpint = &remaining[4..4 + len];
+        let leading_zeroes = mpint.iter().take_while(|&&byte| byte == 0).count();
+        let integer = &mpint[leading_zeroes..];
+
+        signature.extend(vec![0u8; 32 - integer.len()]);
+        signature.extend_from_slice(integer);
+        remaining = &remaining[4 + len..];
+    }
+
+    ring::signature::verify(
+        &ring::signature::ECDSA_P256_SHA256_FIXED,
+        untrusted::Input::from(&point),
+        untrusted::Input::from(body),
+        untrusted::Input::from(&signature),
+    )
+    .unwrap();
+}
+
+/// Ensure CA keys not allowed by the template can't sign certificates
+#[test]
+fn ca_key_not_allowed_test() {
+    let client = crate::get_hsm_client();
+    let ca_public_key = put_ed25519_ca(&client, ssh_template(TEST_KEY_ID + 1));
+    let signed_request = sign_timestamp(&certificate_request(&ca_public_key));
+
+    assert_constraint_violation(&client, &signed_request);
+}
+
+/// Ensure requests whose timestamp isn't signed by the template's timestamp
+/// key are rejected
+#[test]
+fn bad_timestamp_signature_test() {
+    let client = crate::get_hsm_client();
+    let ca_public_key = put_ed25519_ca(&client, ssh_template(TEST_KEY_ID));
+
+    let signed_request = certificate_request(&ca_public_key)
+        .sign_timestamp(TEST_TIMESTAMP, |_| vec![0u8; 256])
+        .unwrap();
+
+    assert_constraint_violation(&client, &signed_request);
+}
+
+/// Ensure certificates valid outside of the period allowed by the template
+/// are rejected
+#[test]
+fn validity_period_test() {
+    let client = crate::get_hsm_client();
+    let ca_public_key = put_ed25519_ca(&client, ssh_template(TEST_KEY_ID));
+
+    // Valid for longer than the template's `not_after` allows
+    let request =
+        certificate_request(&ca_public_key).valid_before(u64::from(TEST_TIMESTAMP) + 7200);
+
+    assert_constraint_violation(&client, &sign_timestamp(&request));
+
+    // Valid before the timestamp, which the template's `not_before` forbids
+    let request = certificate_request(&ca_public_key).valid_after(u64::from(TEST_TIMESTAMP) - 60);
+
+    assert_constraint_violation(&client, &sign_timestamp(&request));
+}
+
+/// Ensure certificates for principals the template denies are rejected
+#[test]
+fn principal_constraint_test() {
+    let client = crate::get_hsm_client();
+    let ca_public_key = put_ed25519_ca(&client, ssh_template(TEST_KEY_ID).denied_principal("root"));
+
+    client
+        .sign_ssh_certificate(
+            TEST_KEY_ID,
+            TEST_KEY_ID,
+            asymmetric::Algorithm::Ed25519,
+            &sign_timestamp(&certificate_request(&ca_public_key)),
+        )
+        .unwrap_or_else(|err| panic!("error signing SSH certificate: {}", err));
+
+    let request = certificate_request(&ca_public_key).principal("root");
+    assert_constraint_violation(&client, &sign_timestamp(&request));
+}
+
+/// Check certificate requests against an allow-list of principals
+#[test]
+fn allowed_principals_test() {
+    let ca_public_key = ssh_ed25519_public_key(ED25519_TEST_VECTORS[0].pk);
+    let allowed = ["alice", "bob"];
+
+    let request = certificate_request(&ca_public_key).principal("bob");
+    assert_eq!(
+        request.principals(),
+        &["alice".to_owned(), "bob".to_owned()]
+    );
+    assert!(request.are_principals_allowed(&allowed));
+
+    let request = certificate_request(&ca_public_key).principal("root");
+    assert!(!request.are_principals_allowed(&allowed));
+
+    // Certificates without principals are valid for any principal
+    let request = ssh::CertificateRequest::new(
+        &ssh_ed25519_public_key(ED25519_TEST_VECTORS[1].pk),
+        &ca_public_key,
+    )
+    .unwrap();
+    assert!(!request.are_principals_allowed(&allowed));
+}
+
+/// Ensure unsupported key types are rejected
+#[test]
+fn unsupported_key_type_test() {
+    let mut public_key = vec![0, 0, 0, 7];
+    public_key.extend_from_slice(b"ssh-dss");
+
+    let err = ssh::CertificateRequest::new(&public_key, &public_key).unwrap_err();
+    assert_eq!(err.kind(), ssh::SshErrorKind::AlgorithmUnsupported);
+}
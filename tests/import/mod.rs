@@ -0,0 +1,137 @@
+use crate::{
+    clear_test_key_slot,
+    test_vectors::{
+        ECDH_TEST_VECTORS, ED25519_OPENSSH, ED25519_PKCS8_PEM, ED25519_TEST_VECTORS, P256_OPENSSH,
+        P256_PKCS8_PEM, P256_PUBLIC_KEY, P256_SEC1_PEM, RSA_2048_MODULUS, RSA_2048_OPENSSH,
+        RSA_2048_PKCS1_PEM, RSA_2048_PKCS8_PEM,
+    },
+    TEST_DOMAINS, TEST_KEY_ID, TEST_KEY_LABEL,
+};
+use yubihsm::{
+    asymmetric::{
+        self,
+        import::{ImportErrorKind, PrivateKey},
+        PublicKey,
+    },
+    object, Capability,
+};
+
+/// Import a PEM-encoded private key into the HSM, returning its public key
+fn import_pem(pem: &str, algorithm: asymmetric::Algorithm, capabilities: Capability) -> PublicKey {
+    let client = crate::get_hsm_client();
+    clear_test_key_slot(&client, object::Type::AsymmetricKey);
+
+    let private_key = PrivateKey::from_pem(pem)
+        .unwrap_or_else(|err| panic!("error parsing private key: {}", err));
+
+    assert_eq!(private_key.algorithm(), algorithm);
+
+    let key_id = private_key
+        .put(
+            &client,
+            TEST_KEY_ID,
+            TEST_KEY_LABEL.into(),
+            TEST_DOMAINS,
+            capabilities,
+        )
+        .unwrap_or_else(|err| panic!("error importing private key: {}", err));
+
+    assert_eq!(key_id, TEST_KEY_ID);
+
+    client
+        .get_public_key(TEST_KEY_ID)
+        .unwrap_or_else(|err| panic!("error getting public key: {}", err))
+}
+
+#[test]
+fn rsa_import_test() {
+    for pem in &[RSA_2048_PKCS1_PEM, RSA_2048_PKCS8_PEM, RSA_2048_OPENSSH] {
+        let public_key = import_pem(pem, asymmetric::Algorithm::RSA_2048, Capability::SIGN_PKCS);
+
+        assert_eq!(public_key.algorithm, asymmetric::Algorithm::RSA_2048);
+        assert_eq!(public_key.as_slice(), RSA_2048_MODULUS);
+    }
+}
+
+#[test]
+fn nistp256_import_test() {
+    for pem in &[P256_SEC1_PEM, P256_PKCS8_PEM, P256_OPENSSH] {
+        let public_key = import_pem(pem, asymmetric::Algorithm::EC_P256, Capability::SIGN_ECDSA);
+
+        assert_eq!(public_key.algorithm, asymmetric::Algorithm::EC_P256);
+        assert_eq!(public_key.as_slice(), P256_PUBLIC_KEY);
+    }
+}
+
+#[test]
+fn ed25519_import_test() {
+    for pem in &[ED25519_PKCS8_PEM, ED25519_OPENSSH] {
+        let public_key = import_pem(pem, asymmetric::Algorithm::Ed25519, Capability::SIGN_EDDSA);
+
+        assert_eq!(public_key.algorithm, asymmetric::Algorithm::Ed25519);
+        assert_eq!(public_key.as_slice(), ED25519_TEST_VECTORS[0].pk);
+    }
+}
+
+#[test]
+fn mismatched_public_key_test() {
+    let client = crate::get_hsm_client();
+    clear_test_key_slot(&client, object::Type::AsymmetricKey);
+
+    // SEC1 key whose (valid) public key belongs to a different private key
+    let vector = &ECDH_TEST_VECTORS[0];
+    assert_eq!(vector.algorithm, asymmetric::Algorithm::EC_P256);
+
+    let mut der = vec![0x30, 0x77, 0x02, 0x01, 0x01, 0x04, 0x20];
+    der.extend_from_slice(vector.sk);
+    der.extend_from_slice(&[
+        0xa0, 0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07,
+    ]);
+    der.extend_from_slice(&[0xa1, 0x44, 0x03, 0x42, 0x00, 0x04]);
+    der.extend_from_slice(vector.peer_pk);
+
+    let private_key = PrivateKey::from_sec1_der(&der).unwrap();
+    assert_eq!(private_key.public_key().unwrap().as_slice(), vector.peer_pk);
+
+    let err = private_key
+        .put(
+            &client,
+            TEST_KEY_ID,
+            TEST_KEY_LABEL.into(),
+            TEST_DOMAINS,
+            Capability::DERIVE_ECDH,
+        )
+        .unwrap_err();
+
+    assert_eq!(err.kind(), ImportErrorKind::KeyInvalid);
+    assert!(client
+        .get_object_info(TEST_KEY_ID, object::Type::AsymmetricKey)
+        .is_err());
+}
+
+#[test]
+fn openssh_format_test() {
+    let private_key = PrivateKey::from_openssh(ED25519_OPENSSH).unwrap();
+    assert_eq!(private_key.algorithm(), asymmetric::Algorithm::Ed25519);
+
+    let err = PrivateKey::from_openssh(ED25519_PKCS8_PEM).unwrap_err();
+    assert_eq!(err.kind(), ImportErrorKind::FormatInvalid);
+}
+
+#[test]
+fn invalid_key_test() {
+    let encrypted = ED25519_PKCS8_PEM.replace("PRIVATE KEY", "ENCRYPTED PRIVATE KEY");
+    let err = PrivateKey::from_pem(&encrypted).unwrap_err();
+    assert_eq!(err.kind(), ImportErrorKind::AlgorithmUnsupported);
+
+    let public_key = "-----BEGIN PUBLIC KEY-----
+MCowBQYDK2VwAyEA11qYAYKxCrfVS/7TyWQHOg7hcvPapiMlrwIaaPcHURo=
+-----END PUBLIC KEY-----
+";
+
+    let err = PrivateKey::from_pem(public_key).unwrap_err();
+    assert_eq!(err.kind(), ImportErrorKind::FormatInvalid);
+
+    let err = PrivateKey::from_pkcs8_der(&[0x30, 0x03, 0x02, 0x01, 0x00]).unwrap_err();
+    assert_eq!(err.kind(), ImportErrorKind::FormatInvalid);
+}
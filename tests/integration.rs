@@ -7,6 +7,16 @@ extern crate lazy_static;
 
 use yubihsm::{asymmetric, object, Capability, Client, Connector, DeviceErrorKind, Domain};
 
+/// Attestation certificate tests
+mod attestation;
+
+/// Audit log tests
+mod audit;
+
+/// Backup and restore tests
+#[cfg(feature = "mockhsm")]
+mod backup;
+
 /// Integration tests for individual YubiHSM 2 commands
 mod command;
 
@@ -16,6 +26,18 @@ mod ecdsa;
 /// Ed25519 tests
 mod ed25519;
 
+/// Private key import tests
+mod import;
+
+/// Yubico OTP validation tests
+mod otp;
+
+/// Public key encoding tests
+mod public_key;
+
+/// RSA tests
+mod rsa;
+
 /// Cryptographic test vectors taken from standards documents
 mod test_vectors;
 
@@ -172,3 +194,22 @@ pub fn put_asymmetric_key<T: Into<Vec<u8>>>(
 
     assert_eq!(key_id, TEST_KEY_ID);
 }
+
+/// Put an OTP AEAD key into the HSM
+pub fn put_otp_aead_key(client: &Client, capabilities: Capability, key: &[u8]) {
+    clear_test_key_slot(client, object::Type::OtpAeadKey);
+
+    let key_id = client
+        .put_otp_aead_key_with_nonce_id(
+            TEST_KEY_ID,
+            TEST_KEY_LABEL.into(),
+            TEST_DOMAINS,
+            capabilities,
+            yubihsm::otp::Algorithm::AES128,
+            0x0102_0304,
+            key,
+        )
+        .unwrap_or_else(|err| panic!("error putting OTP AEAD key: {}", err));
+
+    assert_eq!(key_id, TEST_KEY_ID);
+}
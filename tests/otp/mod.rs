@@ -0,0 +1,123 @@
+use crate::{put_otp_aead_key, test_vectors::OTP_TEST_VECTORS, TEST_KEY_ID};
+use yubihsm::{
+    otp::validation::{
+        modhex, Counters, Credential, MemoryStore, Otp, Store, ValidationErrorKind, Validator,
+    },
+    Capability, Client,
+};
+
+/// Public ID of the test YubiKey
+const TEST_PUBLIC_ID: &[u8] = b"\xC0\xFF\xEE\xC0\xFF\xEE";
+
+/// Create a validator for the first OTP test vector's YubiKey
+fn create_validator(client: &Client) -> Validator<MemoryStore> {
+    let vector = &OTP_TEST_VECTORS[0];
+
+    put_otp_aead_key(
+        client,
+        Capability::CREATE_OTP_AEAD | Capability::DECRYPT_OTP,
+        b"\x10\x11\x12\x13\x14\x15\x16\x17\x18\x19\x1A\x1B\x1C\x1D\x1E\x1F",
+    );
+
+    let aead = client
+        .create_otp_aead(TEST_KEY_ID, vector.key, vector.private_id)
+        .unwrap_or_else(|err| panic!("error creating OTP AEAD: {}", err));
+
+    let mut store = MemoryStore::new();
+    store.insert(TEST_PUBLIC_ID, Credential::new(TEST_KEY_ID, aead));
+    Validator::new(store)
+}
+
+/// ModHex-encoded OTP (as typed by the YubiKey) for the first test vector
+fn test_otp() -> String {
+    modhex::encode(TEST_PUBLIC_ID) + &modhex::encode(OTP_TEST_VECTORS[0].otp)
+}
+
+#[test]
+fn parse_otp_test() {
+    let otp: Otp = test_otp().parse().unwrap();
+    assert_eq!(otp.public_id(), TEST_PUBLIC_ID);
+    assert_eq!(otp.ciphertext(), OTP_TEST_VECTORS[0].otp);
+    assert_eq!(&test_otp()[12..], "eikgugcfvgbecfebghthhbghidbhuing");
+
+    for bad_otp in &[
+        "",
+        "eikgugcfvgbecfebghthhbghidbhuing",
+        "cbdefghijklnrtuvvxcb",
+    ] {
+        assert_eq!(
+            bad_otp.parse::<Otp>().unwrap_err().kind(),
+            ValidationErrorKind::FormatInvalid
+        );
+    }
+}
+
+#[test]
+fn validate_otp_test() {
+    let client = crate::get_hsm_client();
+    let mut validator = create_validator(&client);
+    let vector = &OTP_TEST_VECTORS[0];
+
+    let decrypted_otp = validator
+        .validate(&client, &test_otp())
+        .unwrap_or_else(|err| panic!("error validating OTP: {}", err));
+
+    assert_eq!(decrypted_otp.use_counter, vector.use_counter);
+    assert_eq!(decrypted_otp.session_counter, vector.session_counter);
+    assert_eq!(decrypted_otp.timestamp, vector.timestamp);
+
+    let credential = validator.store().get(TEST_PUBLIC_ID).unwrap().unwrap();
+    assert_eq!(credential.counters, Some(Counters::from(decrypted_otp)));
+}
+
+#[test]
+fn replayed_otp_test() {
+    let client = crate::get_hsm_client();
+    let mut validator = create_validator(&client);
+
+    validator.validate(&client, &test_otp()).unwrap();
+
+    let err = validator.validate(&client, &test_otp()).unwrap_err();
+    assert_eq!(err.kind(), ValidationErrorKind::Replayed);
+}
+
+#[test]
+fn stale_otp_test() {
+    let client = crate::get_hsm_client();
+    let mut validator = create_validator(&client);
+
+    // Pretend an OTP from a later session was already accepted
+    validator
+        .store_mut()
+        .update_counters(
+            TEST_PUBLIC_ID,
+            Counters {
+                use_counter: OTP_TEST_VECTORS[0].use_counter + 1,
+                session_counter: 0,
+            },
+        )
+        .unwrap();
+
+    let err = validator.validate(&client, &test_otp()).unwrap_err();
+    assert_eq!(err.kind(), ValidationErrorKind::Replayed);
+}
+
+#[test]
+fn unknown_public_id_test() {
+    let client = crate::get_hsm_client();
+    let mut validator = create_validator(&client);
+
+    let otp = modhex::encode(b"\x01\x02\x03\x04\x05\x06") + &test_otp()[12..];
+    let err = validator.validate(&client, &otp).unwrap_err();
+    assert_eq!(err.kind(), ValidationErrorKind::PublicIdUnknown);
+}
+
+#[test]
+fn invalid_otp_test() {
+    let client = crate::get_hsm_client();
+    let mut validator = create_validator(&client);
+
+    let otp = modhex::encode(TEST_PUBLIC_ID) + &modhex::encode(OTP_TEST_VECTORS[1].otp);
+    let err = validator.validate(&client, &otp).unwrap_err();
+    assert_eq!(err.kind(), ValidationErrorKind::OtpInvalid);
+}
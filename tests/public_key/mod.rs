@@ -0,0 +1,255 @@
+use crate::{
+    generate_asymmetric_key,
+    test_vectors::{ED25519_TEST_VECTORS, RSA_2048_MODULUS},
+    TEST_KEY_ID,
+};
+use subtle_encoding::hex;
+use yubihsm::{
+    asymmetric::{self, PublicKey, PublicKeyErrorKind},
+    Capability,
+};
+
+/// SPKI encoding of `RSA_2048_MODULUS`
+const RSA_2048_PEM: &str = "-----BEGIN PUBLIC KEY-----
+MIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEA2d0yD7tXFT2hgdMH+ayL
+Mo2JwyUfSoQlAAdhHqjHnFmv1VArLPbHS3FvFSUKncbvCloityjRPxvSSKFgZ/iG
+ehQ0L0rnTRCNtGQ8gHRHoC81DGIziIuMHrISiiAzIvrl49n5HswGiiDgDodQEKTq
+u9fmLIBgOmHa0zO1kICSBLApkuFm8I3tuFWpAY+XS/4nG1mHqIF3P0Fyhm4OcH6n
+l8TPwtwNYcLuRU/jEJrscoCHk7ZebXZSycZq8XThMs4Ivxc7s494n0bQwdb2hdh8
+RVO+qfVb9P2OsLS7GQjnVTXDEvLaMHaUDeZieeBD8lTPLLGDXxJ4NHCmIzNt28/B
+ewIDAQAB
+-----END PUBLIC KEY-----
+";
+
+/// OpenSSH encoding of `RSA_2048_MODULUS`
+const RSA_2048_OPENSSH: &str = "ssh-rsa AAAAB3NzaC1yc2EAAAADAQABAAABAQDZ3TIPu1cVPaGB0wf5rIsyjYnDJR9KhCUAB2EeqMecWa/VUCss9sdLcW8VJQqdxu8KWiK3KNE/G9JIoWBn+IZ6FDQvSudNEI20ZDyAdEegLzUMYjOIi4weshKKIDMi+uXj2fkezAaKIOAOh1AQpOq71+YsgGA6YdrTM7WQgJIEsCmS4Wbwje24VakBj5dL/icbWYeogXc/QXKGbg5wfqeXxM/C3A1hwu5FT+MQmuxygIeTtl5tdlLJxmrxdOEyzgi/Fzuzj3ifRtDB1vaF2HxFU76p9Vv0/Y6wtLsZCOdVNcMS8towdpQN5mJ54EPyVM8ssYNfEng0cKYjM23bz8F7";
+
+/// NIST P-256 public key from RFC 7517 Appendix A.1
+const P256_JWK_X: &str = "MKBCTNIcKUSDii11ySs3526iDZ8AiTo7Tu6KPAqv7D4";
+const P256_JWK_Y: &str = "4Etl6SRW2YiLUrN5vfvVHuhp7x8PxltmWWlbbM4IFyM";
+const P256_POINT: &str = "30a0424cd21c2944838a2d75c92b37e76ea20d9f00893a3b4eee8a3c0aafec3e\
+                          e04b65e92456d9888b52b379bdfbd51ee869ef1f0fc65b6659695b6cce081723";
+
+/// SPKI encoding of the RFC 7517 P-256 key
+const P256_PEM: &str = "-----BEGIN PUBLIC KEY-----
+MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEMKBCTNIcKUSDii11ySs3526iDZ8A
+iTo7Tu6KPAqv7D7gS2XpJFbZiItSs3m9+9Ue6GnvHw/GW2ZZaVtszggXIw==
+-----END PUBLIC KEY-----
+";
+
+/// OpenSSH encoding of the RFC 7517 P-256 key
+const P256_OPENSSH: &str = "ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBDCgQkzSHClEg4otdckrN+duog2fAIk6O07uijwKr+w+4Etl6SRW2YiLUrN5vfvVHuhp7x8PxltmWWlbbM4IFyM=";
+
+/// SPKI (DER, base64) encoding of the first Ed25519 test vector's public key
+const ED25519_SPKI: &str = "MCowBQYDK2VwAyEA11qYAYKxCrfVS/7TyWQHOg7hcvPapiMlrwIaaPcHURo=";
+
+/// OpenSSH encoding of the first Ed25519 test vector's public key
+const ED25519_OPENSSH: &str =
+    "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAINdamAGCsQq31Uv+08lkBzoO4XLz2qYjJa8CGmj3B1Ea";
+
+/// Base points of various curves (`X || Y`), which are valid public keys
+const BASE_POINTS: &[(asymmetric::Algorithm, &str)] = &[
+    (
+        asymmetric::Algorithm::EC_P224,
+        "b70e0cbd6bb4bf7f321390b94a03c1d356c21122343280d6115c1d21\
+         bd376388b5f723fb4c22dfe6cd4375a05a07476444d5819985007e34",
+    ),
+    // Negation of the P-224 base point (i.e. with an odd y-coordinate)
+    (
+        asymmetric::Algorithm::EC_P224,
+        "b70e0cbd6bb4bf7f321390b94a03c1d356c21122343280d6115c1d21\
+         42c89c774a08dc04b3dd201932bc8a5ea5f8b89bbb2a7e667aff81cd",
+    ),
+    (
+        asymmetric::Algorithm::EC_P384,
+        "aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a38\
+         5502f25dbf55296c3a545e3872760ab73617de4a96262c6f5d9e98bf9292dc29\
+         f8f41dbd289a147ce9da3113b5f0b8c00a60b1ce1d7e819d7a431d7c90ea0e5f",
+    ),
+    (
+        asymmetric::Algorithm::EC_P521,
+        "00c6858e06b70404e9cd9e3ecb662395b4429c648139053fb521f828af606b4d\
+         3dbaa14b5e77efe75928fe1dc127a2ffa8de3348b3c1856a429bf97e7e31c2e5\
+         bd66011839296a789a3bc0045c8a5fb42c7d1bd998f54449579b446817afbd17\
+         273e662c97ee72995ef42640c550b9013fad0761353c7086a272c24088be9476\
+         9fd16650",
+    ),
+    (
+        asymmetric::Algorithm::EC_K256,
+        "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+         483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+    ),
+    (
+        asymmetric::Algorithm::EC_BP512,
+        "81aee4bdd82ed9645a21322e9c4c6a9385ed9f70b5d916c1b43b62eef4d0098e\
+         ff3b1f78e2d0d48d50d1687b93b97d5f7c6d5047406a5e688b352209bcb9f822\
+         7dde385d566332ecc0eabfa9cf7822fdf209f70024a57b1aa000c55b881f8111\
+         b2dcde494a5f485e5bca4bd88a2763aed1ca2b2fa8f0540678cd1e0f3ad80892",
+    ),
+];
+
+#[test]
+fn rsa_spki_test() {
+    let public_key = PublicKey::new(asymmetric::Algorithm::RSA_2048, RSA_2048_MODULUS).unwrap();
+
+    assert_eq!(public_key.to_spki_pem().unwrap(), RSA_2048_PEM);
+    assert_eq!(PublicKey::from_spki_pem(RSA_2048_PEM).unwrap(), public_key);
+
+    let der = public_key.to_spki_der().unwrap();
+    assert_eq!(PublicKey::from_spki_der(&der).unwrap(), public_key);
+}
+
+#[test]
+fn rsa_openssh_test() {
+    let public_key = PublicKey::new(asymmetric::Algorithm::RSA_2048, RSA_2048_MODULUS).unwrap();
+
+    assert_eq!(public_key.to_openssh(None).unwrap(), RSA_2048_OPENSSH);
+    assert_eq!(
+        public_key.to_openssh(Some("user@example.com")).unwrap(),
+        format!("{} user@example.com", RSA_2048_OPENSSH)
+    );
+
+    assert_eq!(
+        PublicKey::from_openssh(&format!("{} user@example.com\n", RSA_2048_OPENSSH)).unwrap(),
+        public_key
+    );
+}
+
+#[test]
+fn rsa_jwk_test() {
+    let public_key = PublicKey::new(asymmetric::Algorithm::RSA_2048, RSA_2048_MODULUS).unwrap();
+    let jwk = public_key.to_jwk().unwrap();
+
+    assert_eq!(jwk.kty, "RSA");
+    assert_eq!(jwk.e.as_ref().unwrap(), "AQAB");
+    assert_eq!(PublicKey::from_jwk(&jwk).unwrap(), public_key);
+}
+
+#[test]
+fn nistp256_test() {
+    let public_key = PublicKey::new(
+        asymmetric::Algorithm::EC_P256,
+        hex::decode(P256_POINT).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(public_key.to_spki_pem().unwrap(), P256_PEM);
+    assert_eq!(PublicKey::from_spki_pem(P256_PEM).unwrap(), public_key);
+
+    assert_eq!(public_key.to_openssh(None).unwrap(), P256_OPENSSH);
+    assert_eq!(PublicKey::from_openssh(P256_OPENSSH).unwrap(), public_key);
+
+    let jwk = public_key.to_jwk().unwrap();
+    assert_eq!(jwk.kty, "EC");
+    assert_eq!(jwk.crv.as_ref().unwrap(), "P-256");
+    assert_eq!(jwk.x.as_ref().unwrap(), P256_JWK_X);
+    assert_eq!(jwk.y.as_ref().unwrap(), P256_JWK_Y);
+    assert_eq!(PublicKey::from_jwk(&jwk).unwrap(), public_key);
+
+    let uncompressed = public_key.to_sec1(false).unwrap();
+    assert_eq!(uncompressed[0], 0x04);
+    assert_eq!(&uncompressed[1..], public_key.as_slice());
+
+    // The RFC 7517 key has an odd y-coordinate
+    let compressed = public_key.to_sec1(true).unwrap();
+    assert_eq!(compressed[0], 0x03);
+    assert_eq!(&compressed[1..], &public_key.as_slice()[..32]);
+
+    for point in &[uncompressed, compressed] {
+        assert_eq!(
+            PublicKey::from_sec1(asymmetric::Algorithm::EC_P256, point).unwrap(),
+            public_key
+        );
+    }
+}
+
+#[test]
+fn sec1_compression_test() {
+    for (algorithm, point) in BASE_POINTS {
+        let public_key = PublicKey::new(*algorithm, hex::decode(point).unwrap()).unwrap();
+        let compressed = public_key.to_sec1(true).unwrap();
+
+        assert_eq!(compressed.len(), 1 + algorithm.key_len());
+
+        // Decompressing P-224 points would need a general modular square root
+        if *algorithm == asymmetric::Algorithm::EC_P224 {
+            let err = PublicKey::from_sec1(*algorithm, &compressed).unwrap_err();
+            assert_eq!(err.kind(), PublicKeyErrorKind::AlgorithmUnsupported);
+        } else {
+            assert_eq!(
+                PublicKey::from_sec1(*algorithm, &compressed).unwrap(),
+                public_key
+            );
+        }
+
+        let der = public_key.to_spki_der().unwrap();
+        assert_eq!(PublicKey::from_spki_der(&der).unwrap(), public_key);
+    }
+}
+
+#[test]
+fn invalid_point_test() {
+    let mut point = hex::decode(P256_POINT).unwrap();
+    point[63] ^= 1;
+
+    let err = PublicKey::new(asymmetric::Algorithm::EC_P256, point).unwrap_err();
+    assert_eq!(err.kind(), PublicKeyErrorKind::KeyInvalid);
+}
+
+#[test]
+fn ed25519_test() {
+    let public_key =
+        PublicKey::new(asymmetric::Algorithm::Ed25519, ED25519_TEST_VECTORS[0].pk).unwrap();
+
+    let der = subtle_encoding::base64::decode(ED25519_SPKI).unwrap();
+    assert_eq!(public_key.to_spki_der().unwrap(), der);
+    assert_eq!(PublicKey::from_spki_der(&der).unwrap(), public_key);
+
+    assert_eq!(public_key.to_openssh(None).unwrap(), ED25519_OPENSSH);
+    assert_eq!(
+        PublicKey::from_openssh(ED25519_OPENSSH).unwrap(),
+        public_key
+    );
+
+    let jwk = public_key.to_jwk().unwrap();
+    assert_eq!(jwk.kty, "OKP");
+    assert_eq!(jwk.crv.as_ref().unwrap(), "Ed25519");
+    assert_eq!(PublicKey::from_jwk(&jwk).unwrap(), public_key);
+
+    let err = public_key.to_sec1(true).unwrap_err();
+    assert_eq!(err.kind(), PublicKeyErrorKind::AlgorithmUnsupported);
+}
+
+#[test]
+fn unsupported_format_test() {
+    let (algorithm, point) = BASE_POINTS[BASE_POINTS.len() - 1];
+    let public_key = PublicKey::new(algorithm, hex::decode(point).unwrap()).unwrap();
+
+    let err = public_key.to_openssh(None).unwrap_err();
+    assert_eq!(err.kind(), PublicKeyErrorKind::AlgorithmUnsupported);
+
+    let err = public_key.to_jwk().unwrap_err();
+    assert_eq!(err.kind(), PublicKeyErrorKind::AlgorithmUnsupported);
+}
+
+/// Export a key generated by the HSM
+#[test]
+fn generated_key_test() {
+    let client = crate::get_hsm_client();
+
+    generate_asymmetric_key(
+        &client,
+        asymmetric::Algorithm::EC_P256,
+        Capability::SIGN_ECDSA,
+    );
+
+    let public_key = client
+        .get_public_key(TEST_KEY_ID)
+        .unwrap_or_else(|err| panic!("error getting public key: {}", err));
+
+    let pem = public_key.to_spki_pem().unwrap();
+    assert_eq!(PublicKey::from_spki_pem(&pem).unwrap(), public_key);
+
+    let openssh = public_key.to_openssh(Some("yubihsm")).unwrap();
+    assert_eq!(PublicKey::from_openssh(&openssh).unwrap(), public_key);
+}
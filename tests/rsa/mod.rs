@@ -0,0 +1,116 @@
+use crate::{
+    put_asymmetric_key,
+    test_vectors::{RSA_2048_MODULUS, RSA_2048_PRIMES},
+    TEST_KEY_ID,
+};
+use ring::signature::{self, primitive::verify_rsa, RsaParameters};
+use signatory::PublicKeyed;
+use untrusted;
+use yubihsm::{asymmetric, rsa, Capability};
+
+/// Example message to sign
+const TEST_MESSAGE: &[u8] =
+    b"RSA (Rivest-Shamir-Adleman) is one of the first public-key cryptosystems \
+      and is widely used for secure data transmission.";
+
+#[test]
+fn rsa_pkcs1_sign_test() {
+    let client = crate::get_hsm_client();
+
+    put_asymmetric_key(
+        &client,
+        asymmetric::Algorithm::RSA_2048,
+        Capability::SIGN_PKCS,
+        RSA_2048_PRIMES,
+    );
+
+    let signer = rsa::pkcs1::Signer::create(client.clone(), TEST_KEY_ID).unwrap();
+    let public_key = signer.public_key().unwrap();
+    assert_eq!(public_key.modulus(), RSA_2048_MODULUS);
+
+    let signature: rsa::pkcs1::Signature = signatory::sign_sha256(&signer, TEST_MESSAGE).unwrap();
+    verify(
+        &signature::RSA_PKCS1_2048_8192_SHA256,
+        &public_key,
+        signature.as_slice(),
+    );
+
+    let signature: rsa::pkcs1::Signature = signatory::sign_sha384(&signer, TEST_MESSAGE).unwrap();
+    verify(
+        &signature::RSA_PKCS1_2048_8192_SHA384,
+        &public_key,
+        signature.as_slice(),
+    );
+
+    let signature: rsa::pkcs1::Signature = signatory::sign_sha512(&signer, TEST_MESSAGE).unwrap();
+    verify(
+        &signature::RSA_PKCS1_2048_8192_SHA512,
+        &public_key,
+        signature.as_slice(),
+    );
+}
+
+#[test]
+fn rsa_pss_sign_test() {
+    let client = crate::get_hsm_client();
+
+    put_asymmetric_key(
+        &client,
+        asymmetric::Algorithm::RSA_2048,
+        Capability::SIGN_PSS,
+        RSA_2048_PRIMES,
+    );
+
+    let signer = rsa::pss::Signer::create(client.clone(), TEST_KEY_ID).unwrap();
+    let public_key = signer.public_key().unwrap();
+    assert_eq!(public_key.modulus(), RSA_2048_MODULUS);
+
+    let signature: rsa::pss::Signature = signatory::sign_sha256(&signer, TEST_MESSAGE).unwrap();
+    verify(
+        &signature::RSA_PSS_2048_8192_SHA256,
+        &public_key,
+        signature.as_slice(),
+    );
+
+    let signature: rsa::pss::Signature = signatory::sign_sha384(&signer, TEST_MESSAGE).unwrap();
+    verify(
+        &signature::RSA_PSS_2048_8192_SHA384,
+        &public_key,
+        signature.as_slice(),
+    );
+
+    let signature: rsa::pss::Signature = signatory::sign_sha512(&signer, TEST_MESSAGE).unwrap();
+    verify(
+        &signature::RSA_PSS_2048_8192_SHA512,
+        &public_key,
+        signature.as_slice(),
+    );
+}
+
+#[test]
+fn rsa_signer_key_type_test() {
+    let client = crate::get_hsm_client();
+
+    put_asymmetric_key(
+        &client,
+        asymmetric::Algorithm::Ed25519,
+        Capability::SIGN_EDDSA,
+        [0u8; 32].as_ref(),
+    );
+
+    assert!(rsa::pkcs1::Signer::create(client.clone(), TEST_KEY_ID).is_err());
+}
+
+/// Use *ring* to verify an RSA signature of `TEST_MESSAGE`
+fn verify(params: &RsaParameters, public_key: &rsa::PublicKey, signature: &[u8]) {
+    verify_rsa(
+        params,
+        (
+            untrusted::Input::from(public_key.modulus()),
+            untrusted::Input::from(public_key.exponent_bytes()),
+        ),
+        untrusted::Input::from(TEST_MESSAGE),
+        untrusted::Input::from(signature),
+    )
+    .unwrap();
+}
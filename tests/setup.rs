@@ -23,3 +23,646 @@ fn setup_test() {
     // TODO: actually test provisioning the profile
     let _profile = Profile::default().roles(vec![root_role]);
 }
+
+/// Object ID of the role created from the profile configuration
+#[cfg(feature = "setup")]
+const CONFIG_ROLE_KEY_ID: object::Id = 2;
+
+/// Object ID of the wrap key created from the profile configuration
+#[cfg(feature = "setup")]
+const CONFIG_WRAP_KEY_ID: object::Id = 3;
+
+/// Environment variable containing the password for the configured role
+#[cfg(feature = "setup")]
+const CONFIG_ROLE_PASSWORD_ENV: &str = "YUBIHSM_RS_TEST_ROLE_PASSWORD";
+
+/// Password for the configured role
+#[cfg(feature = "setup")]
+const CONFIG_ROLE_PASSWORD: &str = "correct horse battery staple";
+
+/// Write a file referenced by a profile configuration into a temporary directory
+#[cfg(feature = "setup")]
+fn write_temp_file(name: &str, contents: &str) -> std::path::PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("yubihsm-rs-test-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+/// Write a wrap key file for the profile configuration
+#[cfg(feature = "setup")]
+fn write_wrap_key_file() -> std::path::PathBuf {
+    write_temp_file("wrap-key.hex", "000102030405060708090a0b0c0d0e0f\n")
+}
+
+/// Profile configuration using the given wrap key file
+#[cfg(feature = "setup")]
+fn profile_config(wrap_key_file: &std::path::Path) -> String {
+    format!(
+        r#"{{
+            "audit_option": "on",
+            "report_object_id": 4,
+            "roles": [{{
+                "authentication_key_id": {},
+                "authentication_key_label": "signer",
+                "password_env": "{}",
+                "capabilities": ["sign-eddsa", "get-pseudo-random"],
+                "domains": ["1", "dom2"]
+            }}],
+            "wrap_keys": [{{
+                "id": {},
+                "label": "backup key",
+                "key_file": {:?},
+                "capabilities": ["export-wrapped", "import-wrapped"],
+                "delegated_capabilities": ["all"],
+                "domains": ["all"]
+            }}]
+        }}"#,
+        CONFIG_ROLE_KEY_ID,
+        CONFIG_ROLE_PASSWORD_ENV,
+        CONFIG_WRAP_KEY_ID,
+        wrap_key_file.to_str().unwrap()
+    )
+}
+
+#[cfg(feature = "setup")]
+#[test]
+fn profile_config_test() {
+    std::env::set_var(CONFIG_ROLE_PASSWORD_ENV, CONFIG_ROLE_PASSWORD);
+    let wrap_key_file = write_wrap_key_file();
+    let config = profile_config(&wrap_key_file);
+
+    assert!(config.parse::<Profile>().is_ok());
+
+    // Inline passwords must be rejected
+    let inline_password = config.replace(
+        &format!(r#""password_env": "{}""#, CONFIG_ROLE_PASSWORD_ENV),
+        r#""password": "hunter2""#,
+    );
+    assert!(inline_password.parse::<Profile>().is_err());
+
+    // Unknown capability names must be rejected
+    let bad_capability = config.replace("sign-eddsa", "sign-everything");
+    assert!(bad_capability.parse::<Profile>().is_err());
+
+    // The `dom` prefix of domain names may only appear once
+    let bad_domain = config.replace(r#""dom2""#, r#""domdom2""#);
+    assert_ne!(bad_domain, config);
+    assert!(bad_domain.parse::<Profile>().is_err());
+
+    // Wrap keys must be read from a file, so they can be restored elsewhere
+    let generated_wrap_key = config.replace(
+        &format!(r#""key_file": {:?}"#, wrap_key_file.to_str().unwrap()),
+        r#""generate": "aes256-ccm""#,
+    );
+    assert_ne!(generated_wrap_key, config);
+    assert!(generated_wrap_key.parse::<Profile>().is_err());
+
+    std::fs::remove_file(&wrap_key_file).unwrap();
+}
+
+/// Parse the example TOML configuration from the `setup::config` module docs,
+/// pointing the files it references at temporary ones
+#[cfg(feature = "setup")]
+#[test]
+fn documented_toml_config_test() {
+    let docs: String = include_str!("../src/setup/config.rs")
+        .lines()
+        .take_while(|line| line.starts_with("//!"))
+        .map(|line| format!("{}\n", line.trim_start_matches("//!").trim_start()))
+        .collect();
+
+    let example = docs
+        .split("```toml\n")
+        .nth(1)
+        .and_then(|rest| rest.split("```").next())
+        .expect("missing TOML example in setup::config docs");
+
+    let wrap_key_file = write_temp_file("backup-wrap-key.hex", &"00".repeat(32));
+    let hmac_key_file = write_temp_file("hmac-key.hex", &"01".repeat(32));
+    let cert_file = write_temp_file("validator-cert.der", "not actually DER");
+
+    let config = example
+        .replace(
+            "/etc/yubihsm/backup-wrap-key.hex",
+            wrap_key_file.to_str().unwrap(),
+        )
+        .replace("/etc/yubihsm/hmac-key.hex", hmac_key_file.to_str().unwrap())
+        .replace(
+            "/etc/yubihsm/validator-cert.pem",
+            cert_file.to_str().unwrap(),
+        );
+
+    std::env::set_var("YUBIHSM_SIGNER_PASSWORD", CONFIG_ROLE_PASSWORD);
+    let result = Profile::from_toml_str(&config);
+
+    // Command names must be known to the device
+    let unknown_command = config.replace("sign-eddsa = \"fix\"", "sign-everything = \"fix\"");
+    let unknown_command_result = Profile::from_toml_str(&unknown_command);
+
+    for file in &[wrap_key_file, hmac_key_file, cert_file] {
+        std::fs::remove_file(file).unwrap();
+    }
+
+    result.unwrap_or_else(|e| panic!("error parsing documented config: {}", e));
+    assert!(unknown_command_result.is_err());
+}
+
+#[cfg(all(feature = "setup", feature = "mockhsm"))]
+#[test]
+fn provision_from_config_test() {
+    use yubihsm::{AuditOption, Client, Connector};
+
+    std::env::set_var(CONFIG_ROLE_PASSWORD_ENV, CONFIG_ROLE_PASSWORD);
+    let wrap_key_file = write_wrap_key_file();
+    let profile: Profile = profile_config(&wrap_key_file).parse().unwrap();
+    std::fs::remove_file(&wrap_key_file).unwrap();
+
+    let connector = Connector::mockhsm();
+    let mut client = Client::open(connector.clone(), Default::default(), true).unwrap();
+    profile.provision(&mut client).unwrap();
+
+    let role_info = client
+        .get_object_info(CONFIG_ROLE_KEY_ID, object::Type::AuthenticationKey)
+        .unwrap();
+    assert_eq!(
+        role_info.capabilities,
+        Capability::SIGN_EDDSA | Capability::GET_PSEUDO_RANDOM
+    );
+    assert_eq!(role_info.domains, Domain::DOM1 | Domain::DOM2);
+
+    let wrap_key_info = client
+        .get_object_info(CONFIG_WRAP_KEY_ID, object::Type::WrapKey)
+        .unwrap();
+    assert_eq!(wrap_key_info.delegated_capabilities, Capability::all());
+    assert_eq!(wrap_key_info.domains, Domain::all());
+
+    assert_eq!(client.get_force_audit_option().unwrap(), AuditOption::On);
+    assert!(client.get_opaque(4).is_ok());
+
+    // The role's credentials must have been derived from its password
+    let credentials =
+        Credentials::from_password(CONFIG_ROLE_KEY_ID, CONFIG_ROLE_PASSWORD.as_bytes());
+    let role_client = Client::open(connector, credentials, true).unwrap();
+    assert!(role_client.get_pseudo_random(16).is_ok());
+}
+
+#[cfg(all(feature = "setup", feature = "mockhsm"))]
+#[test]
+fn provision_objects_from_config_test() {
+    use yubihsm::{command, setup::Report, AuditOption, Client, Connector};
+
+    let certificate_file = write_temp_file(
+        "certificate.pem",
+        "-----BEGIN CERTIFICATE-----\nAAECAw==\n-----END CERTIFICATE-----\n",
+    );
+
+    let config = format!(
+        r#"{{
+            "report_object_id": 8,
+            "asymmetric_keys": [{{
+                "key_id": 5,
+                "label": "signing key",
+                "algorithm": "ed25519",
+                "capabilities": ["sign-eddsa"],
+                "domains": ["1"]
+            }}],
+            "hmac_keys": [{{
+                "key_id": 6,
+                "algorithm": "hmac-sha256",
+                "capabilities": ["sign-hmac", "verify-hmac"],
+                "domains": ["1"]
+            }}],
+            "opaque_objects": [{{
+                "object_id": 7,
+                "algorithm": "x509-certificate",
+                "file": {:?},
+                "capabilities": ["get-opaque"],
+                "domains": ["1"]
+            }}],
+            "command_audit_options": {{
+                "sign-eddsa": "fix"
+            }}
+        }}"#,
+        certificate_file.to_str().unwrap()
+    );
+
+    let profile: Profile = config.parse().unwrap();
+    std::fs::remove_file(&certificate_file).unwrap();
+
+    let mut client = Client::open(Connector::mockhsm(), Default::default(), true).unwrap();
+    let report = profile.provision(&mut client).unwrap();
+
+    assert_eq!(report.public_keys.len(), 1);
+    assert_eq!(report.public_keys[0].key_id, 5);
+    assert_eq!(report.public_keys[0].label, "signing key");
+    assert_eq!(
+        report.public_keys[0].public_key().unwrap(),
+        client.get_public_key(5).unwrap()
+    );
+
+    let hmac_key_info = client.get_object_info(6, object::Type::HmacKey).unwrap();
+    assert_eq!(
+        hmac_key_info.capabilities,
+        Capability::SIGN_HMAC | Capability::VERIFY_HMAC
+    );
+
+    // PEM certificates are stored DER encoded
+    assert_eq!(client.get_opaque(7).unwrap(), vec![0, 1, 2, 3]);
+
+    assert_eq!(
+        client
+            .get_command_audit_option(command::Code::SignEddsa)
+            .unwrap(),
+        AuditOption::Fix
+    );
+
+    let stored_report: Report = String::from_utf8(client.get_opaque(8).unwrap())
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(stored_report.public_keys.len(), 1);
+}
+
+#[cfg(all(feature = "setup", feature = "mockhsm"))]
+#[test]
+fn plan_test() {
+    use yubihsm::{
+        asymmetric, command,
+        setup::{
+            plan::{AuditChange, Difference},
+            AsymmetricKey,
+        },
+        AuditOption, Client, Connector,
+    };
+
+    let client = Client::open(Connector::mockhsm(), Default::default(), true).unwrap();
+
+    // Existing key with different capabilities than in the profile
+    client
+        .generate_asymmetric_key(
+            5,
+            "signing key".into(),
+            Domain::DOM1,
+            Capability::SIGN_EDDSA | Capability::EXPORTABLE_UNDER_WRAP,
+            asymmetric::Algorithm::Ed25519,
+        )
+        .unwrap();
+
+    let role = Role::new(Credentials::new(
+        ROOT_KEY_ID + 1,
+        authentication::Key::random(),
+    ))
+    .capabilities(Capability::SIGN_EDDSA)
+    .domains(Domain::DOM1);
+
+    let signing_key = AsymmetricKey::new(5, asymmetric::Algorithm::Ed25519)
+        .label("signing key")
+        .capabilities(Capability::SIGN_EDDSA)
+        .domains(Domain::DOM1);
+
+    let profile = Profile::default()
+        .roles(vec![role])
+        .asymmetric_keys(vec![signing_key])
+        .command_audit_option(command::Code::SignEddsa, AuditOption::Fix);
+
+    let objects_before = client.list_objects(&[]).unwrap().len();
+    let plan = profile.plan(&client).unwrap();
+
+    // Planning must not modify the device
+    assert_eq!(client.list_objects(&[]).unwrap().len(), objects_before);
+    assert_eq!(
+        client
+            .get_command_audit_option(command::Code::SignEddsa)
+            .unwrap(),
+        AuditOption::On
+    );
+
+    assert!(!plan.is_empty());
+
+    // The role and the report object need to be created
+    let created: Vec<_> = plan.create.iter().map(|spec| spec.handle()).collect();
+    assert_eq!(
+        created,
+        vec![
+            object::Handle::new(ROOT_KEY_ID + 1, object::Type::AuthenticationKey),
+            object::Handle::new(0xFFFE, object::Type::Opaque),
+        ]
+    );
+
+    assert_eq!(plan.conflicts.len(), 1);
+    assert_eq!(plan.conflicts[0].spec.object_id, 5);
+    assert_eq!(
+        plan.conflicts[0].differences,
+        vec![Difference::Capabilities {
+            expected: Capability::SIGN_EDDSA,
+            actual: Capability::SIGN_EDDSA | Capability::EXPORTABLE_UNDER_WRAP,
+        }]
+    );
+
+    // The default authentication key isn't part of the profile
+    assert_eq!(plan.remove.len(), 1);
+    assert_eq!(plan.remove[0].object_id, ROOT_KEY_ID);
+    assert_eq!(plan.remove[0].object_type, object::Type::AuthenticationKey);
+
+    assert_eq!(
+        plan.audit,
+        vec![AuditChange::Command {
+            command: command::Code::SignEddsa,
+            current: AuditOption::On,
+            desired: AuditOption::Fix,
+        }]
+    );
+}
+
+#[cfg(all(feature = "setup", feature = "mockhsm"))]
+#[test]
+fn reconcile_test() {
+    use yubihsm::{
+        asymmetric, command,
+        setup::{AsymmetricKey, Report},
+        AuditOption, Client, Connector,
+    };
+
+    let connector = Connector::mockhsm();
+    let mut client = Client::open(connector.clone(), Default::default(), true).unwrap();
+
+    // Existing signing key which must survive reconciliation
+    client
+        .generate_asymmetric_key(
+            5,
+            "signing key".into(),
+            Domain::DOM1,
+            Capability::SIGN_EDDSA,
+            asymmetric::Algorithm::Ed25519,
+        )
+        .unwrap();
+
+    let existing_public_key = client.get_public_key(5).unwrap();
+
+    let role_key_id = ROOT_KEY_ID + 1;
+    let role_key = authentication::Key::random();
+    let role = Role::new(Credentials::new(role_key_id, role_key.clone()))
+        .authentication_key_label("signer")
+        .capabilities(Capability::SIGN_EDDSA | Capability::CHANGE_AUTHENTICATION_KEY)
+        .domains(Domain::DOM1);
+
+    let signing_key = |capabilities| {
+        AsymmetricKey::new(5, asymmetric::Algorithm::Ed25519)
+            .label("signing key")
+            .capabilities(capabilities)
+            .domains(Domain::DOM1)
+    };
+
+    let new_key = AsymmetricKey::new(6, asymmetric::Algorithm::Ed25519)
+        .label("new key")
+        .capabilities(Capability::SIGN_EDDSA)
+        .domains(Domain::DOM1);
+
+    let profile = Profile::default()
+        .roles(vec![role])
+        .asymmetric_keys(vec![signing_key(Capability::SIGN_EDDSA), new_key])
+        .command_audit_option(command::Code::SignEddsa, AuditOption::Fix);
+
+    let report = profile.reconcile(&mut client).unwrap();
+
+    // Missing objects were created, and the existing key was left in place
+    assert_eq!(client.get_public_key(5).unwrap(), existing_public_key);
+    assert_eq!(report.public_keys.len(), 2);
+    assert_eq!(report.public_keys[0].key_id, 5);
+    assert_eq!(
+        report.public_keys[0].public_key().unwrap(),
+        existing_public_key
+    );
+    assert_eq!(
+        report.public_keys[1].public_key().unwrap(),
+        client.get_public_key(6).unwrap()
+    );
+
+    assert_eq!(
+        client
+            .get_command_audit_option(command::Code::SignEddsa)
+            .unwrap(),
+        AuditOption::Fix
+    );
+
+    Client::open(
+        connector.clone(),
+        Credentials::new(role_key_id, role_key.clone()),
+        true,
+    )
+    .unwrap();
+
+    // The device now matches the profile, apart from the default auth key
+    let plan = profile.plan(&client).unwrap();
+    assert!(plan.create.is_empty());
+    assert!(plan.conflicts.is_empty());
+    assert!(plan.audit.is_empty());
+
+    // Reconciling again replaces the stored report
+    let report = profile.reconcile(&mut client).unwrap();
+    let stored_report: Report = String::from_utf8(client.get_opaque(0xFFFE).unwrap())
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(stored_report.uuid, report.uuid);
+
+    // Objects which differ from the profile are refused...
+    let conflicting_profile = Profile::default().asymmetric_keys(vec![signing_key(
+        Capability::SIGN_EDDSA | Capability::EXPORTABLE_UNDER_WRAP,
+    )]);
+
+    assert!(conflicting_profile.reconcile(&mut client).is_err());
+    assert_eq!(client.get_public_key(5).unwrap(), existing_public_key);
+
+    // ...unless replacing them was requested
+    let report = conflicting_profile
+        .replace_conflicting(true)
+        .reconcile(&mut client)
+        .unwrap();
+
+    let replaced_public_key = client.get_public_key(5).unwrap();
+    assert_ne!(replaced_public_key, existing_public_key);
+    assert_eq!(
+        report.public_keys[0].public_key().unwrap(),
+        replaced_public_key
+    );
+    assert_eq!(
+        client
+            .get_object_info(5, object::Type::AsymmetricKey)
+            .unwrap()
+            .capabilities,
+        Capability::SIGN_EDDSA | Capability::EXPORTABLE_UNDER_WRAP
+    );
+
+    // Rotating a role's credentials changes its authentication key in place
+    let rotated_key = authentication::Key::random();
+    let rotated_role = Role::new(Credentials::new(role_key_id, rotated_key.clone()))
+        .authentication_key_label("signer")
+        .capabilities(Capability::SIGN_EDDSA | Capability::CHANGE_AUTHENTICATION_KEY)
+        .domains(Domain::DOM1);
+
+    Profile::default()
+        .roles(vec![rotated_role])
+        .rotate_credentials(vec![Credentials::new(role_key_id, role_key.clone())])
+        .reconcile(&mut client)
+        .unwrap();
+
+    assert!(Client::open(
+        connector.clone(),
+        Credentials::new(role_key_id, role_key),
+        false
+    )
+    .is_err());
+
+    Client::open(connector, Credentials::new(role_key_id, rotated_key), false).unwrap();
+}
+
+#[cfg(all(feature = "setup", feature = "mockhsm"))]
+#[test]
+fn plan_label_test() {
+    use yubihsm::{
+        asymmetric,
+        setup::{plan::Difference, AsymmetricKey},
+        Client, Connector,
+    };
+
+    let client = Client::open(Connector::mockhsm(), Default::default(), true).unwrap();
+
+    // Existing key which matches the profile apart from its label
+    client
+        .generate_asymmetric_key(
+            5,
+            "old label".into(),
+            Domain::DOM1,
+            Capability::SIGN_EDDSA,
+            asymmetric::Algorithm::Ed25519,
+        )
+        .unwrap();
+
+    let relabelled_key = AsymmetricKey::new(5, asymmetric::Algorithm::Ed25519)
+        .label("new label")
+        .capabilities(Capability::SIGN_EDDSA)
+        .domains(Domain::DOM1);
+
+    let plan = Profile::default()
+        .asymmetric_keys(vec![relabelled_key])
+        .plan(&client)
+        .unwrap();
+
+    assert!(plan
+        .unchanged
+        .iter()
+        .all(|spec| spec.handle() != object::Handle::new(5, object::Type::AsymmetricKey)));
+
+    assert_eq!(plan.conflicts.len(), 1);
+    assert_eq!(
+        plan.conflicts[0].differences,
+        vec![Difference::Label {
+            expected: "new label".into(),
+            actual: "old label".into(),
+        }]
+    );
+}
+
+#[cfg(all(feature = "setup", feature = "mockhsm"))]
+#[test]
+fn fixed_audit_option_test() {
+    use yubihsm::{command, setup::plan::AuditChange, AuditOption, Client, Connector};
+
+    let mut client = Client::open(Connector::mockhsm(), Default::default(), true).unwrap();
+    client
+        .set_command_audit_option(command::Code::SignEddsa, AuditOption::Fix)
+        .unwrap();
+
+    let profile =
+        Profile::default().command_audit_option(command::Code::SignEddsa, AuditOption::Off);
+
+    // Fixed audit options can't be changed, so they're conflicts rather than
+    // planned changes
+    let plan = profile.plan(&client).unwrap();
+    assert!(plan.audit.is_empty());
+    assert_eq!(
+        plan.audit_conflicts,
+        vec![AuditChange::Command {
+            command: command::Code::SignEddsa,
+            current: AuditOption::Fix,
+            desired: AuditOption::Off,
+        }]
+    );
+    assert!(!plan.is_empty());
+
+    assert!(profile.reconcile(&mut client).is_err());
+    assert_eq!(
+        client
+            .get_command_audit_option(command::Code::SignEddsa)
+            .unwrap(),
+        AuditOption::Fix
+    );
+}
+
+#[cfg(all(feature = "setup", feature = "mockhsm"))]
+#[test]
+fn reconcile_rotation_failure_test() {
+    use yubihsm::{Client, Connector};
+
+    let connector = Connector::mockhsm();
+    let mut client = Client::open(connector.clone(), Default::default(), true).unwrap();
+
+    let role_key_id = ROOT_KEY_ID + 1;
+    let role_key = authentication::Key::random();
+    let role = |key: &authentication::Key, capabilities| {
+        Role::new(Credentials::new(role_key_id, key.clone()))
+            .authentication_key_label("rotated role")
+            .capabilities(capabilities)
+            .domains(Domain::DOM1)
+    };
+
+    let capabilities = Capability::GET_PSEUDO_RANDOM | Capability::CHANGE_AUTHENTICATION_KEY;
+
+    Profile::default()
+        .roles(vec![role(&role_key, capabilities)])
+        .reconcile(&mut client)
+        .unwrap();
+
+    // The role remains usable with its current key, and not the new one
+    let assert_role_unchanged = |new_key: &authentication::Key| {
+        let role_client = Client::open(
+            connector.clone(),
+            Credentials::new(role_key_id, role_key.clone()),
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(role_client.get_pseudo_random(32).unwrap().len(), 32);
+
+        assert!(Client::open(
+            connector.clone(),
+            Credentials::new(role_key_id, new_key.clone()),
+            false
+        )
+        .is_err());
+    };
+
+    // Changing the key fails if the current credentials are wrong
+    let new_key = authentication::Key::random();
+    let wrong_credentials = Credentials::new(role_key_id, authentication::Key::random());
+
+    assert!(Profile::default()
+        .roles(vec![role(&new_key, capabilities)])
+        .rotate_credentials(vec![wrong_credentials])
+        .reconcile(&mut client)
+        .is_err());
+
+    assert_role_unchanged(&new_key);
+
+    // Roles which can't change their own key are refused before any changes
+    assert!(Profile::default()
+        .roles(vec![role(&new_key, Capability::GET_PSEUDO_RANDOM)])
+        .rotate_credentials(vec![Credentials::new(role_key_id, role_key.clone())])
+        .replace_conflicting(true)
+        .reconcile(&mut client)
+        .is_err());
+
+    assert_role_unchanged(&new_key);
+}
@@ -1,7 +1,7 @@
 use super::EncryptionTestVector;
 
 /// AES-CCM test vectors (from RFC 3610, converted to Rust bytestring literals)
-// TODO: we don't actually test against these yet, but we should!
+// NOTE: also included by the `ccm` module's unit tests
 pub const AESCCM_TEST_VECTORS: &[EncryptionTestVector] = &[
     EncryptionTestVector {
         key: b"\xC0\xC1\xC2\xC3\xC4\xC5\xC6\xC7\xC8\xC9\xCA\xCB\xCC\xCD\xCE\xCF",
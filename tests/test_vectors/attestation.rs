@@ -0,0 +1,106 @@
+//! Synthetic attestation certificate chain (generated with the `cryptography`
+//! Python package)
+
+/// Self-signed RSA-2048 root certificate
+pub const ATTESTATION_ROOT_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIDMTCCAhmgAwIBAgICEjQwDQYJKoZIhvcNAQELBQAwUDELMAkGA1UEBhMCVVMx
+GTAXBgNVBAoMEEV4YW1wbGUgVGVzdCBPcmcxJjAkBgNVBAMMHVRlc3QgWXViaUhT
+TSBBdHRlc3RhdGlvbiBSb290MCAXDTE5MDEwMTAwMDAwMFoYDzIwNjkwMTAxMDAw
+MDAwWjBQMQswCQYDVQQGEwJVUzEZMBcGA1UECgwQRXhhbXBsZSBUZXN0IE9yZzEm
+MCQGA1UEAwwdVGVzdCBZdWJpSFNNIEF0dGVzdGF0aW9uIFJvb3QwggEiMA0GCSqG
+SIb3DQEBAQUAA4IBDwAwggEKAoIBAQDXFWLpyP0+f44IqttLlxBPqU7qb93MsXad
+3RyH+gecg5Gn/DczP5I60zGl/Fz4MeJisQlLibN3SpjDIIT9FxJOHpeUpH39bSiY
+3mHRhccFbHLiXjTvDgbg/PFg8WNulPsw412oqgKwEbEEsYHs4kkOqexrNdl7XT0i
+sG36K9/CBtWpEFCNKcl0npFfq/gp9idlVLcUMhB9XfzHzGwKE3KCzyrU53Y9P/Un
+ZXxeGNAucaAe2pzrQ3iSp98c0n1bDXPVhaeW/zIM3Apt66I8yt6K5MfH2RT+v4AJ
+e/4oV6BLBYzAHAqsXNPdWA2nmn4NS2SI1hQ5HY+ym3d3WWopBak7AgMBAAGjEzAR
+MA8GA1UdEwEB/wQFMAMBAf8wDQYJKoZIhvcNAQELBQADggEBAGU4SFn/6+trRnvL
+LBOuynrRFNjtfU9xXDn+OPAtqdx8g6WvBTtFxMBxQOdh00DBMjcqZXL4dqKru1/t
+v1gYMv/xnYIQbe/QFYyt8q8RpQAG/SzSbaIvy9g26VzwVz7Lkh52iVdIVqaqPYM/
+7yCTFfJYZwpd2ztNO0yEi5juc2NstiMTacuw5L9Z4oMBvv52Ca/lgqaQSktOLyWV
+s8AQZW6Y0f10dqbaTY7cYBsaYsq0ZxiXWLfl/EnA009Ul+CpEwVFIPPEOrzkwr7r
+ZImKioZVlz7GSX4wKy28Oo1mLaphFXgXTMmqjPi5o+VhVE+Q8Hs/MTMmraIIKONy
+5uxamxI=
+-----END CERTIFICATE-----
+";
+
+/// Unrelated self-signed RSA-2048 root certificate
+pub const ATTESTATION_OTHER_ROOT_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIDCzCCAfOgAwIBAgICVngwDQYJKoZIhvcNAQELBQAwPTELMAkGA1UEBhMCVVMx
+GTAXBgNVBAoMEEV4YW1wbGUgVGVzdCBPcmcxEzARBgNVBAMMCk90aGVyIFJvb3Qw
+IBcNMTkwMTAxMDAwMDAwWhgPMjA2OTAxMDEwMDAwMDBaMD0xCzAJBgNVBAYTAlVT
+MRkwFwYDVQQKDBBFeGFtcGxlIFRlc3QgT3JnMRMwEQYDVQQDDApPdGhlciBSb290
+MIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEAmAQBcPlhGEcvduP3OOii
+6Mhu9aoqiDq+Kb//gzdeE3Qn+6k3HicrcX5GZgmr/xyt01om97HmBFXprtkPp9u7
+FtFlRGle3nOZYQH3R8z8nSFKbnkIMHMSiN/lY8P4tUEsJT6yGbyatxrhkjWAS8ai
+RxujYLJAdZ7pEHz2bneT60TZie+mT5iDhjANlmRRN8yy0HlBvMZzzB5PDIt3DunL
+538FPfzihYRIPExpDwo89wBQrMSmQEvYxdl1n/OTBCp/jBALOGyI44Aq4smELjvw
+QqJ+oDYEs/oziWCETrcPNkuwvqlF5fCq9JTFApril3ZLdUcn/vp8h2OTzwIJVyFe
+PQIDAQABoxMwETAPBgNVHRMBAf8EBTADAQH/MA0GCSqGSIb3DQEBCwUAA4IBAQAx
+j+mAfY6gI+biSjx6wwd9dviP5t9iNpdLUOqJwajXA/c1RNjdXZg+eCQmr4htv5Pv
+28pR5GWHSbMZYrhbzFJBdEPN1z2sNs5llapU1gpXzYWXw6kYPu3c8nPo/ln+3W08
+cYhvQXC/4i9UxkhuxfPZCajDMoRwHh9X2PJO/tIFSm/BdPFEp/7wQ+z5KLe0IgBy
+Bdef0NWDCVvt4+vwcP6sFdHvVSy1xS3U/GxSOcBpWcsxulT0WTTSWoQjKc0Eus+s
+R6D6a1f6NqLVF6KtclTWSMKiOi7cnDEDnsDXlGZqltCK20DVpOBkZsX77iz4RkoQ
+2vsaBwBYipnK/lzT8EFn
+-----END CERTIFICATE-----
+";
+
+/// Self-signed RSA-2048 root certificate with the same name as the root, but
+/// a different key
+pub const ATTESTATION_SAME_NAME_ROOT_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIDQzCCAiugAwIBAgIUGi54fiAmcM4WHlg7jetHwP7t7ewwDQYJKoZIhvcNAQEL
+BQAwUDELMAkGA1UEBhMCVVMxGTAXBgNVBAoMEEV4YW1wbGUgVGVzdCBPcmcxJjAk
+BgNVBAMMHVRlc3QgWXViaUhTTSBBdHRlc3RhdGlvbiBSb290MCAXDTE5MDEwMTAw
+MDAwMFoYDzIwNjkwMTAxMDAwMDAwWjBQMQswCQYDVQQGEwJVUzEZMBcGA1UECgwQ
+RXhhbXBsZSBUZXN0IE9yZzEmMCQGA1UEAwwdVGVzdCBZdWJpSFNNIEF0dGVzdGF0
+aW9uIFJvb3QwggEiMA0GCSqGSIb3DQEBAQUAA4IBDwAwggEKAoIBAQDcuNYFhYcd
+saNMP8CkkWWeGqd+OY4msFRuya3ixoZ8Xju/IFpSkyQKcsroEvY4X7M62WCGpNMv
+W6SR4bGVcZ9V23DYSe5jBGSy1GR7iE9RqJFVzkTuIEcG10nlx4kkw56SRTCA8dOf
+z2qezEtpB3NJYD2gBPXfIvgHTG2wWkCeBurxvDV1Ndr0JtHJXhILSSic2I6uxHIU
+zAjUCFOj/Fuze1DIMsmCb4fTPKYIIIL7N1ohAswT0JsjDcJl4AcUQ5nE3vr+/AEH
+jaJGigh9Gss5xiWabAEin4rN4bKfNDZmVlF1sQVVjtZ7UcT66aFb5ExgMY/9HRTA
+kgLi9muM/saLAgMBAAGjEzARMA8GA1UdEwEB/wQFMAMBAf8wDQYJKoZIhvcNAQEL
+BQADggEBABU7QrIwA2M6MZiEsGSaLoUbKpgVIsiAdXIZddnd5bm1gJT54kgG/aJm
+YEox6jVuWqUjf/w8e1Ie3mGLB63OrxKpTP7IIqduf4NWmz3oNFnrtOQlQu2ryOn4
+r9MMyyB4rgr5gMLyNDr2fBxbfBwgIIICXeGPOUh7MoLoR+OwYmAwViOpcY56E4mp
+UgbqeGleaXdp2vZEyJipjEH92HUZKieQnfMOwtJhqWBT24UKkI0YA9qwLh8pV7Er
+29c/pINPiSPYuKbTrVmY2DHlhZUeR5m33xx/HROL7tI/mieEJiIXIJCTZmLPmwrL
+FDjwzxv5PuDToNZ4BMiqd6l9LbUokbo=
+-----END CERTIFICATE-----
+";
+
+/// NIST P-256 device attestation certificate, signed by the root
+pub const DEVICE_ATTESTATION_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIICQDCCASigAwIBAgIDEtaHMA0GCSqGSIb3DQEBCwUAMFAxCzAJBgNVBAYTAlVT
+MRkwFwYDVQQKDBBFeGFtcGxlIFRlc3QgT3JnMSYwJAYDVQQDDB1UZXN0IFl1YmlI
+U00gQXR0ZXN0YXRpb24gUm9vdDAgFw0xOTAxMDEwMDAwMDBaGA8yMDY5MDEwMTAw
+MDAwMFowKTEnMCUGA1UEAwweWXViaUhTTSBBdHRlc3RhdGlvbiAoMDAxMmQ2ODcp
+MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEqQcjKFILM2PEkAT3JAKEH8JoBwUz
+bpbcdsImGoH0zxcDKXtuLMm/ZUh+y/9bu90HXMdu1zXm0gk/X7n1/2qJ/aMTMBEw
+DwYDVR0TAQH/BAUwAwEB/zANBgkqhkiG9w0BAQsFAAOCAQEAFUwBykP0Wwy7acQ3
+PTINQlgr2X2Eb1DSZ0H0fTlAuC81J1HbGiSP84ChIrktpBP6dLnRSlsL/cJvS0c6
+r1QWqR6/zWWNdv/Kuv6RAIXVZmHQt3p782/D5EurEmMFuu+8SvoAzHBe01qD5QiP
+FUbcCk5Oi7ZXw5DVZmXy24jb6lfo61LrHGTq7q7Ya7k/dWhBkNMhn2Dax9vlkCAP
+BsaIfPGoxFIeXLiFtjkoeIMPuVylJO1+YhdpFM6EzFH4JDFzwDrvRtAoBf7KDdqv
+PjRJb+mcHjw5ZAd0o1O9WoaxvLHmanntIN9NQaFAaF18osmHVswNJy5idquOv6fq
+tK6ZSw==
+-----END CERTIFICATE-----
+";
+
+/// Attestation certificate for the NIST P-256 test key (i.e.
+/// `P256_PUBLIC_KEY`) with object ID 100, signed by the device
+pub const KEY_ATTESTATION_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIB6TCCAY+gAwIBAgIBZDAKBggqhkjOPQQDAjApMScwJQYDVQQDDB5ZdWJpSFNN
+IEF0dGVzdGF0aW9uICgwMDEyZDY4NykwIBcNMTkwMTAxMDAwMDAwWhgPMjA2OTAx
+MDEwMDAwMDBaMCgxJjAkBgNVBAMMHVl1YmlIU00gQXR0ZXN0YXRpb24gaWQ6MHgw
+MDY0MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEYP7UuiVanTHJYet0xjVtaMBJ
+uJI7Yfps5mliLmDyn7Z5A/4QCLi8maQa6elWKLxk8vGyDC1+n1F3o8KU1EYimaOB
+pjCBozATBgorBgEEAYLECgQBBAUEAwIAADATBgorBgEEAYLECgQCBAUCAxLWhzAS
+BgorBgEEAYLECgQDBAQDAgABMBMGCisGAQQBgsQKBAQEBQMDAAABMBkGCisGAQQB
+gsQKBAUECwMJAAAAAAAAAQCAMBEGCisGAQQBgsQKBAYEAwIBZDAgBgorBgEEAYLE
+CgQJBBIMEGF0dGVzdGF0aW9uIHRlc3QwCgYIKoZIzj0EAwIDSAAwRQIhAIwedpQP
+LMpkrHRRdxfDomaHZz+7zYEUm6ZYm6fk/AMLAiAFxPTKzUB0AnLp/VP9Hm+6iUXf
+nsqrqf07gK8YbB0NdQ==
+-----END CERTIFICATE-----
+";
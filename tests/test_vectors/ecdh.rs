@@ -0,0 +1,34 @@
+use super::EcdhTestVector;
+use yubihsm::asymmetric::Algorithm;
+
+/// ECDH test vectors (randomly generated with the `cryptography` Python package)
+pub const ECDH_TEST_VECTORS: &[EcdhTestVector] = &[
+    EcdhTestVector {
+        algorithm: Algorithm::EC_P256,
+        sk: b"\x24\x1D\x15\xF1\x63\xAC\xCE\xEF\xFC\x61\xF9\x70\x8B\xD4\xC6\xBA\x40\x5D\xFD\x52\x99\xB7\xAB\x88\x5B\x1E\x24\x69\x18\xAA\xEC\x33",
+        pk: b"\x65\xA1\x33\x4E\xF0\x1D\x6C\x0C\x22\x85\xDE\xDB\xEE\x50\x7F\xB3\xB4\x0F\xE5\x47\x28\x00\x35\x91\xE8\xC8\x8A\x26\x90\x8E\x72\x46\xEF\x1D\xB4\x74\x4F\x4A\xDE\x94\xD3\xD0\x0E\x39\x85\x80\xDE\x3D\xA4\x69\x44\xC1\x19\x4E\x66\x0F\xDA\xB8\xFF\x15\x02\x16\x1A\xAE",
+        peer_pk: b"\x0F\xC6\xFB\xB5\xA0\x62\xF9\x6F\x25\x55\xCE\xB8\x77\xB0\xFC\xE3\x31\x21\x15\x3B\x90\x34\xCE\x94\x4E\xFF\x80\x93\x30\x02\x6C\xDC\xEA\x7B\x45\xC9\xD1\x4B\x26\x01\x78\xD3\x32\xAF\x23\xCF\xCE\x66\x29\xD9\xD4\xEF\xDE\xF9\xF6\xAB\xC1\x7B\x97\x2F\x39\xF4\x2C\x35",
+        shared_secret: b"\x1A\x3A\x12\x3D\x2B\xA2\xF7\x8A\x5B\x4A\xAB\x5E\x5C\x3D\x4E\xD7\xED\x50\x6D\x11\x03\xC4\xEE\x03\xD4\x5E\xB1\x9F\xB8\x14\x58\xA9"
+    },
+    EcdhTestVector {
+        algorithm: Algorithm::EC_P384,
+        sk: b"\x27\x13\xA9\x27\xDB\x67\xE3\x5F\x29\xF8\x97\x55\xAB\x36\xB6\x83\x67\x1F\x96\x05\xF5\x74\xE2\xAE\x91\x38\x6B\x98\xE8\x86\xC3\x16\xCF\xB2\x12\xCD\x70\xA8\x03\x33\x06\x29\x0D\x93\x5D\xE2\x6B\xDF",
+        pk: b"\x43\x92\x79\xE9\xEF\x45\x84\x46\xE5\xDF\xEA\xD2\x3F\x98\x68\xEE\x8F\xD7\xD2\x54\x6B\xB1\x31\x14\xC8\x8F\x9B\xA0\xAC\x12\x04\x5E\xBF\xA3\xBC\xC8\x6A\x74\x47\x8B\xC5\x84\x64\x2E\x31\x15\xEC\x1C\x5C\x0B\x8C\xE3\xF5\x14\x48\x1D\xCE\x78\x58\xC0\xFC\x24\x42\x5E\xF1\xCD\xB1\xEB\xF1\x1E\xCA\xF5\x14\xAF\xAD\x70\xD6\x18\x80\xBA\x34\x9C\x6C\x09\x9E\x6F\xF2\x08\x96\xA6\xE7\x68\x41\xBF\xEE\xEC",
+        peer_pk: b"\x59\xE9\x9D\xF9\x6F\x35\xE7\x7E\xF2\x21\x42\x3B\xDC\xEB\x3C\xE8\xF9\x25\x5A\x7B\xB1\x33\x24\xA1\x9C\x4B\x1E\x58\xC7\x9E\x4C\x47\xE7\xF4\xA4\x53\x5B\x5F\x4C\x3D\xA6\x5D\x78\x91\x01\xAC\xF9\xF1\x14\xCC\x98\xE3\x73\x7D\x89\x31\xB0\xE5\x3A\x95\x3D\x27\x90\x03\x2C\xD3\x80\x3E\xA3\x3A\x16\xC5\xD0\xBC\xFE\x8B\x2D\x98\xAA\xAA\x34\xC0\xAC\x17\xA7\xF3\x80\x51\xBA\xDE\x89\xB9\x9B\xC2\xE4\x5A",
+        shared_secret: b"\xD0\xBE\xF5\x76\x41\x25\xB4\xA4\x4B\x00\x19\x7B\x44\x99\x23\x0D\x34\x35\x5D\x20\x70\x1E\x92\x3A\x73\xEA\x69\x33\x2B\xF9\xD2\xB0\x37\xCF\xE1\x11\x26\x52\x8B\x97\x57\x2C\x9F\x1D\xFF\xCB\xB4\x1D"
+    },
+    EcdhTestVector {
+        algorithm: Algorithm::EC_P521,
+        sk: b"\x00\x8C\xA5\x8D\x17\xF7\x8C\xC7\x04\xB8\xF5\x1A\x37\xFC\xA1\xF4\xDD\xC3\x07\x63\x4D\x59\x4E\x1C\x97\x13\x5C\x68\xA8\x55\x4C\xAE\x3B\x5B\x72\xA9\xD8\x24\xA8\xFF\xDF\xC0\x08\xD8\x8B\xFF\x4C\x84\x25\xA2\x98\x9C\xBF\x11\x96\x01\xFA\x12\x21\xAE\xF1\x60\x14\x77\xD6\x83",
+        pk: b"\x00\x68\x18\xCF\xB6\x79\x93\x8B\xDD\xE4\x05\x23\x9C\x15\xBC\x48\x44\xB7\xE6\x03\x72\x5A\xD1\x66\x2A\x62\x15\x58\x8D\x59\x54\x64\x83\x01\x49\x52\xA7\x88\x85\x7E\x06\xB8\xA9\x4F\x3A\x53\x85\x6F\x45\x49\x45\x35\xD0\x9E\x61\x60\xC7\x79\xB1\xDB\x24\xEC\x10\x5C\x1A\x4A\x00\xD3\xFA\x41\x3C\xE5\x40\x7F\x7C\xFD\x38\x0C\x1E\x43\xF3\x49\x44\x4A\x5D\x1B\x42\xE4\x31\x15\xC7\xE5\x9E\x23\x63\x12\x22\xDB\xAF\x66\x6E\xCF\x1E\x2D\xB3\x03\x0A\x27\x9A\x9D\x84\x79\xC6\xD6\x7E\x25\xAE\xB6\x68\x32\x90\xA3\x44\x65\x52\xD7\x0A\x42\xD1\x5B\x67\xB7",
+        peer_pk: b"\x00\xA3\x8F\x08\x0D\x24\xCC\xA1\xBE\x63\x88\x76\xE0\x06\xC0\xAA\x69\x4C\x1D\x79\x7E\x10\x48\x63\x6B\xAC\xF9\x3C\x50\xF6\xD2\x3F\xE9\x20\x2C\x84\xA1\x63\x6A\x57\xF3\x70\x71\x62\xBE\x16\xE9\xB3\xA2\x45\xE4\x14\x51\xD5\xF0\x4A\x4F\xC4\x5F\x77\xFE\xFC\xFF\xB6\xAD\xC8\x00\xA7\xAB\x6F\xAC\x0C\x2D\xFB\xE1\x53\xB3\xB1\xC8\x7E\x22\x81\x31\xE4\x4E\x3A\x41\xD8\x3B\x35\xFD\xB7\x23\x50\x3B\x07\xC4\x41\x53\xB2\x27\x19\x84\x0E\xE0\xD6\x5B\xC7\x4A\xDD\xA8\x21\xC8\x85\xC1\x56\xD9\x25\xB9\x29\x80\xB5\xB2\x95\xCD\xD6\x2D\xE5\x00\x62\x35\x40",
+        shared_secret: b"\x00\xFC\x26\xA9\xC0\x3B\x92\x5D\xE6\x98\x93\x92\x7C\x68\x0E\x48\x2B\xE4\xDF\x90\xD0\x3A\x40\x9F\xF1\x6A\x94\xB9\xC2\xE3\x29\x64\x66\xB9\x12\xE5\xDC\x40\x3D\x80\x63\x0B\xF3\x3F\x5E\x41\x34\x02\xBD\x1F\x1A\x2C\xE2\x77\xE4\xE7\x4D\x93\xCE\x13\x76\x48\x6E\xFA\x8A\xC0"
+    },
+    EcdhTestVector {
+        algorithm: Algorithm::EC_K256,
+        sk: b"\x7A\x13\xEB\x7C\x6E\x51\x35\xA0\xC9\x2E\xD4\xCE\x19\x42\x86\xD7\x34\x9C\xC4\x96\xFF\xCF\x75\x16\x61\xA5\x23\x3D\xEC\x43\xEE\xE3",
+        pk: b"\xFF\x4F\x9A\x59\xD5\x46\x26\x91\x76\xAC\x42\x4D\x5F\xD3\xA2\x4D\x43\xD4\x1D\xB6\xA7\xBD\x63\x12\x1B\xF7\x0F\x21\x14\xF4\x2B\x0C\x31\x7D\x8C\xC0\x16\xC1\x33\x1C\xB8\x59\xBE\xD9\x63\xE8\x81\x09\xBD\xD2\x2E\xAA\x73\x47\x86\xD6\x31\x2D\xAB\x86\xF4\x63\xA7\x55",
+        peer_pk: b"\x10\x0D\xAF\x7E\x76\x21\xA2\xFF\xCE\xCF\xCB\x35\xA6\xB3\xD0\x72\x76\xD7\x89\x62\x42\x4C\x6A\xB7\xF5\xF5\xD3\x12\xD1\x60\xE0\x6D\x78\xFB\x33\x02\x97\x13\x5F\xFF\xC4\xCB\xBF\x9D\x4E\xD4\xEC\x65\xF6\x84\x0D\xA3\xB3\x36\x21\x9C\x09\x5D\x97\x07\xDC\x70\x6E\xD3",
+        shared_secret: b"\x3E\xCD\x43\x18\x4C\x19\xBB\xF4\xCA\x2E\xD6\x78\x36\xDF\x03\xA3\xBC\xFD\xE4\x40\xEB\x31\x09\xBF\xCF\x9A\x74\xDC\x4A\xCD\x72\xF0"
+    },
+];
@@ -3,15 +3,43 @@
 /// AES-CCM (Counter with CBC-MAC) test vectors
 mod aesccm;
 
+/// Attestation certificate chain
+mod attestation;
+
+/// Elliptic Curve Diffie-Hellman test vectors
+mod ecdh;
+
 /// Ed25519 digital signature test vectors
 mod ed25519;
 
 /// HMAC-SHA-256 test vectors
 mod hmac;
 
+/// Yubico OTP test vectors
+mod otp;
+
+/// Private keys in various formats
+mod private_key;
+
+/// RSA encryption test vectors
+mod rsa;
+
 pub use self::aesccm::AESCCM_TEST_VECTORS;
+pub use self::attestation::{
+    ATTESTATION_OTHER_ROOT_PEM, ATTESTATION_ROOT_PEM, ATTESTATION_SAME_NAME_ROOT_PEM,
+    DEVICE_ATTESTATION_PEM, KEY_ATTESTATION_PEM,
+};
+pub use self::ecdh::ECDH_TEST_VECTORS;
 pub use self::ed25519::ED25519_TEST_VECTORS;
 pub use self::hmac::HMAC_SHA256_TEST_VECTORS;
+pub use self::otp::OTP_TEST_VECTORS;
+pub use self::private_key::{
+    ED25519_OPENSSH, ED25519_PKCS8_PEM, P256_OPENSSH, P256_PKCS8_PEM, P256_PUBLIC_KEY,
+    P256_SEC1_PEM, RSA_2048_OPENSSH, RSA_2048_PKCS1_PEM, RSA_2048_PKCS8_PEM,
+};
+pub use self::rsa::{
+    RSA_2048_MODULUS, RSA_2048_PRIMES, RSA_OAEP_TEST_VECTORS, RSA_PKCS1V15_TEST_VECTORS,
+};
 
 /// Authenticated encryption test vector (presently specialized for AES-CCM)
 pub struct EncryptionTestVector {
@@ -31,6 +59,24 @@ pub struct EncryptionTestVector {
     pub ciphertext: &'static [u8],
 }
 
+/// Elliptic Curve Diffie-Hellman test vector
+pub struct EcdhTestVector {
+    /// Elliptic curve algorithm
+    pub algorithm: yubihsm::asymmetric::Algorithm,
+
+    /// Secret scalar
+    pub sk: &'static [u8],
+
+    /// Public key in untagged `X || Y` form
+    pub pk: &'static [u8],
+
+    /// Peer's public key in untagged `X || Y` form
+    pub peer_pk: &'static [u8],
+
+    /// Expected shared secret (x-coordinate of the ECDH result)
+    pub shared_secret: &'static [u8],
+}
+
 /// Authenticated encryption test vector (presently specialized for AES-CCM)
 pub struct HMACTestVector {
     /// Encryption key
@@ -43,6 +89,63 @@ pub struct HMACTestVector {
     pub tag: &'static [u8],
 }
 
+/// Yubico OTP test vector
+pub struct OtpTestVector {
+    /// YubiKey's OTP key (AES-128)
+    pub key: &'static [u8],
+
+    /// YubiKey's private ID
+    pub private_id: &'static [u8],
+
+    /// OTP (binary, i.e. before ModHex encoding)
+    pub otp: &'static [u8],
+
+    /// Expected use counter
+    pub use_counter: u16,
+
+    /// Expected session counter
+    pub session_counter: u8,
+
+    /// Expected timestamp
+    pub timestamp: u32,
+}
+
+/// RSA decryption test vector
+pub struct RsaDecryptionTestVector {
+    /// Primes `p || q` of the RSA private key
+    pub primes: &'static [u8],
+
+    /// Public modulus of the RSA key
+    pub modulus: &'static [u8],
+
+    /// Plaintext message
+    pub msg: &'static [u8],
+
+    /// Message encrypted under the RSA public key
+    pub ct: &'static [u8],
+}
+
+/// RSA-OAEP decryption test vector
+pub struct RsaOaepTestVector {
+    /// Primes `p || q` of the RSA private key
+    pub primes: &'static [u8],
+
+    /// OAEP algorithm (selects the label digest)
+    pub algorithm: yubihsm::rsa::Algorithm,
+
+    /// Digest used by the MGF1 mask generating function
+    pub mgf1_hash_alg: yubihsm::rsa::mgf::Algorithm,
+
+    /// OAEP label
+    pub label: &'static [u8],
+
+    /// Plaintext message
+    pub msg: &'static [u8],
+
+    /// Message encrypted under the RSA public key
+    pub ct: &'static [u8],
+}
+
 /// Signature test vector
 pub struct SignatureTestVector {
     /// Secret key (i.e. seed)
@@ -0,0 +1,29 @@
+use super::OtpTestVector;
+
+/// Yubico OTP test vectors (generated with the `cryptography` Python package)
+pub const OTP_TEST_VECTORS: &[OtpTestVector] = &[
+    OtpTestVector {
+        key: b"\x2B\x7E\x15\x16\x28\xAE\xD2\xA6\xAB\xF7\x15\x88\x09\xCF\x4F\x3C",
+        private_id: b"\x87\x92\xEB\xFE\x26\xCC",
+        otp: b"\x37\x95\xE5\x04\xF5\x13\x04\x31\x56\xD6\x61\x56\x72\x16\xE7\xB5",
+        use_counter: 19,
+        session_counter: 0,
+        timestamp: 0x24_c0a8,
+    },
+    OtpTestVector {
+        key: b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0A\x0B\x0C\x0D\x0E\x0F",
+        private_id: b"\xC0\xFF\xEE\x12\x34\x56",
+        otp: b"\x00\x0F\x91\xEB\xD7\xA9\xC0\xAB\xBA\x0D\x59\x35\x8B\x23\xEF\x5A",
+        use_counter: 0x1234,
+        session_counter: 7,
+        timestamp: 0x00_0001,
+    },
+    OtpTestVector {
+        key: b"\xFF\xEE\xDD\xCC\xBB\xAA\x99\x88\x77\x66\x55\x44\x33\x22\x11\x00",
+        private_id: b"\x00\x00\x00\x00\x00\xFF",
+        otp: b"\x32\x09\x54\x5B\x1D\xC1\x06\xF3\xC7\xA1\x32\xD6\x99\xAD\x5E\x03",
+        use_counter: 0xfffe,
+        session_counter: 255,
+        timestamp: 0xff_ffff,
+    },
+];
@@ -0,0 +1,60 @@
+use super::{RsaDecryptionTestVector, RsaOaepTestVector};
+use yubihsm::rsa;
+
+/// Primes `p || q` of the RSA-2048 test key (randomly generated)
+pub const RSA_2048_PRIMES: &[u8] = b"\xFC\x62\x1D\x33\xDA\x2C\x18\x2A\xA4\x9D\xC6\x9D\xF3\xAE\xE6\x78\xFC\x7A\x68\x9E\x1B\x9C\x61\x20\xAF\xC1\x40\xB9\x93\xB9\x93\x99\x31\xB4\x61\x46\x7C\x57\x36\x98\xB4\x43\x9A\x40\x52\x53\xA0\x74\x9E\x84\x71\x86\xCE\xBE\x9F\x21\x42\x1E\x89\xF7\x0E\x55\x0C\x9A\xDB\x92\x36\xCE\xBC\x5E\x66\xA5\xA3\xAB\x4C\x58\xFF\x62\x0E\x5B\x1A\x4E\x55\xDA\x63\x2F\xDB\x3B\x88\xB8\xEE\x0B\x71\x1C\x81\xD2\xC9\xD7\x5B\x81\xFC\xF4\x09\x2E\x04\x49\x81\x4E\x24\xD9\xF9\xB5\x09\x36\xB5\x05\xFA\xA5\xFE\x5C\x0E\xDE\x70\xD2\xDF\x54\xCD\xD5\xDC\xFC\x71\xFE\xB7\x27\xFE\x15\xB5\x53\x9A\x11\x06\xBD\xC2\x65\xD3\x00\xD6\xBC\x43\x84\x8C\xBF\x70\x5A\x3A\x6B\x45\x04\xE7\x34\x77\x98\x6E\x0B\xC5\x2D\xA3\x2D\xF7\x90\x95\x8A\xFB\x75\xF3\x6B\x5A\x6C\x58\x06\xF6\x5F\x2B\x0F\x49\x8D\x5B\x61\x10\xFA\x74\xD1\xAC\x39\x75\x89\xBD\x02\x94\xB4\x62\xA9\xC1\xA7\x0E\xDE\x8D\x9E\x7E\x11\x98\xE1\x48\x0C\x27\x5A\xBC\xDD\xCA\xFF\xEC\x87\x86\x91\x8A\xAD\x52\xD8\xFE\xD3\x49\xFF\x95\xB6\x9F\x7F\x31\x3F\x53\x26\x43\x79\x37\x4A\x85\x54\x77\x97\xC5\x50\x7C\x1A\x2D\x67\xEA\x0F";
+
+/// Public modulus of the RSA-2048 test key
+pub const RSA_2048_MODULUS: &[u8] = b"\xD9\xDD\x32\x0F\xBB\x57\x15\x3D\xA1\x81\xD3\x07\xF9\xAC\x8B\x32\x8D\x89\xC3\x25\x1F\x4A\x84\x25\x00\x07\x61\x1E\xA8\xC7\x9C\x59\xAF\xD5\x50\x2B\x2C\xF6\xC7\x4B\x71\x6F\x15\x25\x0A\x9D\xC6\xEF\x0A\x5A\x22\xB7\x28\xD1\x3F\x1B\xD2\x48\xA1\x60\x67\xF8\x86\x7A\x14\x34\x2F\x4A\xE7\x4D\x10\x8D\xB4\x64\x3C\x80\x74\x47\xA0\x2F\x35\x0C\x62\x33\x88\x8B\x8C\x1E\xB2\x12\x8A\x20\x33\x22\xFA\xE5\xE3\xD9\xF9\x1E\xCC\x06\x8A\x20\xE0\x0E\x87\x50\x10\xA4\xEA\xBB\xD7\xE6\x2C\x80\x60\x3A\x61\xDA\xD3\x33\xB5\x90\x80\x92\x04\xB0\x29\x92\xE1\x66\xF0\x8D\xED\xB8\x55\xA9\x01\x8F\x97\x4B\xFE\x27\x1B\x59\x87\xA8\x81\x77\x3F\x41\x72\x86\x6E\x0E\x70\x7E\xA7\x97\xC4\xCF\xC2\xDC\x0D\x61\xC2\xEE\x45\x4F\xE3\x10\x9A\xEC\x72\x80\x87\x93\xB6\x5E\x6D\x76\x52\xC9\xC6\x6A\xF1\x74\xE1\x32\xCE\x08\xBF\x17\x3B\xB3\x8F\x78\x9F\x46\xD0\xC1\xD6\xF6\x85\xD8\x7C\x45\x53\xBE\xA9\xF5\x5B\xF4\xFD\x8E\xB0\xB4\xBB\x19\x08\xE7\x55\x35\xC3\x12\xF2\xDA\x30\x76\x94\x0D\xE6\x62\x79\xE0\x43\xF2\x54\xCF\x2C\xB1\x83\x5F\x12\x78\x34\x70\xA6\x23\x33\x6D\xDB\xCF\xC1\x7B";
+
+/// RSAES-PKCS#1v1.5 test vectors (generated with the `cryptography` Python package)
+pub const RSA_PKCS1V15_TEST_VECTORS: &[RsaDecryptionTestVector] = &[
+    RsaDecryptionTestVector {
+        primes: RSA_2048_PRIMES,
+        modulus: RSA_2048_MODULUS,
+        msg: b"\x54\x68\x65\x20\x59\x75\x62\x69\x48\x53\x4D\x20\x32\x20\x69\x73\x20\x61\x20\x73\x69\x6D\x70\x6C\x65\x2C\x20\x61\x66\x66\x6F\x72\x64\x61\x62\x6C\x65\x2C\x20\x61\x6E\x64\x20\x73\x65\x63\x75\x72\x65\x20\x48\x53\x4D\x20\x73\x6F\x6C\x75\x74\x69\x6F\x6E",
+        ct: b"\x8D\xE6\x12\xDA\x16\x9C\x91\x11\x83\x84\x9F\x2A\xA6\x14\x32\x2F\xA8\xF0\xCC\xCD\xCC\x43\x5C\xD6\xB4\x5D\x16\x2F\x63\x63\x50\x46\x46\x67\xC0\xFA\xEA\x8E\x59\x25\x52\x98\x39\x88\x6A\xA8\x38\x13\x2C\x1F\xF0\xCB\x66\x69\x07\x66\xE5\x3A\x4C\xE9\xB1\x43\xE9\x76\x42\x0F\x90\xF5\x87\xE3\x64\x93\x5C\xCC\x61\x5B\x93\xA4\x6B\x2B\xBE\xCF\xCF\x03\x01\x6F\x0F\x87\xFC\x73\x75\x0E\x85\xAC\x8B\xD1\x5D\xA7\x55\x5B\x7C\x6F\xD7\x59\xF1\x49\xD2\x8A\x17\x67\x1A\x0B\xA4\xCC\x3B\xF9\x8B\xB5\x05\x87\x83\x2E\x9E\x65\x47\x63\x00\xE9\x9A\xB5\x62\x34\x13\x3D\x7C\x38\xC5\x91\xAC\x06\x51\x9B\xD9\x50\xD2\xB9\x92\xC2\xC5\xE4\xBB\xE1\x84\x75\xD4\x14\x85\xF0\xB0\x00\x0A\x57\x50\x1B\xBC\xAB\xBF\xC6\x08\x51\x33\x73\x3C\x25\x55\xEA\x64\x19\xC9\x77\xA2\xA4\xA3\x85\x0F\xFC\xBD\x6E\x79\x72\x1D\xC7\x58\x8F\xA2\x93\x78\x34\x7A\xE9\x40\xD1\xA5\xF6\x89\xE9\x37\x0D\x77\x77\x7F\x91\x89\x12\x17\x9D\xF6\xFF\x1E\x58\xB7\x73\x37\x2C\xCF\x7D\xE7\x6E\xFB\xB2\x1F\x05\xC6\x38\x65\xB9\xFA\xDE\x75\x94\x71\xD5\xF8\xC2\xE9\x40\xCC\x7F\x56\x05\x03\x6F\xC7\xA3\x8B\xFE"
+    },
+    RsaDecryptionTestVector {
+        primes: RSA_2048_PRIMES,
+        modulus: RSA_2048_MODULUS,
+        msg: b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0A\x0B\x0C\x0D\x0E\x0F\x10\x11\x12\x13\x14\x15\x16\x17\x18\x19\x1A\x1B\x1C\x1D\x1E\x1F",
+        ct: b"\x2B\xA7\x2B\x74\xB5\x5D\x1B\x1F\xDB\x73\x27\x53\x2F\x4A\xA9\x4E\x79\xBB\x6E\xD9\x04\x7A\xF4\x63\xBF\x41\xC8\x83\xA5\xD6\x45\x1E\x8A\x12\x31\xA5\x2C\xFB\xEB\x72\xC3\x1D\xB6\xA1\xB8\xB9\x2C\xE2\xD7\x6F\xF2\xC6\x8B\xDC\x83\x28\x22\x00\x05\x6C\x0F\xCB\x9F\x64\xA8\xE0\xEE\x77\x1E\xD4\xAB\x37\x3B\xCC\x9B\xA6\x22\x86\xF2\xE1\x1B\x68\xC0\xD7\x53\xD3\x60\xEA\x65\x34\x85\x3E\x65\x70\xED\x0E\x83\xB4\x54\xAA\xC0\xDB\xB4\x1E\x1F\x6C\xFF\xFF\xC0\x16\x1A\x45\xE7\x4A\x60\xD1\xC5\x66\x65\x53\xA0\x36\x7C\x36\x74\x07\x94\xD3\x12\x64\x7B\x50\xC3\x8C\x3A\x3C\xB4\x20\x8A\x1B\x4D\xB2\x64\xF1\x15\xFC\x3D\x3C\x4B\x56\x5F\xBC\xA3\xAE\x66\xEB\x97\xB0\x78\x0A\xA1\xBA\x04\xA2\xA4\xF4\xC4\xF0\xDE\xBB\xC3\x56\x50\x4E\xF2\xBE\x84\x25\x04\x60\xF6\x45\xC4\xB5\xBC\xD0\x4A\x1A\xCB\x6A\x07\xA2\x4F\x54\x02\x8F\x91\x78\xAF\x50\xB7\x32\xDF\x64\xE2\x94\x3B\x4D\x79\x53\x57\xFF\xD9\x85\xAB\x13\x8F\xC5\x04\x13\xEF\x10\xD4\x86\xE1\x33\x94\x2E\x5D\x13\xE1\xA4\xC8\x44\xB4\x96\x24\xFD\x84\xAA\x18\x2E\x95\xB0\x15\x03\x63\xF3\xC1\x6C\x8B\xED\xD4\x6E\x32\xDF"
+    },
+];
+
+/// RSAES-OAEP test vectors (generated with the `cryptography` Python package)
+pub const RSA_OAEP_TEST_VECTORS: &[RsaOaepTestVector] = &[
+    RsaOaepTestVector {
+        primes: RSA_2048_PRIMES,
+        algorithm: rsa::Algorithm::OAEP_SHA1,
+        mgf1_hash_alg: rsa::mgf::Algorithm::SHA1,
+        label: b"",
+        msg: b"\x54\x68\x65\x20\x59\x75\x62\x69\x48\x53\x4D\x20\x32\x20\x69\x73\x20\x61\x20\x73\x69\x6D\x70\x6C\x65\x2C\x20\x61\x66\x66\x6F\x72\x64\x61\x62\x6C\x65\x2C\x20\x61\x6E\x64\x20\x73\x65\x63\x75\x72\x65\x20\x48\x53\x4D\x20\x73\x6F\x6C\x75\x74\x69\x6F\x6E",
+        ct: b"\xB0\xC2\x26\xBA\xA8\x04\x64\x05\xAF\x8A\x11\xAF\xF6\x48\xCE\x14\x38\xBA\xF9\x57\x0E\xD4\x41\x6A\xA6\x24\x07\x46\x5D\x89\x5C\x19\x4C\xAE\x3A\xDB\x9C\x1A\xFA\x0A\xE1\xC3\xB2\x21\x33\x3B\x3B\xE1\x9F\x87\xF1\xF9\x54\x32\xBD\x43\xFA\xEA\x09\xF0\x48\xFC\xD6\x65\x5D\x19\xE2\xC8\xB2\x0B\x6C\xC9\x08\xDE\xF3\x45\x5B\x06\xCE\x8A\xDC\xEA\x8C\xAF\x7F\x68\x1E\x72\x0C\xA9\x9C\x91\xEF\x01\xD9\x76\xBE\x2B\xC6\x6C\x84\xFB\x9F\x8E\x9D\xAF\x4B\x74\x54\x5B\x07\x1C\xE3\x61\xF3\xB4\x36\xCF\xB2\x31\x6D\xE5\xA2\x72\x09\x08\x9E\xFE\x43\xB7\x92\x6C\x3E\xBF\x45\x94\x2D\x13\xE6\x70\xDF\x38\x14\x0E\x41\x12\x10\x61\xAB\xF6\x75\xE0\xA0\xBF\xA2\xA5\x6C\x91\x63\x42\x06\xCC\x53\x4B\x86\x3C\x6A\x24\x45\xEC\x70\xFE\x79\xEC\xCA\x72\xD0\xF2\x27\x8B\x26\xAA\x0C\x61\x5E\xAB\x6F\xA6\x83\xFC\x82\x3D\x40\x07\x73\x4B\x69\xAB\x8B\x34\x8D\x55\x43\x21\x31\xD1\xA6\x57\x51\x8F\xDE\x67\x3C\xE0\x56\xE2\x8E\xE8\x2C\xF1\xC2\x82\xAC\x0A\xB4\x27\xFE\x06\x90\x70\x91\x8D\x28\x8A\xD7\xA4\xB0\xF3\x63\x50\xC2\x14\xA6\xF5\xAC\xC0\x10\x4F\x29\xB6\xF5\x15\x8F\x82\x86\xCA"
+    },
+    RsaOaepTestVector {
+        primes: RSA_2048_PRIMES,
+        algorithm: rsa::Algorithm::OAEP_SHA256,
+        mgf1_hash_alg: rsa::mgf::Algorithm::SHA256,
+        label: b"yubihsm.rs test label",
+        msg: b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0A\x0B\x0C\x0D\x0E\x0F\x10\x11\x12\x13\x14\x15\x16\x17\x18\x19\x1A\x1B\x1C\x1D\x1E\x1F",
+        ct: b"\x33\x2D\xE7\x31\xE2\xC0\xAF\x10\x69\x9B\xDF\xA5\xAE\x89\x6E\x14\x38\xBB\x74\x58\xCA\x76\x11\xFB\xC1\x51\xD8\x01\x4D\x77\xCC\x9F\x3F\xCC\x21\x61\x37\x2E\x9E\x04\x48\x15\x4C\x39\x88\xB7\xF2\xDC\x0B\x6C\x92\x13\xE2\x81\x43\x2C\xB9\xD8\x3A\x69\xF6\x6B\x2B\x60\xFC\x95\x6B\x48\x1B\xF9\xAF\x08\xF4\xDC\x3F\x50\xC3\x9E\x94\xC5\xBE\x00\xD3\x35\xE6\xE8\xD9\x28\xD3\xC5\x2A\xD4\x01\x77\x48\xB5\xAC\xA3\xB7\x44\x96\x67\xFE\x76\x9C\x8B\xEF\xC9\xE3\x38\x59\xBC\x97\x45\xA8\x9B\xF5\xE8\xA4\x28\xDC\x72\xBE\x68\xE0\xBF\x66\xBC\x0B\xAE\x3F\x67\x3D\x89\x90\xD7\xA5\x2F\xE4\x38\x56\x7D\xFF\x52\xFC\x6F\x8B\x65\x38\xF0\x3C\x27\x5C\x25\xD3\xEF\xB5\xAA\x75\x96\xA9\xBF\x2C\x89\x3F\x70\x93\xF5\xC6\xC6\x1F\xB1\xE9\xE0\x67\x13\xDA\x7B\x28\x83\xD7\x30\x40\x70\x07\xE5\x9F\x1F\x54\x41\xCC\x4D\xCB\xAA\xB6\xAD\x5E\x24\x1A\x90\x7D\xD4\x8D\x4C\xF1\x83\x52\x17\x37\xAD\xDB\x0F\x10\xF9\x96\x25\x13\xA3\xE8\x31\xE9\x5C\xCA\xE6\x25\x05\x13\x07\xA5\xF6\x99\x50\xDB\x32\x47\x85\x42\xA6\x45\x0B\xD0\x70\xE4\xD8\x98\xBE\x40\x51\x12\x87\xE4\x85\x81\x26\x88\xA3"
+    },
+    RsaOaepTestVector {
+        primes: RSA_2048_PRIMES,
+        algorithm: rsa::Algorithm::OAEP_SHA384,
+        mgf1_hash_alg: rsa::mgf::Algorithm::SHA1,
+        label: b"",
+        msg: b"\x4F\x41\x45\x50\x20\x77\x69\x74\x68\x20\x61\x20\x64\x69\x66\x66\x65\x72\x65\x6E\x74\x20\x4D\x47\x46\x31\x20\x64\x69\x67\x65\x73\x74",
+        ct: b"\x79\x20\x55\x3D\x40\x4E\x92\x9C\xC4\x4E\x85\xE9\x74\x1B\x9D\x7F\x2E\xE7\x17\x9D\x47\xE7\xEC\xD9\xDF\xDA\x89\xEF\x20\x41\x07\x60\x22\xEF\xC2\xF7\x49\x45\x73\xD1\x63\xCA\x60\x9D\x11\xF5\x51\xAB\xFD\x55\xDC\xE3\x10\x83\x63\xDD\xAF\xA7\x22\x53\x24\x0D\x11\x52\xEA\x07\x3E\xE8\xC2\xED\xDA\xAE\x7A\x9D\x22\x93\xE3\x07\xEA\x66\xB0\x42\x6F\x3F\xC0\xB6\xD2\x6F\xC3\x18\x93\x13\x61\x3F\x8D\x25\x9C\x55\xC3\xAF\xCB\x14\x90\xF5\xA6\x90\x4F\x39\x28\x51\x50\xF2\x29\x28\x55\xDC\x9A\x15\x67\xC4\x8E\x7C\x6A\xE6\xE8\x66\x8D\x41\xBE\x4F\x1E\xD3\x6B\x6A\x9E\x3A\xCB\xAC\x2D\xED\x35\xF1\x30\x69\xC8\xC3\xB0\x23\xF1\x27\x8E\xC0\x5D\xFD\xBB\xD1\x95\xBE\x71\x63\x44\x32\xEE\x31\x95\x57\x5B\x2A\x83\x1F\xD6\xB5\x3E\x43\x35\x9F\x9C\x9C\x7C\xE7\xB8\x29\xF8\x51\x12\xBD\x96\xBD\x50\xF3\xF6\xB2\xE6\xBE\xA3\xAD\x95\x3C\x66\xA4\x2D\xCF\x15\x0A\xF9\x38\x4F\x47\xDC\x86\x4C\x13\xFA\xAA\x05\xD2\x9A\xF4\x26\x56\x25\x30\x93\xE9\x3D\xD2\x2E\xB7\xAF\x73\x86\x02\x6E\x59\x98\xCC\x50\xDE\x75\xDD\x1C\x99\xD5\x47\x9C\xA4\xC4\x95\x83\x34\x55\x49\x31\xEF\xC0\x6C"
+    },
+    RsaOaepTestVector {
+        primes: RSA_2048_PRIMES,
+        algorithm: rsa::Algorithm::OAEP_SHA512,
+        mgf1_hash_alg: rsa::mgf::Algorithm::SHA512,
+        label: b"yubihsm.rs test label",
+        msg: b"\x00\x01\x02",
+        ct: b"\xC2\xE4\xD7\x4D\x59\xE0\x66\x10\xBB\x09\xA2\x8C\xD6\x19\x19\x6A\xB3\x75\x94\x3A\xEB\x6C\xFA\x16\xB0\x1A\xF8\xBE\xFF\xAC\xCC\xE6\x75\x58\x97\x6F\x36\xFB\xA6\xBA\xF0\xED\x4C\x8D\x30\x44\x87\x09\xF2\xE3\x2A\xDA\xB6\xC4\x41\xA9\xB8\x97\xB5\x06\x8E\xE9\x3B\xFB\xD8\xF7\xD4\x68\x11\x4C\x0A\x63\x2F\x1C\xC6\x4E\xA8\x27\x32\xC2\xA2\xF5\x71\x85\xD5\xC1\x66\xEC\xFB\x46\x3E\xAF\xBA\xEB\x9B\x6D\x71\xAD\x6B\x9C\x9B\x7C\x90\x70\x0A\x0C\x21\x44\xA1\x82\xB7\x1E\xAD\xF0\x78\x4B\x77\x30\x20\x29\xD3\x34\x5B\x86\x77\x34\xD1\x5E\x54\xC4\x50\xAB\x19\xB1\x3E\x74\xA7\xA4\xC6\xD0\xAB\xC6\xC9\x2A\x66\x54\x32\x5D\xB0\x65\xBB\x78\x7F\x5D\x70\x43\xE9\x0B\xAF\x3D\x61\xAD\xB0\x8A\xED\xE0\x51\x3A\x2B\x5C\x5D\xEA\x84\x41\xAC\xD3\x45\x2A\xE2\xB8\xDC\x42\x77\x57\xAB\x23\xD0\x87\x15\x93\x51\xE7\x2D\x0E\x7A\x92\x41\x2B\x9A\xBE\x7F\x1E\x09\x8D\x7B\xD2\x13\xA9\x2F\xD4\x1C\x6A\xDB\x6B\xC3\x7B\x0C\x9A\xFC\x87\xFA\xC0\x0D\x5D\x90\x26\x6F\x93\xA6\x43\xCA\xE6\x66\x64\x37\xEE\x3D\xEF\x85\x68\x84\xBE\xFA\x0D\xAC\x48\xF6\x0D\xD5\x7B\x24\x42\x12\x38\x35\x51"
+    },
+];